@@ -53,6 +53,42 @@ fn cmp_short(c: &mut Criterion) {
     }
 }
 
+fn cmp_inline(c: &mut Criterion) {
+    let mut group = c.benchmark_group("cmp inline");
+    group.measurement_time(Duration::from_secs(3));
+
+    // NOTE: Inlined keys with a shared prefix that is longer than the stored prefix
+    let keys = [
+        "user:00000123",
+        "user:00000124",
+        "user:000001234",
+        "user:00000123",
+    ]
+    .into_iter()
+    .map(ByteView::from)
+    .collect::<Vec<_>>();
+
+    group.bench_function("cmp", |b| {
+        b.iter(|| {
+            for x in &keys {
+                for y in &keys {
+                    let _ = black_box(black_box(x).cmp(black_box(y)));
+                }
+            }
+        });
+    });
+
+    group.bench_function("eq", |b| {
+        b.iter(|| {
+            for x in &keys {
+                for y in &keys {
+                    let _ = black_box(black_box(x) == black_box(y));
+                }
+            }
+        });
+    });
+}
+
 fn cmp_long(c: &mut Criterion) {
     let mut group = c.benchmark_group("cmp long");
     group.measurement_time(Duration::from_secs(3));
@@ -294,6 +330,7 @@ criterion_group!(
     eq_short,
     eq_long,
     cmp_short,
+    cmp_inline,
     cmp_long,
 );
 criterion_main!(benches);
@@ -0,0 +1,212 @@
+// Copyright (c) 2024-present, fjall-rs
+// This source code is licensed under both the Apache 2.0 and MIT License
+// (found in the LICENSE-* files in the repository)
+
+use crate::{
+    byteview::{HeapKind, INLINE_SIZE},
+    source::SourceHeader,
+    ByteView, Error,
+};
+use allocator_api2::alloc::Allocator;
+use std::{alloc::Layout, ptr::NonNull};
+
+/// Header of a heap allocation made by a custom allocator
+///
+/// The data region directly follows the handle, inside the same allocation.
+#[repr(C)]
+struct AllocHandle<A> {
+    base: SourceHeader,
+    alloc: A,
+    layout: Layout,
+}
+
+unsafe fn release_alloc<A: Allocator>(heap: NonNull<u8>) {
+    let handle = heap.cast::<AllocHandle<A>>().as_ptr();
+
+    // NOTE: Move the allocator out of the allocation before freeing it
+    let alloc = std::ptr::read(std::ptr::addr_of!((*handle).alloc));
+    let layout = (*handle).layout;
+
+    alloc.deallocate(heap, layout);
+}
+
+impl ByteView {
+    /// Creates a new slice from an existing byte slice,
+    /// heap-allocating it using the given allocator.
+    ///
+    /// The allocator is kept alive until the last slice referencing
+    /// the heap allocation is dropped.
+    /// If the slice is short enough to be inlined, the allocator is not used.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use byteview::ByteView;
+    /// use allocator_api2::alloc::Global;
+    ///
+    /// let slice = ByteView::new_in(b"helloworld_thisisaverylongstring", Global);
+    /// assert_eq!(b"helloworld_thisisaverylongstring", &*slice);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length does not fit in a u32 (4 GiB).
+    #[must_use]
+    pub fn new_in<A: Allocator + Send + 'static>(slice: &[u8], alloc: A) -> Self {
+        Self::allocate_in(slice.len(), alloc, Some(slice))
+    }
+
+    /// Creates a new zeroed, fixed-length byteview,
+    /// heap-allocating it using the given allocator.
+    ///
+    /// See [`ByteView::new_in`].
+    ///
+    /// Use [`ByteView::get_mut`] to mutate the content.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length does not fit in a u32 (4 GiB).
+    #[must_use]
+    pub fn with_size_in<A: Allocator + Send + 'static>(slice_len: usize, alloc: A) -> Self {
+        Self::allocate_in(slice_len, alloc, None)
+    }
+
+    /// Allocates a slice using the given allocator, and copies `init` into it
+    /// (or zeroes it, if `init` is not given).
+    fn allocate_in<A: Allocator + Send + 'static>(
+        slice_len: usize,
+        alloc: A,
+        init: Option<&[u8]>,
+    ) -> Self {
+        match Self::try_allocate_in(slice_len, alloc, init) {
+            Ok(view) => view,
+            Err(Error::OutOfMemory(layout)) => std::alloc::handle_alloc_error(layout),
+            Err(e) => panic!("{e}"),
+        }
+    }
+
+    fn try_allocate_in<A: Allocator + Send + 'static>(
+        slice_len: usize,
+        alloc: A,
+        init: Option<&[u8]>,
+    ) -> Result<Self, Error> {
+        debug_assert!(init.map_or(true, |init| init.len() == slice_len));
+
+        if slice_len <= INLINE_SIZE {
+            return init.map_or_else(|| Self::try_with_size(slice_len), Self::try_new);
+        }
+
+        let Ok(len) = u32::try_from(slice_len) else {
+            return Err(Error::TooLong(slice_len));
+        };
+
+        let header_size = std::mem::size_of::<AllocHandle<A>>();
+        let alignment = std::mem::align_of::<AllocHandle<A>>();
+
+        let Ok(layout) = Layout::from_size_align(header_size + slice_len, alignment) else {
+            return Err(Error::TooLong(slice_len));
+        };
+
+        // IMPORTANT: Zero-allocate the region, unless we overwrite it anyway
+        let ptr = if init.is_some() {
+            alloc.allocate(layout)
+        } else {
+            alloc.allocate_zeroed(layout)
+        };
+        let Ok(ptr) = ptr else {
+            return Err(Error::OutOfMemory(layout));
+        };
+
+        let heap = ptr.cast::<u8>();
+
+        // SAFETY: The allocation fits the handle, followed by the data region
+        unsafe {
+            let data = heap.as_ptr().add(header_size);
+
+            if let Some(init) = init {
+                std::ptr::copy_nonoverlapping(init.as_ptr(), data, slice_len);
+            }
+
+            let mut base =
+                SourceHeader::new(HeapKind::Owned, header_size, None, release_alloc::<A>);
+            base.header.len = len;
+            base.data = data;
+
+            heap.cast::<AllocHandle<A>>().as_ptr().write(AllocHandle {
+                base,
+                alloc,
+                layout,
+            });
+
+            crate::hooks::on_alloc(layout.size());
+
+            Ok(Self::from_heap_parts(heap, data, len))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ByteView;
+    use allocator_api2::alloc::{AllocError, Allocator, Global};
+    use std::{
+        alloc::Layout,
+        ptr::NonNull,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+    };
+
+    #[derive(Clone, Default)]
+    struct Counting(Arc<AtomicUsize>);
+
+    unsafe impl Allocator for Counting {
+        fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+            self.0.fetch_add(1, Ordering::Relaxed);
+            Global.allocate(layout)
+        }
+
+        unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+            self.0.fetch_sub(1, Ordering::Relaxed);
+            Global.deallocate(ptr, layout);
+        }
+    }
+
+    #[test]
+    fn new_in() {
+        let alloc = Counting::default();
+
+        let slice = ByteView::new_in(b"helloworld_thisisaverylongstring", alloc.clone());
+        assert_eq!(b"helloworld_thisisaverylongstring", &*slice);
+        assert_eq!(ByteView::from("helloworld_thisisaverylongstring"), slice);
+        assert_eq!(1, alloc.0.load(Ordering::Relaxed));
+
+        let copy = slice.slice(5..);
+        drop(slice);
+        assert_eq!(b"world_thisisaverylongstring", &*copy);
+        assert_eq!(1, alloc.0.load(Ordering::Relaxed));
+
+        drop(copy);
+        assert_eq!(0, alloc.0.load(Ordering::Relaxed));
+        assert_eq!(1, Arc::strong_count(&alloc.0));
+    }
+
+    #[test]
+    fn with_size_in() {
+        let alloc = Counting::default();
+
+        let mut slice = ByteView::with_size_in(32, alloc.clone());
+        assert_eq!([0; 32], *slice);
+
+        slice.get_mut().unwrap()[0] = 1;
+        assert_eq!(1, slice[0]);
+
+        let inline = ByteView::with_size_in(4, alloc.clone());
+        assert!(inline.is_inline());
+        assert_eq!(1, alloc.0.load(Ordering::Relaxed));
+
+        drop(slice);
+        assert_eq!(0, alloc.0.load(Ordering::Relaxed));
+    }
+}
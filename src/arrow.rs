@@ -0,0 +1,313 @@
+// Copyright (c) 2024-present, fjall-rs
+// This source code is licensed under both the Apache 2.0 and MIT License
+// (found in the LICENSE-* files in the repository)
+
+//! Conversions between slices and Apache Arrow's `BinaryView`/`Utf8View` arrays
+//!
+//! Arrow stores short values (up to 12 bytes) inside a 16-byte view,
+//! and longer values in data buffers that the views point into.
+//! Both directions share the data buffers instead of copying them:
+//!
+//! - Slices are packed like in a [`crate::ViewArray`], whose data buffers
+//!   keep the slices' heap allocations alive.
+//! - Every referenced data buffer of the array becomes a shared source (see [`ByteView::from_source`])
+//!   that the returned slices reference. Values in data buffers larger than 4 GiB
+//!   each become their own source instead.
+//!
+//! Short values are copied, because they are inlined on both sides anyway.
+//!
+//! ```
+//! # use byteview::{arrow, ByteView};
+//! use arrow_array::BinaryViewArray;
+//!
+//! let array = BinaryViewArray::from(vec![
+//!     Some(&b"helloworld_thisisaverylongstring"[..]),
+//!     None,
+//!     Some(b"abc"),
+//! ]);
+//!
+//! let slices = arrow::from_binary_view_array(&array);
+//! assert_eq!(Some(ByteView::from("abc")), slices[2]);
+//!
+//! // NOTE: The long value references the array's data buffer
+//! assert_eq!(array.value(0).as_ptr(), slices[0].as_ref().unwrap().as_ptr());
+//! ```
+
+use crate::{ByteSource, ByteView, StrView};
+use arrow_array::{
+    types::ByteViewType, Array, BinaryViewArray, GenericByteViewArray, StringViewArray,
+};
+
+/// Maximum length of values that Arrow stores inside the view itself
+const ARROW_INLINE_SIZE: usize = 12;
+
+/// Converts slices into a [`BinaryViewArray`], without copying long values.
+///
+/// Slices that share the same heap allocation share the same data buffer (see [`crate::ViewArray`]).
+///
+/// # Examples
+///
+/// ```
+/// # use byteview::{arrow, ByteView};
+/// let buffer = ByteView::from("helloworld_thisisaverylongstring_helloworld_thisisanotherlongstring");
+/// let slices = vec![buffer.slice(..32), buffer.slice(33..)];
+///
+/// let array = arrow::to_binary_view_array(&slices);
+/// assert_eq!(1, array.data_buffers().len());
+/// assert_eq!(b"helloworld_thisisanotherlongstring", array.value(1));
+/// ```
+#[cfg(not(feature = "local"))]
+#[must_use]
+pub fn to_binary_view_array(views: &[ByteView]) -> BinaryViewArray {
+    // SAFETY: Binary views do not need to be valid UTF-8
+    unsafe { to_array(views.iter()) }
+}
+
+/// Converts strings into a [`StringViewArray`], without copying long values.
+///
+/// Strings that share the same heap allocation share the same data buffer (see [`crate::ViewArray`]).
+#[cfg(not(feature = "local"))]
+#[must_use]
+pub fn to_string_view_array(strs: &[StrView]) -> StringViewArray {
+    // SAFETY: String views are valid UTF-8
+    unsafe { to_array(strs.iter().map(StrView::as_view)) }
+}
+
+/// Converts a [`BinaryViewArray`] into slices, without copying long values.
+///
+/// Null values are returned as `None`.
+#[must_use]
+pub fn from_binary_view_array(array: &BinaryViewArray) -> Vec<Option<ByteView>> {
+    from_array(array)
+}
+
+/// Converts a [`StringViewArray`] into strings, without copying long values.
+///
+/// Null values are returned as `None`.
+#[must_use]
+pub fn from_string_view_array(array: &StringViewArray) -> Vec<Option<StrView>> {
+    from_array(array)
+        .into_iter()
+        // SAFETY: String view arrays are validated to contain UTF-8 when they are created
+        .map(|view| view.map(|view| unsafe { StrView::from_raw(view) }))
+        .collect()
+}
+
+/// Builds an array from the given slices.
+///
+/// # Safety
+///
+/// The slices need to be valid values of `T` (i.e. UTF-8 for string views).
+#[cfg(not(feature = "local"))]
+unsafe fn to_array<'a, T: ByteViewType + ?Sized>(
+    views: impl Iterator<Item = &'a ByteView>,
+) -> GenericByteViewArray<T> {
+    use arrow_buffer::{Buffer, ScalarBuffer};
+    use std::{ptr::NonNull, sync::Arc};
+
+    let mut array = crate::ViewArray::new();
+
+    for view in views {
+        array.push(view);
+    }
+
+    let buffers = array
+        .buffers
+        .iter()
+        .map(|buffer| {
+            let owner = Arc::new(buffer.clone());
+
+            // SAFETY: The owner keeps the bytes alive (and never mutates them),
+            // and does not move, because it is behind an `Arc`
+            Buffer::from_custom_allocation(NonNull::from(&**owner).cast::<u8>(), owner.len(), owner)
+        })
+        .collect::<Vec<_>>();
+
+    let views = array
+        .views
+        .iter()
+        .map(|view| view.to_u128())
+        .collect::<ScalarBuffer<u128>>();
+
+    // SAFETY: The views use the same layout as Arrow's, and the caller guarantees
+    // that the values are valid
+    GenericByteViewArray::new_unchecked(views, buffers, None)
+}
+
+/// Keeps an Arrow data buffer alive for the slices referencing it
+struct DataBuffer(arrow_buffer::Buffer);
+
+impl ByteSource for DataBuffer {
+    fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+fn from_array<T: ByteViewType + ?Sized>(array: &GenericByteViewArray<T>) -> Vec<Option<ByteView>> {
+    let data_buffers = array.data_buffers();
+
+    // NOTE: Sources are only created for buffers that are actually referenced
+    let mut buffers = vec![None::<ByteView>; data_buffers.len()];
+
+    array
+        .views()
+        .iter()
+        .enumerate()
+        .map(|(idx, &raw)| {
+            if array.is_null(idx) {
+                return None;
+            }
+
+            let bytes = raw.to_le_bytes();
+
+            #[allow(clippy::cast_possible_truncation)]
+            let len = raw as u32 as usize;
+
+            if len <= ARROW_INLINE_SIZE {
+                return Some(ByteView::new(bytes.get(4..4 + len).unwrap_or_default()));
+            }
+
+            #[allow(clippy::cast_possible_truncation)]
+            let buffer_idx = (raw >> 64) as u32 as usize;
+
+            #[allow(clippy::cast_possible_truncation)]
+            let offset = (raw >> 96) as u32 as usize;
+
+            let (Some(data), Some(buffer)) =
+                (data_buffers.get(buffer_idx), buffers.get_mut(buffer_idx))
+            else {
+                unreachable!("view arrays are validated when created");
+            };
+
+            // NOTE: Arrow data buffers may be larger than a slice can be (4 GiB),
+            // in which case only the referenced value becomes a source
+            if u32::try_from(data.len()).is_err() {
+                return Some(ByteView::from_source(DataBuffer(
+                    data.slice_with_length(offset, len),
+                )));
+            }
+
+            let buffer =
+                buffer.get_or_insert_with(|| ByteView::from_source(DataBuffer(data.clone())));
+
+            Some(buffer.slice(offset..offset + len))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{from_binary_view_array, from_string_view_array};
+    use crate::{ByteView, StrView};
+    use arrow_array::{BinaryViewArray, StringViewArray};
+
+    #[test]
+    #[cfg(not(feature = "local"))]
+    fn arrow_roundtrip() {
+        use super::{to_binary_view_array, to_string_view_array};
+        use arrow_array::Array;
+
+        let buffer =
+            ByteView::from("helloworld_thisisaverylongstring_helloworld_thisisanotherlongstring");
+        let other = ByteView::from("helloworld_thisisaverylongstring");
+
+        let slices = vec![
+            buffer.slice(..32),
+            ByteView::from("abc"),
+            other.clone(),
+            ByteView::from("helloworld_thisis"),
+            buffer.slice(33..),
+            ByteView::from_static(b"helloworld_thisisastaticstring"),
+            ByteView::from(""),
+        ];
+
+        let array = to_binary_view_array(&slices);
+        assert_eq!(slices.len(), array.len());
+        assert_eq!(4, array.data_buffers().len());
+        assert_eq!(
+            slices.iter().map(|view| &**view).collect::<Vec<_>>(),
+            array.iter().map(Option::unwrap).collect::<Vec<_>>(),
+        );
+
+        // NOTE: The array shares the slices' heap allocations
+        assert_eq!(4, buffer.ref_count());
+        assert!(array
+            .data_buffers()
+            .iter()
+            .any(|data| data.as_ptr() == other.as_ptr()));
+
+        let views = from_binary_view_array(&array);
+        assert_eq!(
+            slices,
+            views.into_iter().map(Option::unwrap).collect::<Vec<_>>(),
+        );
+
+        let strs = vec![
+            StrView::new("helloworld_thisisaverylongstring"),
+            StrView::new("abc"),
+        ];
+        let array = to_string_view_array(&strs);
+        assert_eq!("helloworld_thisisaverylongstring", array.value(0));
+        assert_eq!(
+            strs,
+            from_string_view_array(&array)
+                .into_iter()
+                .map(Option::unwrap)
+                .collect::<Vec<_>>(),
+        );
+    }
+
+    #[test]
+    fn arrow_nulls() {
+        let array = BinaryViewArray::from(vec![
+            Some(&b"helloworld_thisisaverylongstring"[..]),
+            None,
+            Some(b"abc"),
+        ]);
+
+        let views = from_binary_view_array(&array);
+        assert_eq!(
+            vec![
+                Some(ByteView::from("helloworld_thisisaverylongstring")),
+                None,
+                Some(ByteView::from("abc")),
+            ],
+            views,
+        );
+
+        let long = views[0].as_ref().unwrap();
+        assert_eq!(array.value(0).as_ptr(), long.as_ptr());
+
+        let array = StringViewArray::from(vec![None, Some("helloworld_thisisaverylongstring")]);
+        assert_eq!(
+            vec![None, Some(StrView::new("helloworld_thisisaverylongstring"))],
+            from_string_view_array(&array),
+        );
+    }
+
+    #[test]
+    fn arrow_unreferenced_buffer() {
+        use arrow_buffer::Buffer;
+
+        let array = BinaryViewArray::from(vec![
+            &b"helloworld_thisisaverylongstring"[..],
+            b"helloworld_thisisanotherlongstring",
+        ]);
+        assert_eq!(1, array.data_buffers().len());
+
+        let mut buffers = array.data_buffers().to_vec();
+        buffers.push(Buffer::from_vec(vec![0_u8; 64]));
+        let array = BinaryViewArray::new(array.views().clone(), buffers, None);
+
+        let views = from_binary_view_array(&array);
+        assert_eq!(2, views.len());
+
+        // NOTE: Both values share the source of the first buffer
+        let a = views[0].as_ref().unwrap();
+        assert_eq!(2, a.ref_count());
+        assert_eq!(
+            b"helloworld_thisisanotherlongstring",
+            &**views[1].as_ref().unwrap()
+        );
+    }
+}
@@ -2,40 +2,194 @@
 // This source code is licensed under both the Apache 2.0 and MIT License
 // (found in the LICENSE-* files in the repository)
 
+use crate::{
+    refcount::{fence, spin_loop, RefCount},
+    Error,
+};
 use std::{
+    io::IoSlice,
     mem::ManuallyDrop,
     ops::Deref,
-    sync::{
-        atomic::{AtomicU64, Ordering},
-        Arc,
-    },
+    ptr::NonNull,
+    sync::{atomic::Ordering, Arc},
 };
 
 #[cfg(target_pointer_width = "64")]
-const INLINE_SIZE: usize = 20;
+pub const INLINE_SIZE: usize = 23;
 
 #[cfg(target_pointer_width = "32")]
-const INLINE_SIZE: usize = 16;
+pub const INLINE_SIZE: usize = 19;
 
 const PREFIX_SIZE: usize = 4;
 
+/// Tag of a slice that is stored in a [`HeapKind::Global`] heap allocation
+const TAG_GLOBAL: u8 = 0xFF;
+
+/// Tag of a slice that references static data
+const TAG_STATIC: u8 = 0xFE;
+
+/// Tag of a slice that is stored in a [`crate::ByteSource`] handle
+const TAG_SOURCE: u8 = 0xFD;
+
+/// Describes where the data region of a heap allocation lives,
+/// and how to release it
+#[repr(u8)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum HeapKind {
+    /// The data region follows the header in the same global allocation
+    Global,
+
+    /// The header is part of a handle to a custom [`crate::ByteSource`]
+    Source,
+
+    /// The header is part of a handle to a buffer of a custom allocator,
+    /// which may be mutated while uniquely referenced
+    #[cfg(feature = "allocator-api2")]
+    Owned,
+
+    /// The header is part of a handle to an adopted `Vec<u8>`,
+    /// which may be mutated while uniquely referenced,
+    /// and taken back out by [`ByteView::into_vec`]
+    Vec,
+}
+
+/// Ref counts above this are considered to be leaked, see [`Arc`]
+const MAX_REFCOUNT: u64 = i64::MAX as u64;
+
 #[repr(C)]
-struct HeapAllocationHeader {
-    ref_count: AtomicU64,
+pub struct HeapAllocationHeader {
+    pub(crate) ref_count: RefCount,
+
+    /// Amount of weak references, plus one that is shared by all strong references (like [`Arc`])
+    ///
+    /// The heap allocation is freed once this drops to zero.
+    pub(crate) weak_count: RefCount,
+
+    /// Length of the data region (its capacity)
+    ///
+    /// Slices may be shorter than their backing allocation,
+    /// so we need to remember the original length to deallocate it,
+    /// and to know how far a uniquely referenced slice can grow in place.
+    pub(crate) len: u32,
+
+    pub(crate) kind: HeapKind,
+
+    /// xxh3 checksum of the data region, if constructed with [`ByteView::new_checksummed`]
+    #[cfg(feature = "checksum")]
+    pub(crate) checksum: Option<u64>,
+
+    /// xxh3 hash of the data region, cached by [`ByteView::full_hash`] (0 if not computed yet)
+    #[cfg(feature = "hash-cache")]
+    pub(crate) hash: std::sync::atomic::AtomicU64,
+}
+
+impl HeapAllocationHeader {
+    /// Increments the ref count by `n`.
+    ///
+    /// Aborts the process if the ref count grows absurdly large (like [`Arc`] does),
+    /// because a wrapped ref count would cause a use-after-free.
+    pub(crate) fn increment_ref_count(&self, n: u64) {
+        // NOTE: Relaxed is enough (like in `Arc::clone`), because new references
+        // can only be created from an existing one, which keeps the allocation alive
+        let rc_before = self.ref_count.fetch_add(n, Ordering::Relaxed);
+
+        if rc_before > MAX_REFCOUNT || n > MAX_REFCOUNT - rc_before {
+            std::process::abort();
+        }
+    }
+
+    /// Increments the ref count, unless it already dropped to zero.
+    ///
+    /// Returns `false` if there are no strong references left.
+    pub(crate) fn try_increment_ref_count(&self) -> bool {
+        let mut rc = self.ref_count.load(Ordering::Relaxed);
+
+        loop {
+            if rc == 0 {
+                return false;
+            }
+
+            if rc > MAX_REFCOUNT {
+                std::process::abort();
+            }
+
+            match self.ref_count.compare_exchange_weak(
+                rc,
+                rc + 1,
+                Ordering::Acquire,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => return true,
+                Err(current) => rc = current,
+            }
+        }
+    }
+
+    /// Increments the weak count.
+    ///
+    /// Spins while the weak count is locked by [`HeapAllocationHeader::is_unique`].
+    pub(crate) fn increment_weak_count(&self) {
+        loop {
+            let wc = self.weak_count.load(Ordering::Relaxed);
+
+            if wc == u64::MAX {
+                spin_loop();
+                continue;
+            }
+
+            if wc > MAX_REFCOUNT {
+                std::process::abort();
+            }
+
+            if self
+                .weak_count
+                .compare_exchange_weak(wc, wc + 1, Ordering::Acquire, Ordering::Relaxed)
+                .is_ok()
+            {
+                return;
+            }
+        }
+    }
+
+    /// Returns `true` if there is a single strong reference, and no weak references.
+    pub(crate) fn is_unique(&self) -> bool {
+        // NOTE: Lock the weak count (like `Arc::is_unique` does),
+        // so no weak reference can be created while we check the ref count
+        if self
+            .weak_count
+            .compare_exchange(1, u64::MAX, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            return false;
+        }
+
+        let unique = self.ref_count.load(Ordering::Acquire) == 1;
+        self.weak_count.store(1, Ordering::Release);
+        unique
+    }
 }
 
+/// Inlined slice
+///
+/// The length doubles as the tag of the slice, so it is never larger than [`INLINE_SIZE`].
 #[repr(C)]
 struct ShortRepr {
-    len: u32,
+    len: u8,
     data: [u8; INLINE_SIZE],
 }
 
+/// Slice of a heap allocation (or static data)
+///
+/// Only stores the base pointer, so the data pointer is computed
+/// from it and the offset, which frees up space for inlining.
 #[repr(C)]
 struct LongRepr {
-    len: u32,
+    tag: u8,
     prefix: [u8; PREFIX_SIZE],
-    heap: *const u8,
-    data: *const u8,
+    _pad: [u8; 3],
+    len: u32,
+    offset: u32,
+    heap: NonNull<u8>,
 }
 
 #[repr(C)]
@@ -58,7 +212,7 @@ impl Default for Trailer {
 /// An immutable byte slice
 ///
 /// Will be inlined (no pointer dereference or heap allocation)
-/// if it is 20 characters or shorter (on a 64-bit system).
+/// if it is 23 characters or shorter (on a 64-bit system).
 ///
 /// A single heap allocation will be shared between multiple slices.
 /// Even subslices of that heap allocation can be cloned without additional heap allocation.
@@ -76,11 +230,33 @@ pub struct ByteView {
     trailer: Trailer,
 }
 
+#[cfg(not(feature = "local"))]
 #[allow(clippy::non_send_fields_in_send_ty)]
 unsafe impl Send for ByteView {}
+#[cfg(not(feature = "local"))]
 #[allow(clippy::non_send_fields_in_send_ty)]
 unsafe impl Sync for ByteView {}
 
+/// The raw representation of a [`ByteView`], see [`ByteView::into_raw`]
+///
+/// The parts are plain bytes that can be copied freely, but they own
+/// the slice's reference to its heap allocation (if any),
+/// so they need to be turned back into a slice exactly once using [`ByteView::from_raw`].
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct RawParts {
+    bytes: [std::mem::MaybeUninit<u8>; std::mem::size_of::<ByteView>()],
+    _align: [usize; 0],
+}
+
+const _: () = assert!(std::mem::align_of::<RawParts>() >= std::mem::align_of::<ByteView>());
+
+impl std::fmt::Debug for RawParts {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RawParts").finish_non_exhaustive()
+    }
+}
+
 impl Clone for ByteView {
     fn clone(&self) -> Self {
         self.slice(..)
@@ -89,25 +265,140 @@ impl Clone for ByteView {
 
 impl Drop for ByteView {
     fn drop(&mut self) {
-        if self.is_inline() {
-            return;
+        self.release_strong();
+
+        // NOTE: Inlined data (and the prefix) are stored in the slice itself
+        #[cfg(feature = "zeroize")]
+        // SAFETY: The slice is not used anymore, and any byte pattern is a valid MaybeUninit<u8>
+        unsafe {
+            ::zeroize::Zeroize::zeroize(
+                &mut *std::ptr::addr_of_mut!(*self)
+                    .cast::<[std::mem::MaybeUninit<u8>; std::mem::size_of::<Self>()]>(),
+            );
         }
+    }
+}
 
-        let heap_region = self.get_heap_region();
+impl ByteView {
+    /// Releases the slice's strong reference, dropping the data if it was the last one.
+    fn release_strong(&self) {
+        let Some(heap_region) = self.heap_region() else {
+            return;
+        };
 
-        if heap_region.ref_count.fetch_sub(1, Ordering::AcqRel) != 1 {
+        // NOTE: Release all our accesses to the data before (maybe) giving up the allocation
+        if heap_region.ref_count.fetch_sub(1, Ordering::Release) != 1 {
             return;
         }
 
+        // NOTE: Synchronize with the decrements of all other references (like `Arc::drop`),
+        // so their accesses happen before we drop the data
+        fence(Ordering::Acquire);
+
+        // SAFETY: We were the last strong reference
         unsafe {
-            let header_size = std::mem::size_of::<HeapAllocationHeader>();
-            let alignment = std::mem::align_of::<HeapAllocationHeader>();
-            let total_size = header_size + self.len();
-            let layout = std::alloc::Layout::from_size_align(total_size, alignment).unwrap();
+            #[cfg(feature = "zeroize")]
+            self.wipe_data_region();
+
+            if heap_region.kind != HeapKind::Global {
+                crate::source::drop_source(self.trailer.long.heap);
+            }
+
+            // NOTE: Release the weak reference that is shared by all strong references
+            self.release_weak();
+        }
+    }
+}
+
+impl ByteView {
+    /// Overwrites the whole data region with zeros.
+    ///
+    /// Data regions of custom sources are left untouched, as they are not owned by the slice.
+    ///
+    /// # Safety
+    ///
+    /// Shall only be called once no strong references are left.
+    #[cfg(feature = "zeroize")]
+    unsafe fn wipe_data_region(&self) {
+        let Some(heap_region) = self.heap_region() else {
+            return;
+        };
+
+        if heap_region.kind == HeapKind::Source {
+            return;
+        }
+
+        // NOTE: The spare capacity of the data region may be uninitialized
+        let data = self
+            .data_base()
+            .cast_mut()
+            .cast::<std::mem::MaybeUninit<u8>>();
+
+        ::zeroize::Zeroize::zeroize(std::slice::from_raw_parts_mut(
+            data,
+            heap_region.len as usize,
+        ));
+    }
+
+    /// Releases a weak reference, freeing the heap allocation if it was the last one.
+    ///
+    /// # Safety
+    ///
+    /// Shall only be called once per weak reference.
+    pub(crate) unsafe fn release_weak(&self) {
+        let Some(heap_region) = self.heap_region() else {
+            return;
+        };
+
+        if heap_region.weak_count.fetch_sub(1, Ordering::Release) != 1 {
+            return;
+        }
+
+        fence(Ordering::Acquire);
+
+        crate::hooks::on_dealloc(self.heap_size());
 
-            let ptr = self.trailer.long.heap.cast_mut();
-            std::alloc::dealloc(ptr, layout);
+        if heap_region.kind != HeapKind::Global {
+            crate::source::release(self.trailer.long.heap);
+            return;
         }
+
+        let header_size = std::mem::size_of::<HeapAllocationHeader>();
+        let alignment = std::mem::align_of::<HeapAllocationHeader>();
+        let total_size = header_size + heap_region.len as usize;
+        let Ok(layout) = std::alloc::Layout::from_size_align(total_size, alignment) else {
+            unreachable!("layout was valid when allocating");
+        };
+
+        std::alloc::dealloc(self.trailer.long.heap.as_ptr(), layout);
+    }
+}
+
+/// Compares the bytes of (usually long) slices for equality.
+#[inline]
+fn bytes_eq(a: &[u8], b: &[u8]) -> bool {
+    #[cfg(feature = "simd")]
+    {
+        crate::simd::eq(a, b)
+    }
+
+    #[cfg(not(feature = "simd"))]
+    {
+        a == b
+    }
+}
+
+/// Compares the bytes of (usually long) slices lexicographically.
+#[inline]
+fn bytes_cmp(a: &[u8], b: &[u8]) -> std::cmp::Ordering {
+    #[cfg(feature = "simd")]
+    {
+        crate::simd::cmp(a, b)
+    }
+
+    #[cfg(not(feature = "simd"))]
+    {
+        a.cmp(b)
     }
 }
 
@@ -115,13 +406,26 @@ impl Eq for ByteView {}
 
 impl std::cmp::PartialEq for ByteView {
     fn eq(&self, other: &Self) -> bool {
-        unsafe {
-            let src_ptr = (self as *const Self).cast::<u8>();
-            let other_ptr: *const u8 = (other as *const Self).cast::<u8>();
+        if self.is_inline() && other.is_inline() {
+            // NOTE: Unused bytes of inlined slices are always zeroed,
+            // so the slices are equal if all their words (including the length) are
+            return self.raw_words() == other.raw_words();
+        }
 
-            let a = *src_ptr.cast::<u64>();
-            let b = *other_ptr.cast::<u64>();
+        if self.len() != other.len() {
+            return false;
+        }
+
+        // SAFETY: Both trailer layouts have the prefix stored at the same position,
+        // and unused bytes of inlined slices are always zeroed
+        unsafe {
+            if self.trailer.long.prefix != other.trailer.long.prefix {
+                return false;
+            }
+        }
 
+        #[cfg(feature = "hash-cache")]
+        if let (Some(a), Some(b)) = (self.cached_hash(), other.cached_hash()) {
             if a != b {
                 return false;
             }
@@ -129,31 +433,33 @@ impl std::cmp::PartialEq for ByteView {
 
         // NOTE: At this point we know
         // both strings must have the same prefix and same length
-        //
-        // If we are inlined, the other string must be inlined too,
-        // so checking the prefix is enough
-        if self.is_inline() {
-            self.get_short_slice() == other.get_short_slice()
-        } else {
-            self.get_long_slice() == other.get_long_slice()
-        }
+        bytes_eq(self.get_slice(), other.get_slice())
     }
 }
 
 impl std::cmp::Ord for ByteView {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        if self.is_inline() && other.is_inline() {
+            let [a0, a1, a2] = self.inline_words();
+            let [b0, b1, b2] = other.inline_words();
+
+            // NOTE: Unused bytes of inlined slices are always zeroed,
+            // so if all words are equal, the shorter slice is a prefix of the other one
+            return a0
+                .cmp(&b0)
+                .then(a1.cmp(&b1))
+                .then(a2.cmp(&b2))
+                .then(self.len().cmp(&other.len()));
+        }
+
         self.prefix().cmp(other.prefix()).then_with(|| {
             let this_len = self.len();
             let other_len = other.len();
 
             if this_len <= 4 && other_len <= 4 {
                 this_len.cmp(&other_len)
-            } else if self.is_inline() && other.is_inline() {
-                let a = self.get_short_slice();
-                let b = other.get_short_slice();
-                a.cmp(b)
             } else {
-                self.deref().cmp(&**other)
+                bytes_cmp(self, other)
             }
         })
     }
@@ -165,9 +471,96 @@ impl std::cmp::PartialOrd for ByteView {
     }
 }
 
+macro_rules! impl_cmp_bytes {
+    ($($ty:ty),*) => {
+        $(
+            impl std::cmp::PartialEq<$ty> for ByteView {
+                fn eq(&self, other: &$ty) -> bool {
+                    bytes_eq(self.get_slice(), AsRef::<[u8]>::as_ref(other))
+                }
+            }
+
+            impl std::cmp::PartialEq<ByteView> for $ty {
+                fn eq(&self, other: &ByteView) -> bool {
+                    other == self
+                }
+            }
+
+            impl std::cmp::PartialOrd<$ty> for ByteView {
+                fn partial_cmp(&self, other: &$ty) -> Option<std::cmp::Ordering> {
+                    Some(bytes_cmp(self.get_slice(), AsRef::<[u8]>::as_ref(other)))
+                }
+            }
+
+            impl std::cmp::PartialOrd<ByteView> for $ty {
+                fn partial_cmp(&self, other: &ByteView) -> Option<std::cmp::Ordering> {
+                    other.partial_cmp(self).map(std::cmp::Ordering::reverse)
+                }
+            }
+        )*
+    };
+}
+
+impl_cmp_bytes!([u8], &[u8], Vec<u8>, str, &str);
+
 impl std::fmt::Debug for ByteView {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}", &**self)
+        write!(f, "{:?}", self.display_escaped())
+    }
+}
+
+/// Writes the bytes as hex digits.
+///
+/// The alternate flag (`#`) adds a `0x` prefix, and the precision limits
+/// the amount of bytes that are written, so huge slices can be logged safely.
+/// If bytes were left out, `...` is appended.
+fn fmt_hex(bytes: &[u8], f: &mut std::fmt::Formatter<'_>, upper: bool) -> std::fmt::Result {
+    let limit = f.precision().unwrap_or(bytes.len());
+
+    if f.alternate() {
+        f.write_str("0x")?;
+    }
+
+    for byte in bytes.iter().take(limit) {
+        if upper {
+            write!(f, "{byte:02X}")?;
+        } else {
+            write!(f, "{byte:02x}")?;
+        }
+    }
+
+    if bytes.len() > limit {
+        f.write_str("...")?;
+    }
+
+    Ok(())
+}
+
+impl std::fmt::LowerHex for ByteView {
+    /// Formats the slice as lowercase hex digits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use byteview::ByteView;
+    /// let slice = ByteView::from(&[0xDE, 0xAD, 0xBE, 0xEF][..]);
+    /// assert_eq!("deadbeef", format!("{slice:x}"));
+    /// assert_eq!("0xdeadbeef", format!("{slice:#x}"));
+    ///
+    /// // NOTE: The precision limits the amount of bytes that are written
+    /// assert_eq!("dead...", format!("{slice:.2x}"));
+    /// ```
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt_hex(self, f, false)
+    }
+}
+
+impl std::fmt::UpperHex for ByteView {
+    /// Formats the slice as uppercase hex digits.
+    ///
+    /// See [`std::fmt::LowerHex`] for the supported flags.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt_hex(self, f, true)
     }
 }
 
@@ -175,11 +568,19 @@ impl Deref for ByteView {
     type Target = [u8];
 
     fn deref(&self) -> &Self::Target {
-        if self.is_inline() {
-            self.get_short_slice()
-        } else {
-            self.get_long_slice()
-        }
+        #[cfg(all(feature = "verify-on-deref", debug_assertions))]
+        assert!(self.verify(), "byte slice does not match its checksum");
+
+        self.get_slice()
+    }
+}
+
+impl<I: std::slice::SliceIndex<[u8]>> std::ops::Index<I> for ByteView {
+    type Output = I::Output;
+
+    /// Indexes into the slice, with the same semantics (and panics) as indexing a `[u8]`.
+    fn index(&self, index: I) -> &Self::Output {
+        std::ops::Index::index(&**self, index)
     }
 }
 
@@ -197,7 +598,7 @@ impl std::ops::Deref for Mutator<'_> {
     type Target = [u8];
 
     fn deref(&self) -> &Self::Target {
-        self.0
+        self.0.get_slice()
     }
 }
 
@@ -207,10 +608,124 @@ impl std::ops::DerefMut for Mutator<'_> {
     }
 }
 
+macro_rules! impl_put {
+    ($($name:ident, $ty:ty, $conv:ident, $endian:literal;)*) => {
+        $(
+            #[doc = concat!("Writes a ", $endian, "`", stringify!($ty), "` at the given offset.")]
+            ///
+            /// # Panics
+            ///
+            /// Panics if the value does not fit into the slice at the given offset.
+            pub fn $name(&mut self, offset: usize, value: $ty) {
+                self.put_slice(offset, &value.$conv());
+            }
+        )*
+    };
+}
+
+impl Mutator<'_> {
+    /// Copies the given bytes into the slice, starting at `offset`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use byteview::ByteView;
+    /// let mut slice = ByteView::with_size(8);
+    ///
+    /// {
+    ///     let mut mutator = slice.get_mut().unwrap();
+    ///     mutator.put_slice(0, b"abc");
+    ///     mutator.put_u32_le(4, 1);
+    /// }
+    ///
+    /// assert_eq!(b"abc\0\x01\0\0\0", &*slice);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the bytes do not fit into the slice at the given offset.
+    pub fn put_slice(&mut self, offset: usize, bytes: &[u8]) {
+        let len = self.len();
+
+        let Some(dst) = offset
+            .checked_add(bytes.len())
+            .and_then(|end| self.get_mut(offset..end))
+        else {
+            panic!(
+                "write out of bounds: {offset:?} + {:?} > {len:?}",
+                bytes.len(),
+            );
+        };
+
+        dst.copy_from_slice(bytes);
+    }
+
+    impl_put! {
+        put_u8, u8, to_le_bytes, "";
+        put_i8, i8, to_le_bytes, "";
+        put_u16_le, u16, to_le_bytes, "little-endian ";
+        put_u16_be, u16, to_be_bytes, "big-endian ";
+        put_i16_le, i16, to_le_bytes, "little-endian ";
+        put_i16_be, i16, to_be_bytes, "big-endian ";
+        put_u32_le, u32, to_le_bytes, "little-endian ";
+        put_u32_be, u32, to_be_bytes, "big-endian ";
+        put_i32_le, i32, to_le_bytes, "little-endian ";
+        put_i32_be, i32, to_be_bytes, "big-endian ";
+        put_u64_le, u64, to_le_bytes, "little-endian ";
+        put_u64_be, u64, to_be_bytes, "big-endian ";
+        put_i64_le, i64, to_le_bytes, "little-endian ";
+        put_i64_be, i64, to_be_bytes, "big-endian ";
+        put_u128_le, u128, to_le_bytes, "little-endian ";
+        put_u128_be, u128, to_be_bytes, "big-endian ";
+        put_f32_le, f32, to_le_bytes, "little-endian ";
+        put_f32_be, f32, to_be_bytes, "big-endian ";
+        put_f64_le, f64, to_le_bytes, "little-endian ";
+        put_f64_be, f64, to_be_bytes, "big-endian ";
+    }
+}
+
 impl Drop for Mutator<'_> {
     fn drop(&mut self) {
         self.0.update_prefix();
+
+        #[cfg(feature = "checksum")]
+        self.0.update_checksum(false);
+
+        #[cfg(feature = "hash-cache")]
+        self.0.invalidate_hash();
+    }
+}
+
+#[cfg(unix)]
+fn read_exact_at(file: &std::fs::File, buf: &mut [u8], offset: u64) -> std::io::Result<()> {
+    std::os::unix::fs::FileExt::read_exact_at(file, buf, offset)
+}
+
+#[cfg(windows)]
+fn read_exact_at(file: &std::fs::File, mut buf: &mut [u8], mut offset: u64) -> std::io::Result<()> {
+    use std::os::windows::fs::FileExt;
+
+    while !buf.is_empty() {
+        match file.seek_read(buf, offset) {
+            Ok(0) => return Err(std::io::ErrorKind::UnexpectedEof.into()),
+            Ok(n) => {
+                buf = buf.get_mut(n..).unwrap_or_default();
+                offset += n as u64;
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
     }
+
+    Ok(())
+}
+
+#[cfg(not(any(unix, windows)))]
+fn read_exact_at(mut file: &std::fs::File, buf: &mut [u8], offset: u64) -> std::io::Result<()> {
+    use std::io::{Read, Seek, SeekFrom};
+
+    file.seek(SeekFrom::Start(offset))?;
+    file.read_exact(buf)
 }
 
 impl ByteView {
@@ -221,15 +736,28 @@ impl ByteView {
         unsafe { self.trailer.short.data.get_unchecked(..len) }
     }
 
-    fn is_inline(&self) -> bool {
-        self.len() <= INLINE_SIZE
+    /// Returns `true` if the slice is inlined, so it does not reference any heap allocation.
+    ///
+    /// The representation only depends on the length: every slice that is short enough
+    /// is inlined, even if it was sliced from a heap-allocated slice, so short slices
+    /// never keep a large parent allocation alive.
+    ///
+    /// The only exception are slices that are being built using [`ByteView::with_capacity`].
+    #[must_use]
+    pub fn is_inline(&self) -> bool {
+        self.tag() as usize <= INLINE_SIZE
+    }
+
+    /// Returns the length of inlined slices, or the tag of long slices.
+    fn tag(&self) -> u8 {
+        // SAFETY: Both trailer layouts have the tag stored at the same position
+        unsafe { self.trailer.short.len }
     }
 
-    fn update_prefix(&mut self) {
+    pub(crate) fn update_prefix(&mut self) {
         if !self.is_inline() {
             unsafe {
-                let slice_ptr: &[u8] = &*self;
-                let slice_ptr = slice_ptr.as_ptr();
+                let slice_ptr = self.get_slice().as_ptr();
 
                 // Zero out prefix
                 (*self.trailer.long).prefix[0] = 0;
@@ -243,15 +771,53 @@ impl ByteView {
         }
     }
 
+    /// Returns `true` if the slice can be mutated without affecting other slices.
+    fn is_uniquely_mutable(&self) -> bool {
+        if self.is_inline() {
+            return true;
+        }
+
+        // NOTE: Static data and custom sources may be immutable or aliased elsewhere
+        self.heap_region().is_some_and(|heap_region| {
+            heap_region.kind != HeapKind::Source && heap_region.is_unique()
+        })
+    }
+
     /// Returns a mutable reference into the given Byteview, if there are no other pointers to the same allocation.
     pub fn get_mut(&mut self) -> Option<Mutator<'_>> {
-        if self.ref_count() == 1 {
+        if self.is_uniquely_mutable() {
             Some(Mutator(self))
         } else {
             None
         }
     }
 
+    /// Returns a mutable reference into the given Byteview.
+    ///
+    /// If there are other pointers to the same allocation (or the slice cannot be mutated
+    /// in place, e.g. because it references static data), the bytes are copied into
+    /// a new heap allocation first, similar to [`Arc::make_mut`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use byteview::ByteView;
+    /// let a = ByteView::from("helloworld_thisisaverylongstring");
+    /// let mut b = a.clone();
+    ///
+    /// b.make_mut()[0] = b'j';
+    ///
+    /// assert_eq!(b"helloworld_thisisaverylongstring", &*a);
+    /// assert_eq!(b"jelloworld_thisisaverylongstring", &*b);
+    /// ```
+    pub fn make_mut(&mut self) -> Mutator<'_> {
+        if !self.is_uniquely_mutable() {
+            *self = self.to_detached();
+        }
+
+        Mutator(self)
+    }
+
     /// Creates a slice and populates it with  `len` bytes
     /// from the given reader.
     ///
@@ -270,509 +836,4983 @@ impl ByteView {
         Ok(s)
     }
 
-    /// Creates a new zeroed, fixed-length byteview.
+    /// Creates a slice and populates it with  `len` bytes
+    /// from the given reader.
     ///
-    /// Use [`ByteView::get_mut`] to mutate the content.
+    /// Same as [`ByteView::from_reader`], but does not panic or abort
+    /// if the slice cannot be allocated.
     ///
-    /// # Panics
+    /// # Errors
     ///
-    /// Panics if the length does not fit in a u32 (4 GiB).
-    #[must_use]
-    pub fn with_size(slice_len: usize) -> Self {
-        Self::with_size_zeroed(slice_len)
-    }
-
-    fn with_size_zeroed(slice_len: usize) -> Self {
-        let Ok(len) = u32::try_from(slice_len) else {
-            panic!("byte slice too long");
-        };
-
-        let mut builder = Self {
-            trailer: Trailer {
-                short: ManuallyDrop::new(ShortRepr {
-                    len,
-                    data: [0; INLINE_SIZE],
-                }),
-            },
-        };
-
-        if !builder.is_inline() {
-            unsafe {
-                let header_size = std::mem::size_of::<HeapAllocationHeader>();
-                let alignment = std::mem::align_of::<HeapAllocationHeader>();
-                let total_size = header_size + slice_len;
-                let layout = std::alloc::Layout::from_size_align(total_size, alignment).unwrap();
-
-                // IMPORTANT: Zero-allocate the region
-                let heap_ptr = std::alloc::alloc_zeroed(layout);
-                if heap_ptr.is_null() {
-                    std::alloc::handle_alloc_error(layout);
-                }
-
-                // SAFETY: We store a pointer to the copied slice, which comes directly after the header
-                (*builder.trailer.long).data =
-                    heap_ptr.add(std::mem::size_of::<HeapAllocationHeader>());
-
-                // Set pointer to heap allocation address
-                (*builder.trailer.long).heap = heap_ptr;
-
-                // Set ref count
-                let heap_region = heap_ptr as *const HeapAllocationHeader;
-                let heap_region = &*heap_region;
-                heap_region.ref_count.store(1, Ordering::Release);
-            }
+    /// Returns an error if an I/O error occurred.
+    ///
+    /// If the length does not fit in a u32 (4 GiB), an error of kind [`std::io::ErrorKind::InvalidInput`]
+    /// is returned; if the heap allocation failed, an error of kind [`std::io::ErrorKind::OutOfMemory`].
+    pub fn try_from_reader<R: std::io::Read>(reader: &mut R, len: usize) -> std::io::Result<Self> {
+        // NOTE: See `from_reader`
+        let mut s = Self::try_allocate(len, 0, false)?;
+        {
+            let mut builder = Mutator(&mut s);
+            reader.read_exact(&mut builder)?;
         }
-
-        debug_assert_eq!(1, builder.ref_count());
-
-        builder
+        Ok(s)
     }
 
-    fn with_size_unchecked(slice_len: usize) -> Self {
-        let Ok(len) = u32::try_from(slice_len) else {
-            panic!("byte slice too long");
-        };
-
-        let mut builder = Self {
-            trailer: Trailer {
-                short: ManuallyDrop::new(ShortRepr {
-                    len,
-                    data: [0; INLINE_SIZE],
-                }),
-            },
-        };
-
-        if !builder.is_inline() {
-            unsafe {
-                let header_size = std::mem::size_of::<HeapAllocationHeader>();
-                let alignment = std::mem::align_of::<HeapAllocationHeader>();
-                let total_size = header_size + slice_len;
-                let layout = std::alloc::Layout::from_size_align(total_size, alignment).unwrap();
-
-                // IMPORTANT: Zero-allocate the region
-                let heap_ptr = std::alloc::alloc(layout);
-                if heap_ptr.is_null() {
-                    std::alloc::handle_alloc_error(layout);
-                }
+    /// Creates a slice and populates it with the whole content of the file at the given path.
+    ///
+    /// The file is read directly into the slice's heap allocation.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an I/O error occurred.
+    ///
+    /// If the file is larger than 4 GiB, an error of kind [`std::io::ErrorKind::InvalidInput`]
+    /// is returned; if the heap allocation failed, an error of kind [`std::io::ErrorKind::OutOfMemory`].
+    pub fn from_file<P: AsRef<std::path::Path>>(path: P) -> std::io::Result<Self> {
+        let file = std::fs::File::open(path)?;
 
-                // SAFETY: We store a pointer to the copied slice, which comes directly after the header
-                (*builder.trailer.long).data =
-                    heap_ptr.add(std::mem::size_of::<HeapAllocationHeader>());
+        let len = usize::try_from(file.metadata()?.len())
+            .map_err(|_| std::io::Error::new(std::io::ErrorKind::InvalidInput, "file too large"))?;
 
-                // Set pointer to heap allocation address
-                (*builder.trailer.long).heap = heap_ptr;
+        Self::from_file_range(&file, 0, len)
+    }
 
-                // Set ref count
-                let heap_region = heap_ptr as *const HeapAllocationHeader;
-                let heap_region = &*heap_region;
-                heap_region.ref_count.store(1, Ordering::Release);
-            }
+    /// Creates a slice and populates it with `len` bytes read from
+    /// the given file, starting at `offset`.
+    ///
+    /// Uses positional reads where available, so the file's cursor is not moved
+    /// (on other platforms, the file is seeked to `offset` first).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an I/O error occurred, or the file is too short.
+    ///
+    /// If the length does not fit in a u32 (4 GiB), an error of kind [`std::io::ErrorKind::InvalidInput`]
+    /// is returned; if the heap allocation failed, an error of kind [`std::io::ErrorKind::OutOfMemory`].
+    pub fn from_file_range(file: &std::fs::File, offset: u64, len: usize) -> std::io::Result<Self> {
+        // NOTE: See `from_reader`
+        let mut s = Self::try_allocate(len, 0, false)?;
+        {
+            let mut builder = Mutator(&mut s);
+            read_exact_at(file, &mut builder, offset)?;
         }
+        Ok(s)
+    }
 
-        debug_assert_eq!(1, builder.ref_count());
+    /// Creates a new zeroed, fixed-length byteview.
+    ///
+    /// Use [`ByteView::get_mut`] to mutate the content,
+    /// or [`ByteView::filled`] to fill it with another byte.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length does not fit in a u32 (4 GiB).
+    #[must_use]
+    pub fn with_size(slice_len: usize) -> Self {
+        Self::with_size_zeroed(slice_len)
+    }
 
-        builder
+    /// Creates a new zeroed, fixed-length byteview.
+    ///
+    /// Same as [`ByteView::with_size`], but does not panic.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the length does not fit in a u32 (4 GiB),
+    /// or the heap allocation failed.
+    pub fn try_with_size(slice_len: usize) -> Result<Self, Error> {
+        Self::try_allocate(slice_len, 0, true)
     }
 
-    /// Creates a new slice from an existing byte slice.
+    /// Creates a new fixed-length byteview, with every byte set to `byte`.
+    ///
+    /// # Examples
     ///
-    /// Will heap-allocate the slice if it has at least length 13.
+    /// ```
+    /// # use byteview::ByteView;
+    /// let slice = ByteView::filled(100, 0xFF);
+    /// assert_eq!(100, slice.len());
+    /// assert!(slice.iter().all(|&b| b == 0xFF));
+    /// ```
     ///
     /// # Panics
     ///
     /// Panics if the length does not fit in a u32 (4 GiB).
     #[must_use]
-    pub fn new(slice: &[u8]) -> Self {
-        let slice_len = slice.len();
+    pub fn filled(slice_len: usize, byte: u8) -> Self {
+        // NOTE: The data region is overwritten below, so it does not need to be zeroed
+        let mut view = Self::allocate(slice_len, 0, byte == 0);
 
-        let mut view = Self::with_size(slice_len);
+        if byte == 0 {
+            return view;
+        }
 
         if view.is_inline() {
-            // SAFETY: We check for inlinability
-            // so we know the the input slice fits our buffer
+            // SAFETY: The slice is inlined, so its length fits the inline buffer
             unsafe {
-                let base_ptr = std::ptr::addr_of_mut!(view) as *mut u8;
-                let prefix_offset = base_ptr.add(std::mem::size_of::<u32>());
-                std::ptr::copy_nonoverlapping(slice.as_ptr(), prefix_offset, slice_len);
+                (*view.trailer.short)
+                    .data
+                    .get_unchecked_mut(..slice_len)
+                    .fill(byte);
             }
         } else {
+            // SAFETY: We just allocated the data region, which is `slice_len` bytes long
             unsafe {
-                // Copy prefix
-                (*view.trailer.long)
-                    .prefix
-                    .copy_from_slice(&slice[0..PREFIX_SIZE]);
-
-                // Copy byte slice into heap allocation
-                std::ptr::copy_nonoverlapping(
-                    slice.as_ptr(),
-                    (*view.trailer.long).data.cast_mut(),
-                    slice_len,
-                );
+                (*view.trailer.long).prefix = [byte; PREFIX_SIZE];
+                std::ptr::write_bytes(view.data_ptr().cast_mut(), byte, slice_len);
             }
         }
 
         view
     }
 
-    fn get_heap_region(&self) -> &HeapAllocationHeader {
-        debug_assert!(
-            !self.is_inline(),
-            "inline slice does not have a heap allocation"
-        );
-
-        unsafe {
-            /*   // SAFETY: Shall only be used when the slice is not inlined
-            // otherwise the heap pointer would be garbage
-            let ptr = u64::from_ne_bytes(self.rest);
-            let ptr = ptr as *const u8; */
-
-            let ptr = self.trailer.long.heap;
-
-            let heap_region: *const HeapAllocationHeader = ptr.cast::<HeapAllocationHeader>();
-            &*heap_region
+    /// Creates a new fixed-length byteview, computing each byte by calling `f` with its index.
+    ///
+    /// The bytes are written straight into the slice's memory, without any intermediate buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use byteview::ByteView;
+    /// let slice = ByteView::from_fn(256, |idx| idx as u8);
+    /// assert_eq!(256, slice.len());
+    /// assert_eq!(Some(&b'A'), slice.get(65));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length does not fit in a u32 (4 GiB).
+    #[must_use]
+    pub fn from_fn(slice_len: usize, mut f: impl FnMut(usize) -> u8) -> Self {
+        // NOTE: Every byte is overwritten below, so the data region does not need to be zeroed
+        let mut view = Self::with_size_unchecked(slice_len);
+        let ptr = view.data_mut_ptr();
+
+        for idx in 0..slice_len {
+            // SAFETY: The slice's memory is `slice_len` bytes long
+            // NOTE: If `f` panics, the slice is dropped without reading its (partly uninitialized) bytes
+            unsafe {
+                ptr.add(idx).write(f(idx));
+            }
         }
+
+        view.update_prefix();
+        view
     }
 
-    /// Returns the ref_count of the underlying heap allocation.
-    #[doc(hidden)]
-    #[must_use]
-    pub fn ref_count(&self) -> u64 {
+    /// Returns a pointer to the start of the slice's memory,
+    /// so a newly allocated slice can be initialized.
+    fn data_mut_ptr(&mut self) -> *mut u8 {
         if self.is_inline() {
-            1
+            // SAFETY: The slice is inlined
+            unsafe { (*self.trailer.short).data.as_mut_ptr() }
         } else {
-            self.get_heap_region().ref_count.load(Ordering::Acquire)
+            self.data_ptr().cast_mut()
         }
     }
 
-    /// Clones the contents of this slice into an independently tracked slice.
-    #[must_use]
-    pub fn to_detached(&self) -> Self {
-        Self::new(self)
-    }
-
-    /// Clones the given range of the existing slice without heap allocation.
+    /// Creates a new slice by repeating this slice `n` times.
+    ///
+    /// Like `<[u8]>::repeat`, but the result is written straight into a single allocation,
+    /// without any intermediate `Vec`.
     ///
     /// # Examples
     ///
     /// ```
     /// # use byteview::ByteView;
-    /// let slice = ByteView::from("helloworld_thisisalongstring");
-    /// let copy = slice.slice(11..);
-    /// assert_eq!(b"thisisalongstring", &*copy);
+    /// let slice = ByteView::from("abc").repeat(10);
+    /// assert_eq!(30, slice.len());
+    /// assert!(slice.starts_with("abcabc"));
     /// ```
     ///
     /// # Panics
     ///
-    /// Panics if the slice is out of bounds.
+    /// Panics if the length would overflow, or does not fit in a u32 (4 GiB).
     #[must_use]
-    pub fn slice(&self, range: impl std::ops::RangeBounds<usize>) -> Self {
-        use core::ops::Bound;
+    pub fn repeat(&self, n: usize) -> Self {
+        let len = self.len();
+        let total = len
+            .checked_mul(n)
+            .unwrap_or_else(|| panic!("capacity overflow"));
 
-        // Credits: This is essentially taken from
-        // https://github.com/tokio-rs/bytes/blob/291df5acc94b82a48765e67eeb1c1a2074539e68/src/bytes.rs#L264
+        // NOTE: Every byte is overwritten below, so the data region does not need to be zeroed
+        let mut view = Self::with_size_unchecked(total);
+        let dst = view.data_mut_ptr();
 
-        let self_len = self.len();
+        if total > 0 {
+            // SAFETY: The new slice's memory is `total` bytes long, and does not overlap with this slice
+            unsafe {
+                std::ptr::copy_nonoverlapping(self.get_slice().as_ptr(), dst, len);
 
-        let begin = match range.start_bound() {
-            Bound::Included(&n) => n,
-            Bound::Excluded(&n) => n.checked_add(1).expect("out of range"),
-            Bound::Unbounded => 0,
-        };
+                // NOTE: Double the written bytes in each step, like `<[u8]>::repeat`
+                let mut written = len;
 
-        let end = match range.end_bound() {
-            Bound::Included(&n) => n.checked_add(1).expect("out of range"),
-            Bound::Excluded(&n) => n,
-            Bound::Unbounded => self_len,
-        };
+                while written < total {
+                    let count = written.min(total - written);
+                    std::ptr::copy_nonoverlapping(dst, dst.add(written), count);
+                    written += count;
+                }
+            }
+        }
 
-        assert!(
-            begin <= end,
-            "range start must not be greater than end: {begin:?} <= {end:?}",
-        );
-        assert!(
-            end <= self_len,
-            "range end out of bounds: {end:?} <= {self_len:?}",
-        );
+        view.update_prefix();
+        view
+    }
 
-        let new_len = end - begin;
-        let len = u32::try_from(new_len).unwrap();
+    /// Creates a new zeroed, fixed-length byteview, reserving `headroom` spare bytes
+    /// in front of it, so bytes can be prepended using [`ByteView::prepend`] without reallocating.
+    ///
+    /// Slices that are short enough to be inlined do not reserve any headroom.
+    ///
+    /// Use [`ByteView::get_mut`] to mutate the content.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use byteview::ByteView;
+    /// let mut slice = ByteView::with_size_and_headroom(30, 4);
+    /// assert_eq!(4, slice.headroom());
+    ///
+    /// slice.prepend(&[1, 2, 3, 4]);
+    /// assert_eq!(34, slice.len());
+    /// assert_eq!(0, slice.headroom());
+    /// assert_eq!([1, 2, 3, 4, 0], slice[..5]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length (including headroom) does not fit in a u32 (4 GiB).
+    #[must_use]
+    pub fn with_size_and_headroom(slice_len: usize, headroom: usize) -> Self {
+        Self::allocate(slice_len, headroom, true)
+    }
 
-        // Target and destination slices are inlined
-        // so we just need to memcpy the struct, and replace
-        // the inline slice with the requested range
-        if new_len <= INLINE_SIZE && self_len <= INLINE_SIZE {
-            let mut cloned = Self {
-                trailer: Trailer {
-                    short: ManuallyDrop::new(ShortRepr {
-                        len,
-                        data: [0; INLINE_SIZE],
-                    }),
-                },
-            };
+    fn with_size_zeroed(slice_len: usize) -> Self {
+        Self::allocate(slice_len, 0, true)
+    }
 
-            let slice = &self.get_short_slice()[begin..end];
-            debug_assert_eq!(slice.len(), new_len);
+    pub(crate) fn with_size_unchecked(slice_len: usize) -> Self {
+        Self::allocate(slice_len, 0, false)
+    }
 
-            unsafe {
-                let base_ptr = std::ptr::addr_of_mut!(cloned) as *mut u8;
-                let prefix_offset = base_ptr.add(std::mem::size_of::<u32>());
-                std::ptr::copy_nonoverlapping(slice.as_ptr(), prefix_offset, new_len);
-            }
+    fn allocate(slice_len: usize, headroom: usize, zeroed: bool) -> Self {
+        match Self::try_allocate(slice_len, headroom, zeroed) {
+            Ok(view) => view,
+            Err(Error::OutOfMemory(layout)) => std::alloc::handle_alloc_error(layout),
+            Err(e) => panic!("{e}"),
+        }
+    }
 
-            cloned
-        } else if new_len <= INLINE_SIZE && self_len > INLINE_SIZE {
-            let mut cloned = Self {
+    fn try_allocate(slice_len: usize, headroom: usize, zeroed: bool) -> Result<Self, Error> {
+        if slice_len <= INLINE_SIZE {
+            // NOTE: We are inlined, so the length fits into a u8
+            #[allow(clippy::cast_possible_truncation)]
+            let len = slice_len as u8;
+
+            return Ok(Self {
                 trailer: Trailer {
                     short: ManuallyDrop::new(ShortRepr {
                         len,
                         data: [0; INLINE_SIZE],
                     }),
                 },
-            };
+            });
+        }
 
-            let slice = &self.get_long_slice()[begin..end];
-            debug_assert_eq!(slice.len(), new_len);
+        let Ok(len) = u32::try_from(slice_len) else {
+            return Err(Error::TooLong(slice_len));
+        };
 
-            unsafe {
-                let base_ptr = std::ptr::addr_of_mut!(cloned) as *mut u8;
-                let prefix_offset = base_ptr.add(std::mem::size_of::<u32>());
-                std::ptr::copy_nonoverlapping(slice.as_ptr(), prefix_offset, new_len);
-            }
+        let region_len = headroom.saturating_add(slice_len);
 
-            cloned
-        } else if new_len > INLINE_SIZE && self_len > INLINE_SIZE {
-            let heap_region = self.get_heap_region();
-            let rc_before = heap_region.ref_count.fetch_add(1, Ordering::Release);
-            debug_assert!(rc_before < u64::MAX, "refcount overflow");
+        let Ok(region_len) = u32::try_from(region_len) else {
+            return Err(Error::TooLong(region_len));
+        };
 
-            let mut cloned = Self {
-                // SAFETY: self.data must be defined
-                // we cannot get a range larger than our own slice
-                // so we cannot be inlined while the requested slice is not inlinable
-                trailer: Trailer {
-                    long: ManuallyDrop::new(LongRepr {
-                        len,
-                        prefix: [0; PREFIX_SIZE],
-                        heap: unsafe { self.trailer.long.heap },
-                        data: unsafe { self.trailer.long.data.add(begin) },
-                    }),
-                },
-            };
+        let header_size = std::mem::size_of::<HeapAllocationHeader>();
+        let alignment = std::mem::align_of::<HeapAllocationHeader>();
+        let total_size = header_size + region_len as usize;
+        let Ok(layout) = std::alloc::Layout::from_size_align(total_size, alignment) else {
+            return Err(Error::TooLong(slice_len));
+        };
 
-            let prefix = &self.get_long_slice()[begin..(begin + 4)];
-            debug_assert_eq!(prefix.len(), 4);
-            unsafe {
-                (*cloned.trailer.long).prefix.copy_from_slice(prefix);
+        // IMPORTANT: Zero-allocate the region, unless the caller overwrites it anyway
+        // SAFETY: The layout is never zero-sized, because of the header
+        let heap_ptr = unsafe {
+            if zeroed {
+                std::alloc::alloc_zeroed(layout)
+            } else {
+                std::alloc::alloc(layout)
             }
+        };
+        let Some(heap_ptr) = NonNull::new(heap_ptr) else {
+            return Err(Error::OutOfMemory(layout));
+        };
 
-            cloned
-        } else {
-            unreachable!()
+        // Set ref count and allocation length
+        // SAFETY: The allocation is aligned to, and large enough for the header
+        unsafe {
+            heap_ptr
+                .cast::<HeapAllocationHeader>()
+                .as_ptr()
+                .write(HeapAllocationHeader {
+                    ref_count: RefCount::new(1),
+                    weak_count: RefCount::new(1),
+                    len: region_len,
+                    kind: HeapKind::Global,
+                    #[cfg(feature = "checksum")]
+                    checksum: None,
+                    #[cfg(feature = "hash-cache")]
+                    hash: std::sync::atomic::AtomicU64::new(0),
+                });
         }
-    }
 
-    /// Returns `true` if `needle` is a prefix of the slice or equal to the slice.
-    pub fn starts_with<T: AsRef<[u8]>>(&self, needle: T) -> bool {
-        let needle = needle.as_ref();
+        crate::hooks::on_alloc(total_size);
 
-        unsafe {
-            let len = PREFIX_SIZE.min(needle.len());
-            let needle_prefix: &[u8] = needle.get_unchecked(..len);
+        // NOTE: The slice comes after the header and headroom
+        // and the headroom is part of the data region, so it fits into a u32
+        #[allow(clippy::cast_possible_truncation)]
+        let offset = headroom as u32;
 
-            if !self.prefix().starts_with(needle_prefix) {
-                return false;
-            }
-        }
+        let builder = Self {
+            trailer: Trailer {
+                long: ManuallyDrop::new(LongRepr {
+                    tag: TAG_GLOBAL,
+                    prefix: [0; PREFIX_SIZE],
+                    _pad: [0; 3],
+                    len,
+                    offset,
+                    heap: heap_ptr,
+                }),
+            },
+        };
 
-        self.deref().starts_with(needle)
-    }
+        debug_assert_eq!(1, builder.ref_count());
 
-    /// Returns `true` if the slice is empty.
-    #[must_use]
-    pub fn is_empty(&self) -> bool {
-        self.len() == 0
+        Ok(builder)
     }
 
-    /// Returns the amount of bytes in the slice.
+    /// Returns the amount of spare bytes in front of the slice inside its heap allocation.
+    ///
+    /// Inlined slices do not have any headroom.
     #[must_use]
-    pub fn len(&self) -> usize {
-        unsafe { self.trailer.short.len as usize }
-    }
+    pub fn headroom(&self) -> usize {
+        if self
+            .heap_region()
+            .map_or(true, |heap_region| heap_region.kind != HeapKind::Global)
+        {
+            return 0;
+        }
 
-    fn get_mut_slice(&mut self) -> &mut [u8] {
-        let len = self.len();
+        // SAFETY: We are not inlined
+        unsafe { self.trailer.long.offset as usize }
+    }
 
-        if self.is_inline() {
-            unsafe {
-                let base_ptr = (self as *mut Self).cast::<u8>();
-                let prefix_offset = base_ptr.add(std::mem::size_of::<u32>());
-                std::slice::from_raw_parts_mut(prefix_offset, len)
+    /// Creates a new empty slice that can grow to `capacity` bytes
+    /// without reallocating (see [`ByteView::extend_from_slice`]).
+    ///
+    /// If the capacity is short enough to be inlined, no heap allocation is made.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use byteview::ByteView;
+    /// let mut slice = ByteView::with_capacity(64);
+    /// assert!(slice.is_empty());
+    /// assert_eq!(64, slice.capacity());
+    ///
+    /// slice.extend_from_slice(b"helloworld_");
+    /// slice.extend_from_slice(b"thisisaverylongstring");
+    /// assert_eq!(b"helloworld_thisisaverylongstring", &*slice);
+    /// assert_eq!(64, slice.capacity());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the capacity does not fit in a u32 (4 GiB).
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        if capacity <= INLINE_SIZE {
+            return Self::default();
+        }
+
+        let mut view = Self::with_size_zeroed(capacity);
+
+        // SAFETY: We are not inlined
+        unsafe {
+            (*view.trailer.long).len = 0;
+        }
+        view.update_prefix();
+
+        view
+    }
+
+    /// Returns the amount of bytes the slice can hold without reallocating
+    /// when using [`ByteView::extend_from_slice`].
+    ///
+    /// Spare capacity can only be used by uniquely referenced slices.
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        if self.is_inline() {
+            return INLINE_SIZE;
+        }
+
+        match self.heap_region() {
+            Some(heap_region) if self.tag() == TAG_GLOBAL => {
+                // SAFETY: We are not inlined
+                let offset = unsafe { self.trailer.long.offset };
+                (heap_region.len - offset) as usize
+            }
+            _ => self.len(),
+        }
+    }
+
+    /// Appends the given bytes to the slice.
+    ///
+    /// If this is the only reference to its heap allocation, and there is enough spare capacity
+    /// (see [`ByteView::with_capacity`]), the bytes are written in place.
+    /// Otherwise, a new slice is allocated, with spare capacity to amortize further appends.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the resulting length does not fit in a u32 (4 GiB).
+    pub fn extend_from_slice(&mut self, bytes: &[u8]) {
+        let n = bytes.len();
+
+        if n == 0 {
+            return;
+        }
+
+        let len = self.len();
+
+        let Some(new_len) = len.checked_add(n) else {
+            panic!("byte slice too long");
+        };
+
+        if new_len > self.capacity() || !self.is_uniquely_mutable() {
+            let capacity = new_len.max(len.saturating_mul(2)).min(u32::MAX as usize);
+
+            let mut view = Self::with_capacity(capacity.max(new_len));
+            view.extend_from_slice(self);
+
+            #[cfg(feature = "checksum")]
+            if self.has_checksum() {
+                view.update_checksum(true);
+            }
+
+            *self = view;
+        }
+
+        // NOTE: We are the only reference to the heap allocation (or inlined),
+        // and there is enough spare capacity
+        let dst = self.get_mut_ptr();
+
+        // SAFETY: The spare capacity comes directly after the slice
+        unsafe {
+            std::ptr::copy_nonoverlapping(bytes.as_ptr(), dst.add(len), n);
+
+            // NOTE: The new length fits into our capacity, so it fits into a u32
+            #[allow(clippy::cast_possible_truncation)]
+            if self.is_inline() {
+                (*self.trailer.short).len = new_len as u8;
+            } else {
+                (*self.trailer.long).len = new_len as u32;
+            }
+        }
+
+        self.update_prefix();
+
+        #[cfg(feature = "checksum")]
+        self.update_checksum(false);
+
+        #[cfg(feature = "hash-cache")]
+        self.invalidate_hash();
+    }
+
+    /// Appends a single byte to the slice.
+    ///
+    /// See [`ByteView::extend_from_slice`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the resulting length does not fit in a u32 (4 GiB).
+    pub fn push(&mut self, byte: u8) {
+        self.extend_from_slice(&[byte]);
+    }
+
+    /// Prepends the given bytes to the slice.
+    ///
+    /// If this is the only reference to its heap allocation, and there is enough headroom
+    /// (see [`ByteView::with_size_and_headroom`]), the bytes are written in place.
+    /// Otherwise, a new slice is allocated.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the resulting length does not fit in a u32 (4 GiB).
+    pub fn prepend(&mut self, bytes: &[u8]) {
+        let n = bytes.len();
+
+        if n == 0 {
+            return;
+        }
+
+        if self.headroom() >= n && self.is_uniquely_mutable() {
+            // SAFETY: We are the only reference to the heap allocation,
+            // and the headroom is large enough, so we can write in front of the slice
+            unsafe {
+                let long = &mut *self.trailer.long;
+
+                // NOTE: The new length is still inside the heap allocation, so it fits into a u32
+                #[allow(clippy::cast_possible_truncation)]
+                {
+                    long.offset -= n as u32;
+                    long.len += n as u32;
+                }
+
+                std::ptr::copy_nonoverlapping(bytes.as_ptr(), self.data_ptr().cast_mut(), n);
+            }
+
+            self.update_prefix();
+
+            #[cfg(feature = "checksum")]
+            self.update_checksum(false);
+
+            #[cfg(feature = "hash-cache")]
+            self.invalidate_hash();
+
+            return;
+        }
+
+        let mut view = Self::with_size_unchecked(n + self.len());
+
+        {
+            let mut mutator = Mutator(&mut view);
+
+            // SAFETY: The new slice was sized to fit both
+            #[allow(clippy::indexing_slicing)]
+            {
+                mutator[..n].copy_from_slice(bytes);
+                mutator[n..].copy_from_slice(self);
+            }
+        }
+
+        *self = view;
+    }
+
+    /// Creates a new slice from an existing byte slice.
+    ///
+    /// Will heap-allocate the slice if it is longer than 23 bytes (on a 64-bit system).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length does not fit in a u32 (4 GiB).
+    #[must_use]
+    pub fn new(slice: &[u8]) -> Self {
+        Self::try_new(slice).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Creates a new slice from an existing byte slice.
+    ///
+    /// Same as [`ByteView::new`], but does not panic.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the length does not fit in a u32 (4 GiB),
+    /// or the heap allocation failed.
+    pub fn try_new(slice: &[u8]) -> Result<Self, Error> {
+        let slice_len = slice.len();
+
+        let mut view = Self::try_with_size(slice_len)?;
+
+        if view.is_inline() {
+            // SAFETY: We check for inlinability
+            // so we know the the input slice fits our buffer
+            unsafe {
+                (*view.trailer.short)
+                    .data
+                    .get_unchecked_mut(..slice_len)
+                    .copy_from_slice(slice);
+            }
+        } else {
+            unsafe {
+                // Copy prefix
+                (*view.trailer.long)
+                    .prefix
+                    .copy_from_slice(slice.get_unchecked(..PREFIX_SIZE));
+
+                // Copy byte slice into heap allocation
+                std::ptr::copy_nonoverlapping(
+                    slice.as_ptr(),
+                    view.data_ptr().cast_mut(),
+                    slice_len,
+                );
+            }
+        }
+
+        Ok(view)
+    }
+
+    /// Creates many slices at once, using a single heap allocation.
+    ///
+    /// All items that are too long to be inlined are copied into one shared heap allocation,
+    /// which the returned slices reference.
+    /// The allocation is freed once all of its slices are dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use byteview::ByteView;
+    /// let slices = ByteView::new_many([
+    ///     &b"helloworld_thisisaverylongstring"[..],
+    ///     b"short",
+    ///     b"helloworld_thisisanotherlongstring",
+    /// ]);
+    ///
+    /// assert_eq!(b"short", &*slices[1]);
+    /// assert_eq!(2, slices[0].ref_count());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the total length of the long items does not fit in a u32 (4 GiB).
+    #[must_use]
+    pub fn new_many<'a, I: IntoIterator<Item = &'a [u8]>>(items: I) -> Vec<Self> {
+        let items = items.into_iter().collect::<Vec<_>>();
+
+        let total_len = items
+            .iter()
+            .map(|item| item.len())
+            .filter(|&len| len > INLINE_SIZE)
+            .sum::<usize>();
+
+        if total_len == 0 {
+            return items.into_iter().map(Self::new).collect();
+        }
+
+        let mut buffer = Self::with_size_unchecked(total_len);
+        let ptr = buffer.get_mut_ptr();
+
+        let mut offset = 0;
+
+        for item in items.iter().filter(|item| item.len() > INLINE_SIZE) {
+            // SAFETY: The buffer is large enough to fit all long items
+            unsafe {
+                std::ptr::copy_nonoverlapping(item.as_ptr(), ptr.add(offset), item.len());
+            }
+            offset += item.len();
+        }
+
+        let mut offset = 0;
+
+        items
+            .into_iter()
+            .map(|item| {
+                if item.len() > INLINE_SIZE {
+                    let view = buffer.slice(offset..offset + item.len());
+                    offset += item.len();
+                    view
+                } else {
+                    Self::new(item)
+                }
+            })
+            .collect()
+    }
+
+    /// Creates a new slice by gathering the given buffers, using a single heap allocation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use byteview::ByteView;
+    /// use std::io::IoSlice;
+    ///
+    /// let slice = ByteView::from_io_slices(&[
+    ///     IoSlice::new(b"helloworld_"),
+    ///     IoSlice::new(b"thisisaverylongstring"),
+    /// ]);
+    ///
+    /// assert_eq!(b"helloworld_thisisaverylongstring", &*slice);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the total length does not fit in a u32 (4 GiB).
+    #[must_use]
+    pub fn from_io_slices(bufs: &[IoSlice<'_>]) -> Self {
+        let Some(total_len) = bufs
+            .iter()
+            .try_fold(0usize, |acc, buf| acc.checked_add(buf.len()))
+        else {
+            panic!("byte slice too long");
+        };
+
+        // NOTE: We can use _unchecked to skip zeroing of the heap allocated slice
+        // because the buffers fill it completely
+        let mut view = Self::with_size_unchecked(total_len);
+        let ptr = view.get_mut_ptr();
+
+        let mut offset = 0;
+
+        for buf in bufs {
+            // SAFETY: The slice is large enough to fit all buffers
+            unsafe {
+                std::ptr::copy_nonoverlapping(buf.as_ptr(), ptr.add(offset), buf.len());
             }
+            offset += buf.len();
+        }
+
+        view.update_prefix();
+        view
+    }
+
+    /// Returns the slice as an [`IoSlice`], to be used with vectored writes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use byteview::ByteView;
+    /// use std::io::Write;
+    ///
+    /// let a = ByteView::from("helloworld_");
+    /// let b = ByteView::from("thisisaverylongstring");
+    ///
+    /// let mut out = Vec::new();
+    /// out.write_vectored(&[a.as_io_slice(), b.as_io_slice()])?;
+    /// assert_eq!(b"helloworld_thisisaverylongstring", &*out);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    #[must_use]
+    pub fn as_io_slice(&self) -> IoSlice<'_> {
+        IoSlice::new(self)
+    }
+
+    /// An empty slice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use byteview::ByteView;
+    /// const KEYS: [ByteView; 2] = [ByteView::EMPTY, ByteView::new_inline(b"abc")];
+    /// assert!(KEYS[0].is_empty());
+    /// ```
+    pub const EMPTY: Self = Self::new_inline(&[]);
+
+    /// Creates a new inlined slice in a `const` context.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use byteview::ByteView;
+    /// const KEY: ByteView = ByteView::new_inline(&[0xFF; 16]);
+    /// assert!(KEY.is_inline());
+    /// assert_eq!([0xFF; 16], *KEY);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the slice is too long to be inlined (23 bytes on a 64-bit system).
+    #[must_use]
+    pub const fn new_inline(slice: &[u8]) -> Self {
+        let slice_len = slice.len();
+
+        assert!(slice_len <= INLINE_SIZE, "slice too long to be inlined");
+
+        #[allow(clippy::cast_possible_truncation)]
+        let len = slice_len as u8;
+
+        let mut data = [0; INLINE_SIZE];
+
+        let mut idx = 0;
+
+        // NOTE: No `copy_from_slice` in const fn
+        #[allow(clippy::indexing_slicing)]
+        while idx < slice_len {
+            data[idx] = slice[idx];
+            idx += 1;
+        }
+
+        Self {
+            trailer: Trailer {
+                short: ManuallyDrop::new(ShortRepr { len, data }),
+            },
+        }
+    }
+
+    /// Creates a new slice that references static data.
+    ///
+    /// Inlinable slices are copied, otherwise the static data is referenced
+    /// directly, without any heap allocation or reference counting.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use byteview::ByteView;
+    /// static DATA: &[u8] = b"this is some long static data";
+    ///
+    /// let view = ByteView::from_static(DATA);
+    /// assert_eq!(DATA, &*view);
+    /// assert_eq!(DATA.as_ptr(), view.as_ptr());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length does not fit in a u32 (4 GiB).
+    #[must_use]
+    pub const fn from_static(slice: &'static [u8]) -> Self {
+        let slice_len = slice.len();
+
+        assert!(slice_len <= u32::MAX as usize, "slice too long");
+
+        if slice_len <= INLINE_SIZE {
+            Self::new_inline(slice)
         } else {
-            unsafe { std::slice::from_raw_parts_mut(self.trailer.long.data.cast_mut(), len) }
+            // SAFETY: We are not inlined, so there are at least PREFIX_SIZE bytes
+            #[allow(clippy::indexing_slicing)]
+            let prefix = [slice[0], slice[1], slice[2], slice[3]];
+
+            #[allow(clippy::cast_possible_truncation)]
+            let len = slice_len as u32;
+
+            Self {
+                trailer: Trailer {
+                    long: ManuallyDrop::new(LongRepr {
+                        tag: TAG_STATIC,
+                        prefix,
+                        _pad: [0; 3],
+                        len,
+                        offset: 0,
+                        // NOTE: For static data, the base pointer is the data itself
+                        // SAFETY: Slices are never null
+                        heap: unsafe { NonNull::new_unchecked(slice.as_ptr().cast_mut()) },
+                    }),
+                },
+            }
+        }
+    }
+
+    /// Returns the header of the backing heap allocation.
+    ///
+    /// Returns `None` if the slice is inlined or references static data.
+    pub(crate) fn heap_region(&self) -> Option<&HeapAllocationHeader> {
+        if self.is_inline() || self.tag() == TAG_STATIC {
+            return None;
+        }
+
+        unsafe {
+            // SAFETY: We are heap-backed, so the heap pointer is valid
+            let ptr = self.trailer.long.heap;
+
+            // NOTE: The heap allocation is aligned to the header
+            #[allow(clippy::cast_ptr_alignment)]
+            let heap_region = ptr.cast::<HeapAllocationHeader>().as_ptr().cast_const();
+
+            heap_region.as_ref()
+        }
+    }
+
+    /// Returns the base pointer of the slice's data region, which identifies
+    /// the heap allocation (or static data) it references.
+    ///
+    /// Returns `None` if the slice is inlined.
+    pub(crate) fn heap_ptr(&self) -> Option<NonNull<u8>> {
+        if self.is_inline() {
+            return None;
+        }
+
+        // SAFETY: We are not inlined
+        Some(unsafe { self.trailer.long.heap })
+    }
+
+    /// Returns the start of the data region that the slice's offset is relative to.
+    ///
+    /// Shall only be called if the slice is not inlined.
+    fn data_base(&self) -> *const u8 {
+        debug_assert!(!self.is_inline(), "inlined slice has no data region");
+
+        // SAFETY: We are not inlined, so the heap pointer is valid
+        // for the kind of data region given by the tag
+        unsafe {
+            let heap = self.trailer.long.heap.as_ptr().cast_const();
+
+            match self.tag() {
+                TAG_GLOBAL => heap.add(std::mem::size_of::<HeapAllocationHeader>()),
+
+                #[allow(clippy::cast_ptr_alignment)]
+                TAG_SOURCE => (*heap.cast::<crate::source::SourceHeader>()).data,
+
+                _ => heap,
+            }
+        }
+    }
+
+    /// Returns a pointer to the first byte of the slice.
+    ///
+    /// Shall only be called if the slice is not inlined.
+    fn data_ptr(&self) -> *const u8 {
+        // SAFETY: The offset stays inside the data region
+        unsafe { self.data_base().add(self.trailer.long.offset as usize) }
+    }
+
+    /// Creates a heap-backed slice from an existing heap region.
+    ///
+    /// # Safety
+    ///
+    /// `heap` needs to point to an initialized [`HeapAllocationHeader`], of which
+    /// the caller transfers one reference count to the new slice.
+    ///
+    /// `data` needs to point to `len` initialized bytes inside the data region of the heap region.
+    ///
+    /// `len` needs to be larger than the inline size (unless the slice has spare capacity).
+    pub(crate) unsafe fn from_heap_parts(heap: NonNull<u8>, data: *const u8, len: u32) -> Self {
+        #[allow(clippy::cast_ptr_alignment)]
+        let tag = match heap.cast::<HeapAllocationHeader>().as_ref().kind {
+            HeapKind::Global => TAG_GLOBAL,
+            HeapKind::Source | HeapKind::Vec => TAG_SOURCE,
+
+            #[cfg(feature = "allocator-api2")]
+            HeapKind::Owned => TAG_SOURCE,
+        };
+
+        let mut view = Self {
+            trailer: Trailer {
+                long: ManuallyDrop::new(LongRepr {
+                    tag,
+                    prefix: [0; PREFIX_SIZE],
+                    _pad: [0; 3],
+                    len,
+                    offset: 0,
+                    heap,
+                }),
+            },
+        };
+
+        // NOTE: The data region is no larger than u32::MAX
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let offset = data.offset_from(view.data_base()) as u32;
+        (*view.trailer.long).offset = offset;
+
+        debug_assert!(
+            !view.is_inline(),
+            "heap slice needs to be longer than the inline size"
+        );
+        view.update_prefix();
+
+        view
+    }
+
+    /// Returns the entire data region of the backing heap allocation.
+    ///
+    /// Returns `None` if the slice is inlined or references static data.
+    fn get_heap_slice(&self) -> Option<&[u8]> {
+        let heap_region = self.heap_region()?;
+
+        // SAFETY: The header stores the length of the data region
+        unsafe {
+            Some(std::slice::from_raw_parts(
+                self.data_base(),
+                heap_region.len as usize,
+            ))
+        }
+    }
+
+    /// Returns the size of the heap allocation backing the slice, including its header.
+    ///
+    /// For adopted buffers and custom sources, this is the size of their handle,
+    /// plus the length of their bytes (and any spare capacity an adopted vector keeps allocated).
+    ///
+    /// All slices sharing the same heap allocation return the same size,
+    /// see [`ByteView::memory_usage`] to split it between them.
+    ///
+    /// Returns 0 if the slice is inlined or references static data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use byteview::ByteView;
+    /// let slice = ByteView::from("helloworld_thisisaverylongstring");
+    /// assert!(slice.heap_size() > slice.len());
+    /// assert_eq!(slice.heap_size(), slice.slice(5..).heap_size());
+    ///
+    /// assert_eq!(0, ByteView::from("abc").heap_size());
+    /// ```
+    #[must_use]
+    pub fn heap_size(&self) -> usize {
+        let Some(heap_region) = self.heap_region() else {
+            return 0;
+        };
+
+        let header_size = if heap_region.kind == HeapKind::Global {
+            std::mem::size_of::<HeapAllocationHeader>()
+        } else {
+            // SAFETY: Non-global heap allocations are always source handles
+            #[allow(clippy::cast_ptr_alignment)]
+            unsafe {
+                self.trailer
+                    .long
+                    .heap
+                    .cast::<crate::source::SourceHeader>()
+                    .as_ref()
+                    .overhead
+            }
+        };
+
+        header_size + heap_region.len as usize
+    }
+
+    /// Returns the slice's share of its heap allocation,
+    /// which is its [`ByteView::heap_size`] divided by the ref count.
+    ///
+    /// Summing this up over all slices sharing a heap allocation
+    /// (roughly) adds up to the size of the heap allocation, which is useful for cache weighers.
+    ///
+    /// Returns 0 if the slice is inlined or references static data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use byteview::ByteView;
+    /// let slice = ByteView::from("helloworld_thisisaverylongstring");
+    /// let copy = slice.clone();
+    /// assert_eq!(slice.heap_size() / 2, copy.memory_usage());
+    /// ```
+    #[must_use]
+    pub fn memory_usage(&self) -> usize {
+        let heap_size = self.heap_size();
+
+        if heap_size == 0 {
+            return 0;
+        }
+
+        // NOTE: The ref count of a live slice is at least 1
+        #[allow(clippy::cast_possible_truncation)]
+        let ref_count = self.ref_count().max(1) as usize;
+
+        heap_size / ref_count
+    }
+
+    /// Returns the ref_count of the underlying heap allocation.
+    #[doc(hidden)]
+    #[must_use]
+    pub fn ref_count(&self) -> u64 {
+        self.heap_region().map_or(1, |heap_region| {
+            heap_region.ref_count.load(Ordering::Acquire)
+        })
+    }
+
+    /// Returns a slice over the entire backing heap allocation (including any headroom).
+    ///
+    /// Slices that alias the same heap allocation return the same buffer.
+    ///
+    /// Returns `None` if the slice is inlined or references static data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use byteview::ByteView;
+    /// let slice = ByteView::from("helloworld_thisisaverylongstring");
+    /// let copy = slice.slice(5..);
+    ///
+    /// let parent = copy.heap_buffer().unwrap();
+    /// assert_eq!(slice, parent);
+    /// assert_eq!(Some(5), copy.offset_in_parent());
+    /// ```
+    #[must_use]
+    pub fn heap_buffer(&self) -> Option<Self> {
+        let heap_region = self.heap_region()?;
+        let data = self.get_heap_slice()?.as_ptr();
+
+        heap_region.increment_ref_count(1);
+
+        // SAFETY: We just took a reference for the new slice,
+        // and the data region is at least as large as our own slice
+        unsafe {
+            Some(Self::from_heap_parts(
+                self.trailer.long.heap,
+                data,
+                heap_region.len,
+            ))
+        }
+    }
+
+    /// Returns the offset of the slice inside its backing heap allocation (see [`ByteView::heap_buffer`]).
+    ///
+    /// Returns `None` if the slice is inlined or references static data.
+    #[must_use]
+    pub fn offset_in_parent(&self) -> Option<usize> {
+        self.heap_region()?;
+
+        // SAFETY: We are not inlined
+        unsafe { Some(self.trailer.long.offset as usize) }
+    }
+
+    /// Returns `n` clones of this slice, incrementing the ref count only once.
+    ///
+    /// This avoids `n` atomic operations on the same cache line
+    /// when fanning out a slice to many worker threads.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use byteview::ByteView;
+    /// let slice = ByteView::from("helloworld_thisisaverylongstring");
+    ///
+    /// let copies = slice.fan_out(4);
+    /// assert_eq!(4, copies.len());
+    /// assert_eq!(5, slice.ref_count());
+    /// ```
+    #[must_use]
+    pub fn fan_out(&self, n: usize) -> Vec<Self> {
+        let mut copies = Vec::with_capacity(n);
+
+        if n == 0 {
+            return copies;
+        }
+
+        if let Some(heap_region) = self.heap_region() {
+            heap_region.increment_ref_count(n as u64);
+        }
+
+        for _ in 0..n {
+            // SAFETY: We incremented the ref count once for every copy
+            // (or the slice is inlined, so copying it is fine anyway)
+            copies.push(unsafe { std::ptr::read(self) });
+        }
+
+        copies
+    }
+
+    /// Shortens the slice to `len` bytes, in place.
+    ///
+    /// Has no effect if `len` is greater than the slice's current length.
+    ///
+    /// Unless the remaining bytes are short enough to be inlined,
+    /// the ref count is not touched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use byteview::ByteView;
+    /// let mut slice = ByteView::from("helloworld_thisisaverylongstring");
+    /// slice.truncate(25);
+    /// assert_eq!(b"helloworld_thisisaverylon", &*slice);
+    /// ```
+    pub fn truncate(&mut self, len: usize) {
+        if len >= self.len() {
+            return;
+        }
+
+        if len <= INLINE_SIZE {
+            *self = self.slice(..len);
+            return;
+        }
+
+        // NOTE: We are shorter than before, so we still fit into a u32
+        #[allow(clippy::cast_possible_truncation)]
+        let len = len as u32;
+
+        // SAFETY: We are not inlined (and stay that way), so we can just shorten the long slice;
+        // the prefix stays the same
+        unsafe {
+            (*self.trailer.long).len = len;
+        }
+    }
+
+    /// Removes the first `n` bytes of the slice, in place.
+    ///
+    /// Unless the remaining bytes are short enough to be inlined,
+    /// the ref count is not touched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use byteview::ByteView;
+    /// let mut slice = ByteView::from("helloworld_thisisaverylongstring");
+    /// slice.advance(5);
+    /// assert_eq!(b"world_thisisaverylongstring", &*slice);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n > len`.
+    pub fn advance(&mut self, n: usize) {
+        let len = self.len();
+
+        assert!(n <= len, "cannot advance past end: {n:?} <= {len:?}");
+
+        let new_len = len - n;
+
+        if new_len <= INLINE_SIZE {
+            *self = self.slice(n..);
+            return;
+        }
+
+        // NOTE: We are shorter than before, so we still fit into a u32
+        #[allow(clippy::cast_possible_truncation)]
+        let new_len = new_len as u32;
+
+        // SAFETY: We are not inlined (and stay that way), and n is in bounds
+        unsafe {
+            // NOTE: The new offset is still inside the data region, so it fits into a u32
+            #[allow(clippy::cast_possible_truncation)]
+            {
+                (*self.trailer.long).offset += n as u32;
+            }
+            (*self.trailer.long).len = new_len;
+        }
+
+        self.update_prefix();
+    }
+
+    /// Splits the slice into two at the given index.
+    ///
+    /// Afterwards, `self` contains `[0, at)`, and the returned slice contains `[at, len)`.
+    /// Both halves share the same heap allocation (unless they are inlined).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use byteview::ByteView;
+    /// let mut slice = ByteView::from("helloworld_thisisaverylongstring");
+    /// let tail = slice.split_off(11);
+    /// assert_eq!(b"helloworld_", &*slice);
+    /// assert_eq!(b"thisisaverylongstring", &*tail);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at > len`.
+    #[must_use = "consider ByteView::truncate if you don't need the other half"]
+    pub fn split_off(&mut self, at: usize) -> Self {
+        let tail = self.slice(at..);
+        self.truncate(at);
+        tail
+    }
+
+    /// Splits the slice into two at the given index.
+    ///
+    /// Afterwards, `self` contains `[at, len)`, and the returned slice contains `[0, at)`.
+    /// Both halves share the same heap allocation (unless they are inlined).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use byteview::ByteView;
+    /// let mut slice = ByteView::from("helloworld_thisisaverylongstring");
+    /// let head = slice.split_to(11);
+    /// assert_eq!(b"helloworld_", &*head);
+    /// assert_eq!(b"thisisaverylongstring", &*slice);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at > len`.
+    #[must_use = "consider ByteView::advance if you don't need the other half"]
+    pub fn split_to(&mut self, at: usize) -> Self {
+        let head = self.slice(..at);
+        self.advance(at);
+        head
+    }
+
+    /// Divides the slice into two at the given index.
+    ///
+    /// The first slice contains `[0, mid)`, and the second slice contains `[mid, len)`.
+    /// Both halves share the same heap allocation (unless they are inlined),
+    /// and the ref count is only updated once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use byteview::ByteView;
+    /// let slice = ByteView::from("helloworld_thisisaverylongstring");
+    /// let (head, tail) = slice.split_at(11);
+    /// assert_eq!(b"helloworld_", &*head);
+    /// assert_eq!(b"thisisaverylongstring", &*tail);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `mid > len`.
+    #[must_use]
+    pub fn split_at(&self, mid: usize) -> (Self, Self) {
+        let head = ManuallyDrop::into_inner(self.slice_uncounted(..mid));
+        let tail = ManuallyDrop::into_inner(self.slice_uncounted(mid..));
+
+        if let Some(heap_region) = self.heap_region() {
+            let refs = u64::from(!head.is_inline()) + u64::from(!tail.is_inline());
+
+            if refs > 0 {
+                heap_region.increment_ref_count(refs);
+            }
+        }
+
+        (head, tail)
+    }
+
+    /// Divides the slice into two at the given index.
+    ///
+    /// Same as [`ByteView::split_at`], but returns `None` if `mid > len`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use byteview::ByteView;
+    /// let slice = ByteView::from("helloworld");
+    /// let (head, tail) = slice.split_at_checked(5).unwrap();
+    /// assert_eq!(b"hello", &*head);
+    /// assert_eq!(b"world", &*tail);
+    /// assert!(slice.split_at_checked(11).is_none());
+    /// ```
+    #[must_use]
+    pub fn split_at_checked(&self, mid: usize) -> Option<(Self, Self)> {
+        if mid > self.len() {
+            return None;
+        }
+
+        Some(self.split_at(mid))
+    }
+
+    /// Consumes the slice, returning its raw representation.
+    ///
+    /// The reference to the heap allocation is kept, so the parts can be passed
+    /// across an FFI or callback boundary, and turned back into the slice using [`ByteView::from_raw`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use byteview::ByteView;
+    /// let slice = ByteView::from("helloworld_thisisaverylongstring");
+    /// let copy = slice.clone();
+    ///
+    /// let parts = copy.into_raw();
+    /// assert_eq!(2, slice.ref_count());
+    ///
+    /// // SAFETY: The parts were returned by into_raw, and are only used once
+    /// let copy = unsafe { ByteView::from_raw(parts) };
+    /// assert_eq!(slice, copy);
+    /// assert_eq!(2, slice.ref_count());
+    /// ```
+    #[must_use = "the parts need to be passed to ByteView::from_raw, or the heap allocation is leaked"]
+    pub fn into_raw(self) -> RawParts {
+        let this = ManuallyDrop::new(self);
+
+        // SAFETY: RawParts has the same size as the slice, and is at least as aligned,
+        // and the bytes (including the heap pointer's provenance) are copied as-is
+        unsafe { std::ptr::read(std::ptr::addr_of!(*this).cast::<RawParts>()) }
+    }
+
+    /// Reconstructs a slice from its raw representation.
+    ///
+    /// # Safety
+    ///
+    /// The parts need to be returned by [`ByteView::into_raw`],
+    /// and every call of `into_raw` may only be matched by a single call of `from_raw`
+    /// (copies of the parts must not be used afterwards).
+    #[must_use]
+    pub const unsafe fn from_raw(parts: RawParts) -> Self {
+        std::ptr::read(std::ptr::addr_of!(parts).cast::<Self>())
+    }
+
+    /// Consumes the slice, returning a reference to its bytes that lives for the rest of the program.
+    ///
+    /// The heap allocation is never freed. Inlined slices are copied
+    /// into a new heap allocation first, because they do not have a stable address.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use byteview::ByteView;
+    /// let config = ByteView::from("helloworld_thisisaverylongstring");
+    /// let config: &'static [u8] = config.leak();
+    /// assert_eq!(b"helloworld_thisisaverylongstring", config);
+    /// ```
+    #[must_use]
+    pub fn leak(self) -> &'static [u8] {
+        if self.is_inline() {
+            return Box::leak(self.get_short_slice().into());
+        }
+
+        let this = ManuallyDrop::new(self);
+
+        // SAFETY: Static data lives forever, and the heap allocation
+        // is kept alive by the reference we never release
+        unsafe { std::slice::from_raw_parts(this.data_ptr(), this.len()) }
+    }
+
+    /// Copies the slice into a new `Vec<u8>`.
+    #[must_use]
+    pub fn to_vec(&self) -> Vec<u8> {
+        self.get_slice().to_vec()
+    }
+
+    /// Converts the slice into a `Vec<u8>`.
+    ///
+    /// If the slice was created from a `Vec<u8>` and is its only reference,
+    /// the vector is taken back without copying (it is only cut down to the slice's bytes).
+    /// Otherwise, the bytes are copied into a new vector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use byteview::ByteView;
+    /// let vec = b"helloworld_thisisaverylongstring".to_vec();
+    /// let ptr = vec.as_ptr();
+    ///
+    /// let slice = ByteView::from(vec);
+    /// let vec = slice.into_vec();
+    /// assert_eq!(ptr, vec.as_ptr());
+    /// assert_eq!(b"helloworld_thisisaverylongstring", &*vec);
+    /// ```
+    #[must_use]
+    pub fn into_vec(self) -> Vec<u8> {
+        self.try_into_adopted_vec()
+            .unwrap_or_else(|view| view.to_vec())
+    }
+
+    /// Clones the contents of this slice into an independently tracked slice.
+    #[must_use]
+    pub fn to_detached(&self) -> Self {
+        #[cfg(feature = "checksum")]
+        if self.has_checksum() {
+            return Self::new_checksummed(self);
+        }
+
+        Self::new(self)
+    }
+
+    /// Detaches the slice from its heap allocation if it only uses part of it.
+    ///
+    /// A small slice of a large heap allocation keeps the entire allocation alive.
+    /// Compacting copies the slice into a tightly sized allocation (or inlines it),
+    /// so the large allocation can be freed once all other slices are gone.
+    ///
+    /// Returns `true` if the slice was detached.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use byteview::ByteView;
+    /// let slice = ByteView::with_size(1_024);
+    /// let mut copy = slice.slice(0..100);
+    /// assert_eq!(2, slice.ref_count());
+    ///
+    /// assert!(copy.compact());
+    /// assert_eq!(1, slice.ref_count());
+    /// assert_eq!(1, copy.ref_count());
+    /// ```
+    pub fn compact(&mut self) -> bool {
+        self.compact_with_ratio(1)
+    }
+
+    /// Detaches the slice from its heap allocation if the allocation is more
+    /// than `max_ratio` times as large as the slice.
+    ///
+    /// See [`ByteView::compact`].
+    ///
+    /// Returns `true` if the slice was detached.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use byteview::ByteView;
+    /// let slice = ByteView::with_size(1_024);
+    ///
+    /// let mut copy = slice.slice(0..600);
+    /// assert!(!copy.compact_with_ratio(2));
+    ///
+    /// let mut copy = slice.slice(0..100);
+    /// assert!(copy.compact_with_ratio(2));
+    /// ```
+    pub fn compact_with_ratio(&mut self, max_ratio: usize) -> bool {
+        let Some(parent_len) = self.get_heap_slice().map(<[u8]>::len) else {
+            return false;
+        };
+
+        if parent_len <= self.len().saturating_mul(max_ratio) {
+            return false;
+        }
+
+        *self = self.to_detached();
+
+        true
+    }
+
+    /// Clones the given range of the existing slice without heap allocation.
+    ///
+    /// If the range is short enough to be inlined, it is copied into the new slice,
+    /// which then does not keep the heap allocation alive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use byteview::ByteView;
+    /// let slice = ByteView::from("helloworld_thisisalongstring");
+    /// let copy = slice.slice(11..);
+    /// assert_eq!(b"thisisalongstring", &*copy);
+    /// assert!(copy.is_inline());
+    /// assert_eq!(1, slice.ref_count());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the slice is out of bounds.
+    #[must_use]
+    pub fn slice(&self, range: impl std::ops::RangeBounds<usize>) -> Self {
+        let cloned = ManuallyDrop::into_inner(self.slice_uncounted(range));
+
+        if let Some(heap_region) = cloned.heap_region() {
+            heap_region.increment_ref_count(1);
+        }
+
+        cloned
+    }
+
+    /// Creates a slice that starts where this slice starts, and ends where `other` ends.
+    ///
+    /// # Safety
+    ///
+    /// Both slices need to reference the same data region,
+    /// and `other` must not end before this slice ends.
+    pub(crate) unsafe fn extend_to(&self, other: &Self) -> Self {
+        debug_assert!(!self.is_inline(), "inlined slice has no data region");
+        debug_assert_eq!(self.heap_ptr(), other.heap_ptr());
+
+        let end = other.get_ptr().add(other.len());
+
+        // NOTE: The new slice is still inside the data region, so its length fits into a u32
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let len = end.offset_from(self.get_ptr()) as u32;
+        debug_assert!(len as usize >= self.len());
+
+        if let Some(heap_region) = self.heap_region() {
+            heap_region.increment_ref_count(1);
+        }
+
+        // NOTE: The initialized bytes of a data region are contiguous
+        // (they are only ever appended or prepended), so the new slice is initialized, too
+        let mut view = std::ptr::read(self);
+        (*view.trailer.long).len = len;
+        view
+    }
+
+    /// Clones the given range of the existing slice, without incrementing the ref count.
+    ///
+    /// The returned slice may only be dropped once its reference is accounted for.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the slice is out of bounds.
+    pub(crate) fn slice_uncounted(
+        &self,
+        range: impl std::ops::RangeBounds<usize>,
+    ) -> ManuallyDrop<Self> {
+        use core::ops::Bound;
+
+        // Credits: This is essentially taken from
+        // https://github.com/tokio-rs/bytes/blob/291df5acc94b82a48765e67eeb1c1a2074539e68/src/bytes.rs#L264
+
+        let self_len = self.len();
+
+        let begin = match range.start_bound() {
+            Bound::Included(&n) => n,
+            Bound::Excluded(&n) => n.checked_add(1).unwrap_or_else(|| panic!("out of range")),
+            Bound::Unbounded => 0,
+        };
+
+        let end = match range.end_bound() {
+            Bound::Included(&n) => n.checked_add(1).unwrap_or_else(|| panic!("out of range")),
+            Bound::Excluded(&n) => n,
+            Bound::Unbounded => self_len,
+        };
+
+        assert!(
+            begin <= end,
+            "range start must not be greater than end: {begin:?} <= {end:?}",
+        );
+        assert!(
+            end <= self_len,
+            "range end out of bounds: {end:?} <= {self_len:?}",
+        );
+
+        let new_len = end - begin;
+
+        // NOTE: We cannot be larger than our own length, which fits into a u32
+        #[allow(clippy::cast_possible_truncation)]
+        let len = new_len as u32;
+
+        // Target and destination slices are inlined
+        // so we just need to memcpy the struct, and replace
+        // the inline slice with the requested range
+        if new_len <= INLINE_SIZE && self.is_inline() {
+            #[allow(clippy::cast_possible_truncation)]
+            let mut cloned = Self {
+                trailer: Trailer {
+                    short: ManuallyDrop::new(ShortRepr {
+                        len: len as u8,
+                        data: [0; INLINE_SIZE],
+                    }),
+                },
+            };
+
+            // SAFETY: We checked the range above
+            let slice = unsafe { self.get_short_slice().get_unchecked(begin..end) };
+            debug_assert_eq!(slice.len(), new_len);
+
+            unsafe {
+                (*cloned.trailer.short)
+                    .data
+                    .get_unchecked_mut(..new_len)
+                    .copy_from_slice(slice);
+            }
+
+            ManuallyDrop::new(cloned)
+        } else if new_len <= INLINE_SIZE {
+            #[allow(clippy::cast_possible_truncation)]
+            let mut cloned = Self {
+                trailer: Trailer {
+                    short: ManuallyDrop::new(ShortRepr {
+                        len: len as u8,
+                        data: [0; INLINE_SIZE],
+                    }),
+                },
+            };
+
+            // SAFETY: We checked the range above
+            let slice = unsafe { self.get_long_slice().get_unchecked(begin..end) };
+            debug_assert_eq!(slice.len(), new_len);
+
+            unsafe {
+                (*cloned.trailer.short)
+                    .data
+                    .get_unchecked_mut(..new_len)
+                    .copy_from_slice(slice);
+            }
+
+            ManuallyDrop::new(cloned)
+        } else if !self.is_inline() {
+            // NOTE: The new offset is still inside the data region, so it fits into a u32
+            #[allow(clippy::cast_possible_truncation)]
+            let mut cloned = Self {
+                // SAFETY: We are not inlined,
+                // we cannot get a range larger than our own slice
+                // so we cannot be inlined while the requested slice is not inlinable
+                trailer: Trailer {
+                    long: ManuallyDrop::new(LongRepr {
+                        tag: self.tag(),
+                        prefix: [0; PREFIX_SIZE],
+                        _pad: [0; 3],
+                        len,
+                        offset: unsafe { self.trailer.long.offset } + begin as u32,
+                        heap: unsafe { self.trailer.long.heap },
+                    }),
+                },
+            };
+
+            // SAFETY: The new slice is longer than the prefix size
+            let prefix = unsafe { self.get_long_slice().get_unchecked(begin..(begin + 4)) };
+            debug_assert_eq!(prefix.len(), 4);
+            unsafe {
+                (*cloned.trailer.long).prefix.copy_from_slice(prefix);
+            }
+
+            ManuallyDrop::new(cloned)
+        } else {
+            unreachable!()
+        }
+    }
+
+    /// Clones the given range of the existing slice without heap allocation.
+    ///
+    /// Same as [`ByteView::slice`], but returns `None` if the range is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use byteview::ByteView;
+    /// let slice = ByteView::from("helloworld");
+    /// assert_eq!(b"world", &*slice.try_slice(5..).unwrap());
+    /// assert!(slice.try_slice(5..11).is_none());
+    /// assert!(slice.try_slice(11..).is_none());
+    /// ```
+    #[must_use]
+    pub fn try_slice(&self, range: impl std::ops::RangeBounds<usize>) -> Option<Self> {
+        use core::ops::Bound;
+
+        let begin = match range.start_bound() {
+            Bound::Included(&n) => n,
+            Bound::Excluded(&n) => n.checked_add(1)?,
+            Bound::Unbounded => 0,
+        };
+
+        let end = match range.end_bound() {
+            Bound::Included(&n) => n.checked_add(1)?,
+            Bound::Excluded(&n) => n,
+            Bound::Unbounded => self.len(),
+        };
+
+        if begin > end || end > self.len() {
+            return None;
+        }
+
+        Some(self.slice(begin..end))
+    }
+
+    /// Returns a slice with leading and trailing ASCII whitespace removed.
+    ///
+    /// Like [`ByteView::slice`], the result references the same heap allocation
+    /// (or is inlined, if short enough), without copying.
+    ///
+    /// "Whitespace" refers to the definition used by [`u8::is_ascii_whitespace`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use byteview::ByteView;
+    /// let slice = ByteView::from("\r helloworld_thisisaverylongstring\n");
+    /// assert_eq!(b"helloworld_thisisaverylongstring", &*slice.trim_ascii());
+    /// assert_eq!(b"", &*ByteView::from("  ").trim_ascii());
+    /// ```
+    #[must_use]
+    pub fn trim_ascii(&self) -> Self {
+        let start = self.trimmed_start();
+        let end = self.trimmed_end().max(start);
+        self.slice(start..end)
+    }
+
+    /// Returns a slice with leading ASCII whitespace removed.
+    ///
+    /// See [`ByteView::trim_ascii`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use byteview::ByteView;
+    /// let slice = ByteView::from(" \t hello world\n");
+    /// assert_eq!(b"hello world\n", &*slice.trim_ascii_start());
+    /// ```
+    #[must_use]
+    pub fn trim_ascii_start(&self) -> Self {
+        self.slice(self.trimmed_start()..)
+    }
+
+    /// Returns a slice with trailing ASCII whitespace removed.
+    ///
+    /// See [`ByteView::trim_ascii`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use byteview::ByteView;
+    /// let slice = ByteView::from(" hello world\r\n");
+    /// assert_eq!(b" hello world", &*slice.trim_ascii_end());
+    /// ```
+    #[must_use]
+    pub fn trim_ascii_end(&self) -> Self {
+        self.slice(..self.trimmed_end())
+    }
+
+    /// Returns the offset of the first byte that is not ASCII whitespace.
+    fn trimmed_start(&self) -> usize {
+        // NOTE: <[u8]>::trim_ascii_start is only available since Rust 1.80
+        self.iter()
+            .position(|b| !b.is_ascii_whitespace())
+            .unwrap_or(self.len())
+    }
+
+    /// Returns the offset after the last byte that is not ASCII whitespace.
+    fn trimmed_end(&self) -> usize {
+        self.iter()
+            .rposition(|b| !b.is_ascii_whitespace())
+            .map_or(0, |idx| idx + 1)
+    }
+
+    /// Returns `true` if `needle` is a prefix of the slice or equal to the slice.
+    ///
+    /// If `needle` starts at the same address as the slice (e.g. because
+    /// it was sliced from the same heap allocation), no bytes are compared.
+    pub fn starts_with<T: AsRef<[u8]>>(&self, needle: T) -> bool {
+        let needle = needle.as_ref();
+
+        if needle.len() > self.len() {
+            return false;
+        }
+
+        let this = self.get_slice();
+
+        // NOTE: Same memory means same bytes
+        if std::ptr::eq(needle.as_ptr(), this.as_ptr()) {
+            return true;
+        }
+
+        unsafe {
+            let len = PREFIX_SIZE.min(needle.len());
+            let needle_prefix: &[u8] = needle.get_unchecked(..len);
+
+            if !self.prefix().starts_with(needle_prefix) {
+                return false;
+            }
+        }
+
+        this.starts_with(needle)
+    }
+
+    /// Returns `true` if `needle` is a suffix of the slice or equal to the slice.
+    ///
+    /// If `needle` ends at the same address as the slice (e.g. because
+    /// it was sliced from the same heap allocation), no bytes are compared.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use byteview::ByteView;
+    /// let slice = ByteView::from("helloworld_thisisaverylongstring");
+    /// assert!(slice.ends_with("string"));
+    /// assert!(slice.ends_with(&slice.slice(10..)));
+    /// assert!(!slice.ends_with("strinG"));
+    /// ```
+    pub fn ends_with<T: AsRef<[u8]>>(&self, needle: T) -> bool {
+        let needle = needle.as_ref();
+
+        if needle.len() > self.len() {
+            return false;
+        }
+
+        let this = self.get_slice();
+
+        // NOTE: Same memory means same bytes
+        if std::ptr::eq(needle.as_ptr_range().end, this.as_ptr_range().end) {
+            return true;
+        }
+
+        this.ends_with(needle)
+    }
+
+    /// Returns the offset of the first occurrence of `needle` in the slice.
+    ///
+    /// An empty needle is found at offset 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use byteview::ByteView;
+    /// let slice = ByteView::from("user:1000:email:user@example.com");
+    ///
+    /// assert_eq!(Some(4), slice.find(":"));
+    /// assert_eq!(Some(16), slice.find(b"user@"));
+    /// assert_eq!(None, slice.find("name"));
+    /// ```
+    #[must_use]
+    pub fn find<T: AsRef<[u8]>>(&self, needle: T) -> Option<usize> {
+        memchr::memmem::find(self, needle.as_ref())
+    }
+
+    /// Returns the offset of the last occurrence of `needle` in the slice.
+    ///
+    /// An empty needle is found at the end of the slice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use byteview::ByteView;
+    /// let slice = ByteView::from("user:1000:email:user@example.com");
+    ///
+    /// assert_eq!(Some(15), slice.rfind(":"));
+    /// assert_eq!(Some(16), slice.rfind("user"));
+    /// ```
+    #[must_use]
+    pub fn rfind<T: AsRef<[u8]>>(&self, needle: T) -> Option<usize> {
+        memchr::memmem::rfind(self, needle.as_ref())
+    }
+
+    /// Returns `true` if `needle` occurs anywhere in the slice.
+    ///
+    /// Note that this takes a byte pattern, unlike `<[u8]>::contains`,
+    /// which checks for a single byte (see [`ByteView::contains_byte`]).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use byteview::ByteView;
+    /// let slice = ByteView::from("user:1000:email:user@example.com");
+    ///
+    /// assert!(slice.contains("email"));
+    /// assert!(!slice.contains("name"));
+    /// ```
+    #[must_use]
+    pub fn contains<T: AsRef<[u8]>>(&self, needle: T) -> bool {
+        self.find(needle).is_some()
+    }
+
+    /// Returns the offset of the first occurrence of the given byte in the slice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use byteview::ByteView;
+    /// let slice = ByteView::from("user:1000:email");
+    ///
+    /// assert_eq!(Some(4), slice.find_byte(b':'));
+    /// assert_eq!(None, slice.find_byte(b'#'));
+    /// ```
+    #[must_use]
+    pub fn find_byte(&self, byte: u8) -> Option<usize> {
+        memchr::memchr(byte, self)
+    }
+
+    /// Returns the offset of the last occurrence of the given byte in the slice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use byteview::ByteView;
+    /// let slice = ByteView::from("user:1000:email");
+    ///
+    /// assert_eq!(Some(9), slice.rfind_byte(b':'));
+    /// ```
+    #[must_use]
+    pub fn rfind_byte(&self, byte: u8) -> Option<usize> {
+        memchr::memrchr(byte, self)
+    }
+
+    /// Returns `true` if the given byte occurs anywhere in the slice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use byteview::ByteView;
+    /// let slice = ByteView::from("user:1000:email");
+    ///
+    /// assert!(!slice.contains_byte(b'@'));
+    /// assert!(slice.contains_byte(b':'));
+    /// ```
+    #[must_use]
+    pub fn contains_byte(&self, byte: u8) -> bool {
+        self.find_byte(byte).is_some()
+    }
+
+    /// Returns the length of the longest prefix both slices share.
+    ///
+    /// The stored 4-byte prefixes are compared first, so slices that differ early
+    /// are resolved without touching the heap.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use byteview::ByteView;
+    /// let a = ByteView::from("user:1000:email");
+    /// let b = ByteView::from("user:1001:name");
+    ///
+    /// assert_eq!(8, a.longest_common_prefix(&b));
+    /// assert_eq!(0, a.longest_common_prefix(&ByteView::from("item")));
+    /// ```
+    #[must_use]
+    pub fn longest_common_prefix(&self, other: &Self) -> usize {
+        let max_len = self.len().min(other.len());
+
+        let shared = self
+            .prefix()
+            .iter()
+            .zip(other.prefix())
+            .take_while(|(a, b)| a == b)
+            .count();
+
+        if shared < PREFIX_SIZE || max_len <= PREFIX_SIZE {
+            return shared;
+        }
+
+        let this = self.get_slice();
+        let other = other.get_slice();
+
+        // NOTE: Same memory means same bytes
+        if std::ptr::eq(this.as_ptr(), other.as_ptr()) {
+            return max_len;
+        }
+
+        PREFIX_SIZE
+            + this
+                .iter()
+                .zip(other)
+                .skip(PREFIX_SIZE)
+                .take_while(|(a, b)| a == b)
+                .count()
+    }
+
+    /// Returns the smallest slice that is greater than every slice starting with this prefix.
+    ///
+    /// This is the exclusive upper bound of a prefix scan: the last byte that is not `0xFF`
+    /// is incremented, and everything after it is truncated.
+    ///
+    /// Returns `None` if there is no such slice (the prefix is empty or all `0xFF`),
+    /// in which case the scan is unbounded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use byteview::ByteView;
+    /// assert_eq!(Some(ByteView::from("abd")), ByteView::from("abc").prefix_successor());
+    /// assert_eq!(Some(ByteView::from("b")), ByteView::new(b"a\xFF\xFF").prefix_successor());
+    /// assert_eq!(None, ByteView::new(b"\xFF\xFF").prefix_successor());
+    /// assert_eq!(None, ByteView::from("").prefix_successor());
+    /// ```
+    #[must_use]
+    pub fn prefix_successor(&self) -> Option<Self> {
+        let bytes = self.get_slice();
+        let len = bytes.iter().rposition(|&byte| byte != 0xFF)? + 1;
+
+        // NOTE: We can use _unchecked to skip zeroing of the heap allocated slice
+        // because we overwrite it completely
+        let mut successor = Self::with_size_unchecked(len);
+
+        {
+            let data = successor.get_mut_slice();
+            data.copy_from_slice(bytes.get(..len).unwrap_or_default());
+
+            if let Some(last) = data.last_mut() {
+                *last += 1;
+            }
+        }
+
+        successor.update_prefix();
+
+        Some(successor)
+    }
+
+    /// Returns `true` if `other` references a range of the same heap allocation
+    /// that lies within this slice.
+    ///
+    /// This only compares addresses, so it is a cheap way to test
+    /// if a slice (e.g. a key) was sliced from another slice (e.g. a block).
+    ///
+    /// Inlined slices do not reference any heap allocation, so this always returns `false` for them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use byteview::ByteView;
+    /// let block = ByteView::from("helloworld_thisisaverylongstring");
+    /// let key = block.slice(1..25);
+    ///
+    /// assert!(block.contains_view(&key));
+    /// assert!(!key.contains_view(&block));
+    /// assert!(!block.contains_view(&ByteView::from("helloworld_thisisaverylongstring")));
+    /// ```
+    #[must_use]
+    pub fn contains_view(&self, other: &Self) -> bool {
+        if self.is_inline() || other.is_inline() {
+            return false;
+        }
+
+        // SAFETY: Both slices are not inlined, and we only compare addresses
+        unsafe {
+            if self.trailer.long.heap != other.trailer.long.heap {
+                return false;
+            }
+
+            let start = self.trailer.long.offset as usize;
+            let end = start + self.len();
+
+            let other_start = other.trailer.long.offset as usize;
+            let other_end = other_start + other.len();
+
+            start <= other_start && other_end <= end
+        }
+    }
+
+    /// Returns a cheap hash of the slice's length and first 4 bytes,
+    /// which never dereferences the heap pointer.
+    ///
+    /// Equal slices always have the same hash, but the hash is NOT cryptographic,
+    /// and all slices of the same length that share their first 4 bytes collide,
+    /// so it should only be used to pre-filter (e.g. to pick a bloom filter block or bucket),
+    /// not as a replacement for [`std::hash::Hash`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use byteview::ByteView;
+    /// let a = ByteView::from("helloworld_thisisaverylongstring");
+    /// let b = ByteView::from("helloworld_thisisaverylongstrinG");
+    /// let c = ByteView::from("hello");
+    ///
+    /// assert_eq!(a.fast_hash(), a.clone().fast_hash());
+    /// assert_eq!(a.fast_hash(), b.fast_hash());
+    /// assert_ne!(a.fast_hash(), c.fast_hash());
+    /// ```
+    #[must_use]
+    pub fn fast_hash(&self) -> u64 {
+        // SAFETY: Both trailer layouts have the prefix stored at the same position,
+        // and unused bytes of inlined slices are always zeroed
+        let prefix = unsafe { u32::from_be_bytes(self.trailer.long.prefix) };
+
+        // NOTE: The length always fits in a u32
+        #[allow(clippy::cast_possible_truncation)]
+        let len = self.len() as u32;
+
+        // NOTE: Finalizer of MurmurHash3, so every input bit affects every output bit
+        let mut hash = (u64::from(prefix) << 32) | u64::from(len);
+        hash ^= hash >> 33;
+        hash = hash.wrapping_mul(0xFF51_AFD7_ED55_8CCD);
+        hash ^= hash >> 33;
+        hash = hash.wrapping_mul(0xC4CE_B9FE_1A85_EC53);
+        hash ^ (hash >> 33)
+    }
+
+    /// Returns `true` if the slice is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the amount of bytes in the slice.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        if self.is_inline() {
+            self.tag() as usize
+        } else {
+            // SAFETY: We are not inlined
+            unsafe { self.trailer.long.len as usize }
+        }
+    }
+
+    /// Returns the first byte of the slice, or `None` if it is empty.
+    ///
+    /// The byte is read from the prefix, so no heap allocation is accessed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use byteview::ByteView;
+    /// assert_eq!(Some(&b'h'), ByteView::from("helloworld_thisisaverylongstring").first());
+    /// assert_eq!(None, ByteView::from("").first());
+    /// ```
+    #[must_use]
+    pub fn first(&self) -> Option<&u8> {
+        self.prefix().first()
+    }
+
+    /// Returns the last byte of the slice, or `None` if it is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use byteview::ByteView;
+    /// assert_eq!(Some(&b'g'), ByteView::from("helloworld_thisisaverylongstring").last());
+    /// assert_eq!(None, ByteView::from("").last());
+    /// ```
+    #[must_use]
+    pub fn last(&self) -> Option<&u8> {
+        self.get_slice().last()
+    }
+
+    /// Returns the byte or bytes at the given index or range, or `None` if it is out of bounds.
+    ///
+    /// Same as `<[u8]>::get`, so it accepts both single indices and ranges.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use byteview::ByteView;
+    /// let slice = ByteView::from("helloworld_thisisaverylongstring");
+    /// assert_eq!(Some(&b'e'), slice.get(1));
+    /// assert_eq!(Some(&b"world"[..]), slice.get(5..10));
+    /// assert_eq!(None, slice.get(32));
+    /// ```
+    #[must_use]
+    pub fn get<I: std::slice::SliceIndex<[u8]>>(&self, index: I) -> Option<&I::Output> {
+        self.get_slice().get(index)
+    }
+
+    /// Returns the bytes in the given range, or `None` if it is out of bounds.
+    ///
+    /// Same as [`ByteView::get`], but only accepts ranges.
+    ///
+    /// Unlike [`ByteView::try_slice`], this borrows the bytes instead of creating a new slice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use byteview::ByteView;
+    /// let slice = ByteView::from("helloworld");
+    /// assert_eq!(Some(&b"world"[..]), slice.get_range(5..));
+    /// assert_eq!(None, slice.get_range(5..11));
+    /// ```
+    #[must_use]
+    pub fn get_range<R: std::slice::SliceIndex<[u8], Output = [u8]>>(
+        &self,
+        range: R,
+    ) -> Option<&[u8]> {
+        self.get_slice().get(range)
+    }
+
+    /// Returns the slice as an array reference, or `None` if its length is not `N`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use byteview::ByteView;
+    /// let id = ByteView::from([7; 16]);
+    /// assert_eq!(Some(&[7; 16]), id.as_array::<16>());
+    /// assert_eq!(None, id.as_array::<8>());
+    /// ```
+    #[must_use]
+    pub fn as_array<const N: usize>(&self) -> Option<&[u8; N]> {
+        self.get_slice().try_into().ok()
+    }
+
+    pub(crate) fn get_mut_slice(&mut self) -> &mut [u8] {
+        let len = self.len();
+
+        if self.is_inline() {
+            unsafe { (*self.trailer.short).data.get_unchecked_mut(..len) }
+        } else {
+            unsafe { std::slice::from_raw_parts_mut(self.data_ptr().cast_mut(), len) }
+        }
+    }
+
+    /// Returns a pointer to the first byte of the slice, without creating a reference to it,
+    /// so the bytes do not need to be initialized.
+    pub(crate) fn get_ptr(&self) -> *const u8 {
+        if self.is_inline() {
+            unsafe { std::ptr::addr_of!(self.trailer.short.data).cast::<u8>() }
+        } else {
+            self.data_ptr()
+        }
+    }
+
+    /// Mutable version of [`ByteView::get_ptr`]
+    pub(crate) fn get_mut_ptr(&mut self) -> *mut u8 {
+        if self.is_inline() {
+            unsafe { std::ptr::addr_of_mut!((*self.trailer.short).data).cast::<u8>() }
+        } else {
+            self.data_ptr().cast_mut()
+        }
+    }
+
+    fn get_slice(&self) -> &[u8] {
+        if self.is_inline() {
+            self.get_short_slice()
+        } else {
+            self.get_long_slice()
+        }
+    }
+
+    /// Returns the inlined data as big-endian words, padded with zeros,
+    /// so comparing the words compares the data lexicographically.
+    ///
+    /// Shall only be called if the slice is inlined.
+    fn inline_words(&self) -> [u64; 3] {
+        debug_assert!(self.is_inline(), "slice is not inlined");
+
+        let mut words = [[0; 8]; 3];
+
+        // SAFETY: Shall only be called if slice is inlined,
+        // and the inlined data fits into the words
+        unsafe {
+            std::ptr::copy_nonoverlapping(
+                self.trailer.short.data.as_ptr(),
+                words.as_mut_ptr().cast::<u8>(),
+                INLINE_SIZE,
+            );
+        }
+
+        words.map(u64::from_be_bytes)
+    }
+
+    /// Returns the raw representation of an inlined slice as native words.
+    ///
+    /// Shall only be called if the slice is inlined.
+    fn raw_words(&self) -> [usize; std::mem::size_of::<Self>() / std::mem::size_of::<usize>()] {
+        debug_assert!(self.is_inline(), "slice is not inlined");
+
+        // SAFETY: Inlined slices are fully initialized (the length and data cover the struct),
+        // and the struct is aligned to a pointer
+        #[allow(clippy::cast_ptr_alignment)]
+        unsafe {
+            std::ptr::read(std::ptr::addr_of!(*self).cast())
+        }
+    }
+
+    fn get_short_slice(&self) -> &[u8] {
+        let len = self.len();
+
+        debug_assert!(
+            self.is_inline(),
+            "cannot get short slice - slice is not inlined"
+        );
+
+        // SAFETY: Shall only be called if slice is inlined
+        unsafe { self.trailer.short.data.get_unchecked(..len) }
+    }
+
+    fn get_long_slice(&self) -> &[u8] {
+        let len = self.len();
+
+        debug_assert!(
+            !self.is_inline(),
+            "cannot get long slice - slice is inlined"
+        );
+
+        // SAFETY: Shall only be called if slice is heap allocated
+        unsafe { std::slice::from_raw_parts(self.data_ptr(), len) }
+    }
+}
+
+macro_rules! impl_read_at {
+    ($($name:ident, $ty:ty, $conv:ident, $endian:literal;)*) => {
+        $(
+            #[doc = concat!("Reads a ", $endian, "`", stringify!($ty), "` at the given offset.")]
+            ///
+            /// Returns `None` if the value does not fit into the slice at the given offset.
+            #[must_use]
+            pub fn $name(&self, offset: usize) -> Option<$ty> {
+                self.read_array_at(offset).map(<$ty>::$conv)
+            }
+        )*
+    };
+}
+
+impl ByteView {
+    fn read_array_at<const N: usize>(&self, offset: usize) -> Option<[u8; N]> {
+        let bytes = self.get_slice().get(offset..offset.checked_add(N)?)?;
+        bytes.try_into().ok()
+    }
+
+    /// Reads a `u32` encoded as a variable-length integer (LEB128) at the given offset.
+    ///
+    /// Returns the value and its encoded size, or `None` if the integer is truncated
+    /// or does not fit in a `u32`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use byteview::ByteView;
+    /// let slice = ByteView::from(&[0, 0xAC, 0x02][..]);
+    /// assert_eq!(Some((300, 2)), slice.read_var_u32_at(1));
+    /// assert_eq!(None, slice.read_var_u32_at(3));
+    /// ```
+    #[must_use]
+    pub fn read_var_u32_at(&self, offset: usize) -> Option<(u32, usize)> {
+        crate::varint::decode_u32(self.get_slice().get(offset..)?).ok()
+    }
+
+    /// Reads a `u64` encoded as a variable-length integer (LEB128) at the given offset.
+    ///
+    /// Returns the value and its encoded size, or `None` if the integer is truncated
+    /// or does not fit in a `u64`.
+    #[must_use]
+    pub fn read_var_u64_at(&self, offset: usize) -> Option<(u64, usize)> {
+        crate::varint::decode_u64(self.get_slice().get(offset..)?).ok()
+    }
+
+    impl_read_at! {
+        read_u8_at, u8, from_le_bytes, "";
+        read_i8_at, i8, from_le_bytes, "";
+        read_u16_le_at, u16, from_le_bytes, "little-endian ";
+        read_u16_be_at, u16, from_be_bytes, "big-endian ";
+        read_i16_le_at, i16, from_le_bytes, "little-endian ";
+        read_i16_be_at, i16, from_be_bytes, "big-endian ";
+        read_u32_le_at, u32, from_le_bytes, "little-endian ";
+        read_u32_be_at, u32, from_be_bytes, "big-endian ";
+        read_i32_le_at, i32, from_le_bytes, "little-endian ";
+        read_i32_be_at, i32, from_be_bytes, "big-endian ";
+        read_u64_le_at, u64, from_le_bytes, "little-endian ";
+        read_u64_be_at, u64, from_be_bytes, "big-endian ";
+        read_i64_le_at, i64, from_le_bytes, "little-endian ";
+        read_i64_be_at, i64, from_be_bytes, "big-endian ";
+        read_u128_le_at, u128, from_le_bytes, "little-endian ";
+        read_u128_be_at, u128, from_be_bytes, "big-endian ";
+        read_f32_le_at, f32, from_le_bytes, "little-endian ";
+        read_f32_be_at, f32, from_be_bytes, "big-endian ";
+        read_f64_le_at, f64, from_le_bytes, "little-endian ";
+        read_f64_be_at, f64, from_be_bytes, "big-endian ";
+    }
+}
+
+#[cfg(feature = "checksum")]
+impl ByteView {
+    /// Creates a new slice from an existing byte slice, and records
+    /// an xxh3 checksum of its contents in the heap allocation.
+    ///
+    /// Use [`ByteView::verify`] to check the contents against the checksum later on,
+    /// for example after handing the slice across an FFI boundary.
+    ///
+    /// Inlined slices do not store a checksum.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length does not fit in a u32 (4 GiB).
+    #[must_use]
+    pub fn new_checksummed(slice: &[u8]) -> Self {
+        let mut view = Self::new(slice);
+        view.update_checksum(true);
+        view
+    }
+
+    /// Returns `true` if the slice has a stored checksum.
+    #[must_use]
+    pub fn has_checksum(&self) -> bool {
+        self.heap_region()
+            .is_some_and(|heap_region| heap_region.checksum.is_some())
+    }
+
+    /// Returns `false` if the contents of the backing heap allocation
+    /// do not match the stored checksum.
+    ///
+    /// Slices without a checksum are always considered valid.
+    #[must_use]
+    pub fn verify(&self) -> bool {
+        self.heap_region()
+            .and_then(|heap_region| heap_region.checksum)
+            .map_or(true, |checksum| Some(checksum) == self.compute_checksum())
+    }
+
+    fn compute_checksum(&self) -> Option<u64> {
+        self.get_heap_slice().map(xxhash_rust::xxh3::xxh3_64)
+    }
+
+    /// Recomputes the checksum after a mutation.
+    ///
+    /// If `force` is set, the checksum is recorded even if there was none before.
+    fn update_checksum(&mut self, force: bool) {
+        if !(force || self.has_checksum()) {
+            return;
+        }
+
+        let Some(checksum) = self.compute_checksum() else {
+            return;
+        };
+
+        // SAFETY: We only get here through unique ownership of the heap allocation
+        // (either while constructing, or through a `Mutator`)
+        unsafe {
+            #[allow(clippy::cast_ptr_alignment)]
+            let heap_region = self.trailer.long.heap.cast::<HeapAllocationHeader>();
+
+            std::ptr::addr_of_mut!((*heap_region.as_ptr()).checksum).write(Some(checksum));
+        }
+    }
+}
+
+#[cfg(feature = "hash-cache")]
+impl ByteView {
+    /// Returns an xxh3 hash of the slice's bytes.
+    ///
+    /// If the slice covers its whole heap allocation, the hash is computed only once,
+    /// and cached in the heap allocation, so it is shared by all clones of the slice.
+    /// Cached hashes are also used to quickly tell slices apart in [`PartialEq`].
+    ///
+    /// [`std::hash::Hash`] does not use this hash, because it needs to match
+    /// the hash of the borrowed `[u8]` (see [`std::borrow::Borrow`]).
+    /// To avoid rehashing large values, key hash maps by this hash instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use byteview::ByteView;
+    /// let value = ByteView::from(vec![1; 4_096]);
+    /// let copy = value.clone();
+    ///
+    /// // NOTE: Only computed once
+    /// assert_eq!(value.full_hash(), copy.full_hash());
+    /// assert_eq!(value.full_hash(), ByteView::from(&*value).full_hash());
+    /// ```
+    #[must_use]
+    pub fn full_hash(&self) -> u64 {
+        let Some(heap_region) = self.full_heap_region() else {
+            return xxhash_rust::xxh3::xxh3_64(self);
+        };
+
+        // NOTE: The bytes cannot change while the slice is shared,
+        // so racing threads would cache the same hash anyway
+        match heap_region.hash.load(Ordering::Relaxed) {
+            0 => {
+                let hash = xxhash_rust::xxh3::xxh3_64(self);
+                heap_region.hash.store(hash, Ordering::Relaxed);
+                hash
+            }
+            hash => hash,
+        }
+    }
+
+    /// Returns the cached hash, if the slice covers its whole heap allocation.
+    fn cached_hash(&self) -> Option<u64> {
+        self.full_heap_region()
+            .map(|heap_region| heap_region.hash.load(Ordering::Relaxed))
+            .filter(|&hash| hash != 0)
+    }
+
+    /// Returns the header of the heap allocation, if the slice covers all of its data region.
+    fn full_heap_region(&self) -> Option<&HeapAllocationHeader> {
+        let heap_region = self.heap_region()?;
+
+        // SAFETY: We are not inlined
+        let offset = unsafe { self.trailer.long.offset };
+
+        (offset == 0 && self.len() == heap_region.len as usize).then_some(heap_region)
+    }
+
+    /// Forgets the cached hash after a mutation.
+    fn invalidate_hash(&self) {
+        if let Some(heap_region) = self.heap_region() {
+            heap_region.hash.store(0, Ordering::Relaxed);
+        }
+    }
+}
+
+impl std::borrow::Borrow<[u8]> for ByteView {
+    fn borrow(&self) -> &[u8] {
+        self
+    }
+}
+
+impl AsRef<[u8]> for ByteView {
+    fn as_ref(&self) -> &[u8] {
+        self
+    }
+}
+
+impl FromIterator<u8> for ByteView {
+    fn from_iter<T>(iter: T) -> Self
+    where
+        T: IntoIterator<Item = u8>,
+    {
+        Self::from(iter.into_iter().collect::<Vec<u8>>())
+    }
+}
+
+impl From<&[u8]> for ByteView {
+    fn from(value: &[u8]) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<Arc<[u8]>> for ByteView {
+    fn from(value: Arc<[u8]>) -> Self {
+        Self::new(&value)
+    }
+}
+
+impl From<std::rc::Rc<[u8]>> for ByteView {
+    fn from(value: std::rc::Rc<[u8]>) -> Self {
+        Self::new(&value)
+    }
+}
+
+impl From<&Vec<u8>> for ByteView {
+    fn from(value: &Vec<u8>) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<std::borrow::Cow<'_, [u8]>> for ByteView {
+    fn from(value: std::borrow::Cow<'_, [u8]>) -> Self {
+        match value {
+            std::borrow::Cow::Borrowed(bytes) => Self::new(bytes),
+            std::borrow::Cow::Owned(vec) => Self::from_vec(vec),
+        }
+    }
+}
+
+impl From<Vec<u8>> for ByteView {
+    /// Takes ownership of the vector's allocation, without copying.
+    ///
+    /// The adopted allocation is referenced through a small handle, which costs
+    /// an additional (small) allocation, and an additional pointer indirection on every access,
+    /// so for short vectors, copying using [`ByteView::new`] may be cheaper.
+    ///
+    /// The vector's spare capacity is kept allocated (and counted in [`ByteView::heap_size`]),
+    /// so consider calling [`Vec::shrink_to_fit`] first.
+    fn from(value: Vec<u8>) -> Self {
+        Self::from_vec(value)
+    }
+}
+
+impl From<ByteView> for Vec<u8> {
+    /// See [`ByteView::into_vec`].
+    fn from(value: ByteView) -> Self {
+        value.into_vec()
+    }
+}
+
+impl From<ByteView> for Box<[u8]> {
+    /// See [`ByteView::into_vec`].
+    fn from(value: ByteView) -> Self {
+        value.into_vec().into_boxed_slice()
+    }
+}
+
+impl From<Box<[u8]>> for ByteView {
+    fn from(value: Box<[u8]>) -> Self {
+        Self::from_vec(value.into_vec())
+    }
+}
+
+impl From<&str> for ByteView {
+    fn from(value: &str) -> Self {
+        Self::from(value.as_bytes())
+    }
+}
+
+impl From<String> for ByteView {
+    /// Takes ownership of the string's allocation, without copying,
+    /// see `From<Vec<u8>>` for the costs of adopting an allocation.
+    fn from(value: String) -> Self {
+        Self::from_vec(value.into_bytes())
+    }
+}
+
+impl From<&String> for ByteView {
+    fn from(value: &String) -> Self {
+        Self::from(value.as_bytes())
+    }
+}
+
+impl From<Arc<str>> for ByteView {
+    fn from(value: Arc<str>) -> Self {
+        Self::from(&*value)
+    }
+}
+
+impl<const N: usize> From<[u8; N]> for ByteView {
+    fn from(value: [u8; N]) -> Self {
+        Self::from(value.as_slice())
+    }
+}
+
+impl<const N: usize> TryFrom<&ByteView> for [u8; N] {
+    type Error = std::array::TryFromSliceError;
+
+    /// Copies the slice into an array, failing if its length is not `N`.
+    fn try_from(value: &ByteView) -> Result<Self, Self::Error> {
+        Self::try_from(value.get_slice())
+    }
+}
+
+// NOTE: The helpers are shared with `StrView` and `human_readable`, so keep them visibly crate-internal
+#[cfg(feature = "serde")]
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) use serde::{borrow_from_source, has_source, ByteViewVisitor};
+
+#[cfg(feature = "serde")]
+#[allow(clippy::redundant_pub_crate)]
+mod serde {
+    use super::ByteView;
+    use crate::human_readable::Encoding;
+    use serde::de::{self, SeqAccess, Visitor};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::{cell::RefCell, fmt};
+
+    thread_local! {
+        /// The slice that is currently being deserialized from (see [`ByteView::deserialize_shared`])
+        static SOURCE: RefCell<Option<ByteView>> = const { RefCell::new(None) };
+    }
+
+    /// Returns `true` if a slice is currently being deserialized from.
+    pub(crate) fn has_source() -> bool {
+        SOURCE.with(|source| source.borrow().is_some())
+    }
+
+    /// Returns a slice of the slice that is currently being deserialized from,
+    /// if `bytes` lies within it.
+    pub(crate) fn borrow_from_source(bytes: &[u8]) -> Option<ByteView> {
+        SOURCE.with(|source| {
+            let source = source.borrow();
+            let source = source.as_ref()?;
+
+            // NOTE: We only compare addresses
+            let offset = (bytes.as_ptr() as usize).checked_sub(source.as_ptr() as usize)?;
+            let end = offset.checked_add(bytes.len())?;
+
+            (end <= source.len()).then(|| source.slice(offset..end))
+        })
+    }
+
+    impl ByteView {
+        /// Runs `f` (which should deserialize a value from the given bytes) on the slice,
+        /// so every [`ByteView`] and [`crate::StrView`] that is deserialized from borrowed input
+        /// shares the slice's heap allocation, instead of copying it.
+        ///
+        /// This works with any format that passes borrowed bytes or strings to the visitor
+        /// (e.g. `bincode`, or unescaped strings in `serde_json`).
+        ///
+        /// The slice is only known to the current thread while `f` runs, so values that are deserialized
+        /// on another thread (or outside of `f`) silently fall back to copying.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// # use byteview::ByteView;
+        /// use serde::{de::value::BorrowedBytesDeserializer, Deserialize};
+        ///
+        /// let input = ByteView::from("helloworld_thisisaverylongstring");
+        ///
+        /// let key = input.deserialize_shared(|bytes| {
+        ///     let deserializer = BorrowedBytesDeserializer::<serde::de::value::Error>::new(&bytes[5..]);
+        ///     ByteView::deserialize(deserializer)
+        /// })?;
+        ///
+        /// assert_eq!(b"world_thisisaverylongstring", &*key);
+        /// assert_eq!(2, input.ref_count());
+        /// # Ok::<(), serde::de::value::Error>(())
+        /// ```
+        ///
+        /// # Errors
+        ///
+        /// Returns the error returned by `f`.
+        pub fn deserialize_shared<T, E>(
+            &self,
+            f: impl FnOnce(&[u8]) -> Result<T, E>,
+        ) -> Result<T, E> {
+            /// Restores the previous source, even if `f` panics
+            struct Restore(Option<ByteView>);
+
+            impl Drop for Restore {
+                fn drop(&mut self) {
+                    SOURCE.with(|source| *source.borrow_mut() = self.0.take());
+                }
+            }
+
+            // NOTE: Inlined slices are copied anyway
+            if self.is_inline() {
+                return f(self);
+            }
+
+            let _restore = Restore(SOURCE.with(|source| source.replace(Some(self.clone()))));
+
+            f(self)
+        }
+    }
+
+    /// Serialized as raw bytes, or as a hex string in human-readable formats
+    /// (see [`crate::human_readable`])
+    impl Serialize for ByteView {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            Encoding::Hex.serialize(self, serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for ByteView {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            Encoding::Hex.deserialize(deserializer)
+        }
+    }
+
+    /// Visits raw bytes, a sequence of bytes, or a string
+    /// (which is decoded using the given encoding, if any)
+    pub(crate) struct ByteViewVisitor(pub(crate) Option<Encoding>);
+
+    impl<'de> Visitor<'de> for ByteViewVisitor {
+        type Value = ByteView;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            match self.0 {
+                Some(Encoding::Hex) => formatter.write_str("a hex string or byte array"),
+                Some(Encoding::Base64) => formatter.write_str("a base64 string or byte array"),
+                None => formatter.write_str("a byte array"),
+            }
+        }
+
+        fn visit_bytes<E>(self, v: &[u8]) -> Result<ByteView, E>
+        where
+            E: de::Error,
+        {
+            ByteView::try_new(v).map_err(E::custom)
+        }
+
+        fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<ByteView, E>
+        where
+            E: de::Error,
+        {
+            if u32::try_from(v.len()).is_err() {
+                return Err(E::invalid_length(v.len(), &self));
+            }
+
+            // NOTE: Takes ownership of the buffer, without copying
+            Ok(ByteView::from(v))
+        }
+
+        fn visit_borrowed_bytes<E>(self, v: &'de [u8]) -> Result<ByteView, E>
+        where
+            E: de::Error,
+        {
+            borrow_from_source(v).map_or_else(|| self.visit_bytes(v), Ok)
+        }
+
+        fn visit_borrowed_str<E>(self, v: &'de str) -> Result<ByteView, E>
+        where
+            E: de::Error,
+        {
+            match self.0 {
+                Some(_) => self.visit_str(v),
+                None => self.visit_borrowed_bytes(v.as_bytes()),
+            }
+        }
+
+        fn visit_str<E>(self, v: &str) -> Result<ByteView, E>
+        where
+            E: de::Error,
+        {
+            match self.0 {
+                Some(encoding) => self.visit_byte_buf(encoding.decode(v).map_err(E::custom)?),
+                None => self.visit_bytes(v.as_bytes()),
+            }
+        }
+
+        fn visit_string<E>(self, v: String) -> Result<ByteView, E>
+        where
+            E: de::Error,
+        {
+            match self.0 {
+                Some(_) => self.visit_str(&v),
+                None => self.visit_byte_buf(v.into_bytes()),
+            }
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> Result<ByteView, A::Error>
+        where
+            A: SeqAccess<'de>,
+        {
+            // NOTE: Do not trust the size hint too much, it may come from untrusted input
+            let capacity = seq.size_hint().unwrap_or_default().min(4_096);
+            let mut bytes = Vec::with_capacity(capacity);
+
+            while let Some(byte) = seq.next_element()? {
+                bytes.push(byte);
+            }
+
+            self.visit_byte_buf(bytes)
+        }
+    }
+}
+
+#[cfg(feature = "bytes")]
+mod bytes {
+    use super::ByteView;
+    use bytes::Bytes;
+
+    impl From<Bytes> for ByteView {
+        /// Converts [`Bytes`] into a slice, without copying.
+        ///
+        /// The [`Bytes`] are kept alive until the last slice referencing them is dropped.
+        /// If they are short enough to be inlined, they are copied instead.
+        fn from(value: Bytes) -> Self {
+            Self::from_owner(value)
+        }
+    }
+
+    #[cfg(not(feature = "local"))]
+    impl From<ByteView> for Bytes {
+        /// Converts a slice into [`Bytes`], without copying.
+        ///
+        /// The slice is kept alive until the [`Bytes`] are dropped.
+        /// Inlined slices are copied instead.
+        fn from(value: ByteView) -> Self {
+            if value.is_inline() {
+                return Self::copy_from_slice(&value);
+            }
+
+            Self::from_owner(value)
+        }
+    }
+
+    impl bytes::Buf for ByteView {
+        fn remaining(&self) -> usize {
+            self.len()
+        }
+
+        fn chunk(&self) -> &[u8] {
+            self
+        }
+
+        fn advance(&mut self, cnt: usize) {
+            Self::advance(self, cnt);
+        }
+
+        /// Splits off the first `len` bytes, without copying.
+        #[cfg(not(feature = "local"))]
+        fn copy_to_bytes(&mut self, len: usize) -> Bytes {
+            Bytes::from(self.split_to(len))
+        }
+    }
+}
+
+#[cfg(feature = "prost")]
+mod prost {
+    use super::ByteView;
+
+    impl ByteView {
+        /// Encodes a protobuf message into a new slice, using a single heap allocation.
+        ///
+        /// Messages can be decoded from a slice directly (because it implements [`bytes::Buf`]).
+        /// If the message's `bytes` fields use [`bytes::Bytes`], they share the slice's heap allocation,
+        /// instead of being copied.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// # use byteview::ByteView;
+        /// use prost::Message;
+        ///
+        /// #[derive(Clone, PartialEq, Message)]
+        /// struct Payload {
+        ///     #[prost(bytes = "bytes", tag = "1")]
+        ///     data: bytes::Bytes,
+        /// }
+        ///
+        /// let message = Payload {
+        ///     data: "helloworld_thisisaverylongstring".into(),
+        /// };
+        ///
+        /// let slice = ByteView::from_message(&message);
+        /// assert_eq!(message.encoded_len(), slice.len());
+        ///
+        /// let decoded = Payload::decode(slice).unwrap();
+        /// assert_eq!(message, decoded);
+        /// ```
+        ///
+        /// If the message writes fewer bytes than its `encoded_len` reports,
+        /// the slice only contains the written bytes.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the encoded length does not fit in a u32 (4 GiB).
+        #[must_use]
+        pub fn from_message(message: &impl prost::Message) -> Self {
+            // IMPORTANT: `Message` is a safe trait, so we cannot trust `encoded_len`
+            // to match the bytes written by `encode_raw`, and need to zero the slice
+            let len = message.encoded_len();
+            let mut view = Self::with_size(len);
+
+            let mut buf = view.get_mut_slice();
+            message.encode_raw(&mut buf);
+            let written = len - buf.len();
+
+            view.update_prefix();
+            view.truncate(written);
+            view
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+mod tokio {
+    use super::{ByteView, Mutator};
+    use tokio::io::{AsyncRead, AsyncReadExt};
+
+    impl ByteView {
+        /// Creates a slice and populates it with `len` bytes
+        /// from the given async reader.
+        ///
+        /// Same as [`ByteView::from_reader`], but for [`AsyncRead`]ers.
+        ///
+        /// # Errors
+        ///
+        /// Returns an error if an I/O error occurred.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the length does not fit in a u32 (4 GiB).
+        #[allow(clippy::future_not_send)]
+        pub async fn from_async_reader<R: AsyncRead + Unpin>(
+            reader: &mut R,
+            len: usize,
+        ) -> std::io::Result<Self> {
+            // NOTE: See `from_reader`
+            let mut s = Self::with_size_unchecked(len);
+            {
+                let mut builder = Mutator(&mut s);
+                reader.read_exact(&mut builder).await?;
+            }
+            Ok(s)
+        }
+    }
+}
+
+#[cfg(feature = "futures")]
+mod futures {
+    use super::ByteView;
+    use futures_core::Stream;
+    use std::io::{Error, ErrorKind};
+
+    impl ByteView {
+        /// Creates a slice and populates it with the chunks of the given stream,
+        /// which need to add up to exactly `len` bytes.
+        ///
+        /// The chunks are copied into a single heap allocation as they come in,
+        /// so there is no need to buffer them first.
+        ///
+        /// # Errors
+        ///
+        /// Returns the first error yielded by the stream.
+        ///
+        /// If the stream yields more than `len` bytes, an error of kind [`ErrorKind::InvalidData`]
+        /// is returned; if it ends before yielding `len` bytes, an error of kind [`ErrorKind::UnexpectedEof`].
+        ///
+        /// # Panics
+        ///
+        /// Panics if the length does not fit in a u32 (4 GiB).
+        #[allow(clippy::future_not_send)]
+        pub async fn from_stream<S, B, E>(stream: S, len: usize) -> Result<Self, E>
+        where
+            S: Stream<Item = Result<B, E>>,
+            B: AsRef<[u8]>,
+            E: From<Error>,
+        {
+            // NOTE: We can use _unchecked to skip zeroing of the heap allocated slice
+            // because we fail if the stream does not yield exactly `len` bytes
+            let mut view = Self::with_size_unchecked(len);
+
+            let mut stream = std::pin::pin!(stream);
+            let mut written = 0;
+
+            while let Some(chunk) = std::future::poll_fn(|cx| stream.as_mut().poll_next(cx)).await {
+                let chunk = chunk?;
+                let chunk = chunk.as_ref();
+
+                let Some(dst) = view
+                    .get_mut_slice()
+                    .get_mut(written..(written + chunk.len()))
+                else {
+                    return Err(Error::new(
+                        ErrorKind::InvalidData,
+                        "stream yielded more bytes than expected",
+                    )
+                    .into());
+                };
+
+                dst.copy_from_slice(chunk);
+                written += chunk.len();
+            }
+
+            if written < len {
+                return Err(Error::from(ErrorKind::UnexpectedEof).into());
+            }
+
+            view.update_prefix();
+            Ok(view)
+        }
+    }
+}
+
+#[cfg(feature = "heed")]
+mod heed {
+    use super::ByteView;
+    use heed_traits::{BoxedError, BytesDecode, BytesEncode};
+    use std::borrow::Cow;
+
+    /// Encodes any byte slice (so a [`ByteView`] can be passed, too), without copying
+    impl<'a> BytesEncode<'a> for ByteView {
+        type EItem = [u8];
+
+        fn bytes_encode(item: &'a [u8]) -> Result<Cow<'a, [u8]>, BoxedError> {
+            Ok(Cow::Borrowed(item))
+        }
+    }
+
+    /// Decodes into a [`ByteView`]
+    ///
+    /// LMDB only hands out slices of its memory map that are valid during the transaction,
+    /// so decoding copies the bytes (short slices are inlined).
+    impl BytesDecode<'_> for ByteView {
+        type DItem = Self;
+
+        fn bytes_decode(bytes: &[u8]) -> Result<Self, BoxedError> {
+            Ok(Self::try_new(bytes)?)
+        }
+    }
+}
+
+#[cfg(feature = "redb")]
+mod redb {
+    use super::ByteView;
+    use redb::{Key, TypeName, Value};
+
+    /// Stored like a `&[u8]`, so existing tables can be opened using [`ByteView`]
+    ///
+    /// redb only hands out slices of its pages, so reading a value copies it
+    /// (short values are inlined).
+    impl Value for ByteView {
+        type SelfType<'a> = Self;
+        type AsBytes<'a> = &'a [u8];
+
+        fn fixed_width() -> Option<usize> {
+            None
+        }
+
+        fn from_bytes<'a>(data: &'a [u8]) -> Self
+        where
+            Self: 'a,
+        {
+            Self::new(data)
+        }
+
+        fn as_bytes<'a, 'b: 'a>(value: &'a Self) -> &'a [u8]
+        where
+            Self: 'b,
+        {
+            value
+        }
+
+        fn type_name() -> TypeName {
+            <&[u8]>::type_name()
+        }
+    }
+
+    impl Key for ByteView {
+        fn compare(data1: &[u8], data2: &[u8]) -> std::cmp::Ordering {
+            data1.cmp(data2)
+        }
+    }
+}
+
+#[cfg(feature = "rkyv")]
+mod rkyv {
+    use super::ByteView;
+    use rkyv::{
+        ser::{ScratchSpace, Serializer},
+        vec::{ArchivedVec, VecResolver},
+        Archive, Deserialize, Fallible, Serialize,
+    };
+
+    /// Archived like a `Vec<u8>`
+    impl Archive for ByteView {
+        type Archived = ArchivedVec<u8>;
+        type Resolver = VecResolver;
+
+        unsafe fn resolve(&self, pos: usize, resolver: Self::Resolver, out: *mut Self::Archived) {
+            ArchivedVec::resolve_from_slice(self, pos, resolver, out);
+        }
+    }
+
+    impl<S: ScratchSpace + Serializer + ?Sized> Serialize<S> for ByteView {
+        fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+            ArchivedVec::serialize_from_slice(self, serializer)
+        }
+    }
+
+    impl<D: Fallible + ?Sized> Deserialize<ByteView, D> for ArchivedVec<u8> {
+        fn deserialize(&self, _: &mut D) -> Result<ByteView, D::Error> {
+            Ok(ByteView::new(self))
+        }
+    }
+}
+
+#[cfg(feature = "equivalent")]
+mod equivalent {
+    use super::ByteView;
+    use equivalent::Equivalent;
+    use std::hash::BuildHasher;
+
+    // NOTE: `[u8]` is already covered by the blanket implementation, through `Borrow<[u8]>`
+    impl Equivalent<ByteView> for &[u8] {
+        fn equivalent(&self, key: &ByteView) -> bool {
+            *self == &**key
+        }
+    }
+
+    impl ByteView {
+        /// Returns the hash that a map using the given hasher computes for a slice
+        /// with the given bytes, without constructing a slice.
+        ///
+        /// Use it for lookups that take a precomputed hash, like raw entries or `hashbrown::HashTable`.
+        /// Maps that support [`Equivalent`] (like `hashbrown` and `indexmap`) can also be
+        /// probed directly using `&[u8]`.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// # use byteview::ByteView;
+        /// use hashbrown::{DefaultHashBuilder, HashTable};
+        ///
+        /// let hasher = DefaultHashBuilder::default();
+        ///
+        /// let mut table = HashTable::new();
+        /// let key = ByteView::from("helloworld_thisisaverylongstring");
+        /// table.insert_unique(ByteView::hash_borrowed(&hasher, &key), key, |key| {
+        ///     ByteView::hash_borrowed(&hasher, key)
+        /// });
+        ///
+        /// let needle: &[u8] = b"helloworld_thisisaverylongstring";
+        /// let hash = ByteView::hash_borrowed(&hasher, needle);
+        /// assert!(table.find(hash, |key| &**key == needle).is_some());
+        /// ```
+        #[must_use]
+        pub fn hash_borrowed<S: BuildHasher>(hasher: &S, bytes: &[u8]) -> u64 {
+            // NOTE: The slice hashes exactly like its bytes
+            hasher.hash_one(bytes)
+        }
+    }
+}
+
+#[cfg(feature = "zeroize")]
+mod zeroize {
+    use super::ByteView;
+    use zeroize::{Zeroize, ZeroizeOnDrop};
+
+    /// Resets the slice to an empty slice.
+    ///
+    /// The data is wiped once the last slice referencing it is dropped or zeroized,
+    /// so other slices that still reference it are unaffected.
+    impl Zeroize for ByteView {
+        fn zeroize(&mut self) {
+            *self = Self::default();
+        }
+    }
+
+    /// The data is wiped once the last slice referencing it is dropped
+    /// (unless it is backed by a custom [`crate::ByteSource`] or static data).
+    impl ZeroizeOnDrop for ByteView {}
+}
+
+// NOTE: Only used to share `with_layout` with `StrView`, so keep it visibly crate-internal
+#[cfg(feature = "arbitrary")]
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) mod arbitrary {
+    use super::ByteView;
+    use arbitrary::{Arbitrary, Result, Unstructured};
+    use std::{
+        collections::HashSet,
+        sync::{Mutex, OnceLock, PoisonError},
+    };
+
+    /// Returns a static copy of the given bytes.
+    ///
+    /// The copies are leaked, but deduplicated, so fuzzing does not leak memory
+    /// for every generated slice.
+    fn leak_static(bytes: &[u8]) -> &'static [u8] {
+        static LEAKED: OnceLock<Mutex<HashSet<&'static [u8]>>> = OnceLock::new();
+
+        let mut leaked = LEAKED
+            .get_or_init(Mutex::default)
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        if let Some(bytes) = leaked.get(bytes) {
+            return bytes;
+        }
+
+        let bytes: &'static [u8] = Box::leak(bytes.into());
+        leaked.insert(bytes);
+        bytes
+    }
+
+    /// Creates a slice of the given bytes, in any of the layouts a slice can have:
+    /// inlined (if short enough), on the heap (with spare capacity or headroom),
+    /// adopted from a vector, static, or sliced from a larger parent slice
+    /// that is padded with `before` and `after`.
+    pub(crate) fn with_layout(
+        u: &mut Unstructured<'_>,
+        before: &[u8],
+        bytes: &[u8],
+        after: &[u8],
+    ) -> Result<ByteView> {
+        let view = match u.int_in_range(0..=6)? {
+            0 => ByteView::new(bytes),
+            1 => ByteView::from_vec(bytes.to_vec()),
+            2 => ByteView::from_static(leak_static(bytes)),
+            3 => {
+                let mut view = ByteView::with_capacity(bytes.len() + after.len());
+                view.extend_from_slice(bytes);
+                view
+            }
+            4 => {
+                let mut view = ByteView::with_size_and_headroom(bytes.len(), before.len());
+
+                if let Some(mut mutator) = view.get_mut() {
+                    mutator.copy_from_slice(bytes);
+                }
+
+                view
+            }
+            5 => {
+                let parent = ByteView::from_vec([before, bytes, after].concat());
+                parent.slice(before.len()..(before.len() + bytes.len()))
+            }
+            _ => {
+                let parent = ByteView::new(&[before, bytes, after].concat());
+                parent.slice(before.len()..(before.len() + bytes.len()))
+            }
+        };
+
+        Ok(view)
+    }
+
+    impl<'a> Arbitrary<'a> for ByteView {
+        fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+            let before = <&[u8]>::arbitrary(u)?;
+            let bytes = <&[u8]>::arbitrary(u)?;
+            let after = <&[u8]>::arbitrary(u)?;
+            with_layout(u, before, bytes, after)
+        }
+
+        fn size_hint(depth: usize) -> (usize, Option<usize>) {
+            arbitrary::size_hint::and(<&[u8]>::size_hint(depth), (0, None))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ByteView;
+    use std::io::Cursor;
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn memsize() {
+        use crate::byteview::{LongRepr, ShortRepr, Trailer};
+
+        assert_eq!(
+            std::mem::size_of::<ShortRepr>(),
+            std::mem::size_of::<LongRepr>()
+        );
+        assert_eq!(
+            std::mem::size_of::<Trailer>(),
+            std::mem::size_of::<LongRepr>()
+        );
+
+        assert_eq!(24, std::mem::size_of::<ByteView>());
+        assert_eq!(23, super::INLINE_SIZE);
+
+        #[cfg(not(any(feature = "checksum", feature = "hash-cache")))]
+        assert_eq!(
+            48,
+            std::mem::size_of::<ByteView>() + std::mem::size_of::<super::HeapAllocationHeader>()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "checksum")]
+    fn checksum_verify() {
+        let slice = ByteView::new_checksummed(b"helloworld_thisisaverylongstring");
+        assert!(slice.has_checksum());
+        assert!(slice.verify());
+
+        let copy = slice.slice(5..);
+        assert!(copy.has_checksum());
+        assert!(copy.verify());
+
+        // Simulate a torn write
+        unsafe {
+            *slice.data_ptr().cast_mut() = b'x';
+        }
+        assert!(!slice.verify());
+        assert!(!copy.verify());
+    }
+
+    #[test]
+    #[cfg(feature = "checksum")]
+    fn checksum_get_mut() {
+        let mut slice = ByteView::new_checksummed(b"helloworld_thisisaverylongstring");
+
+        {
+            let mut mutator = slice.get_mut().unwrap();
+            mutator[0] = b'j';
+        }
+
+        assert!(slice.verify());
+        assert_eq!(b"jelloworld_thisisaverylongstring", &*slice);
+    }
+
+    #[test]
+    #[cfg(feature = "checksum")]
+    fn checksum_none() {
+        let slice = ByteView::new(b"helloworld_thisisaverylongstring");
+        assert!(!slice.has_checksum());
+        assert!(slice.verify());
+
+        let slice = ByteView::new_checksummed(b"short");
+        assert!(!slice.has_checksum());
+        assert!(slice.verify());
+    }
+
+    #[test]
+    fn slice_demotes_to_inline() {
+        let slice = ByteView::from("helloworld_thisisaverylongstring".repeat(1_000));
+
+        let suffix = slice.slice((slice.len() - 6)..);
+        assert!(suffix.is_inline());
+        assert_eq!(1, slice.ref_count());
+
+        drop(slice);
+        assert_eq!(b"string", &*suffix);
+    }
+
+    #[test]
+    fn starts_with_same_allocation() {
+        let a = ByteView::from("helloworld_thisisaverylongstring");
+        let b = a.slice(..25);
+        assert!(a.starts_with(&b));
+        assert!(!b.starts_with(&a));
+        assert!(a.starts_with(&a));
+
+        let c = a.slice(1..25);
+        assert!(!a.starts_with(&c));
+    }
+
+    #[test]
+    fn cmp_cross_type() {
+        for s in ["", "abc", "helloworld_thisisaverylongstring"] {
+            let slice = ByteView::from(s);
+            let bytes = s.as_bytes();
+
+            assert_eq!(slice, *bytes);
+            assert_eq!(slice, bytes);
+            assert_eq!(slice, bytes.to_vec());
+            assert_eq!(slice, *s);
+            assert_eq!(slice, s);
+
+            assert_eq!(*bytes, slice);
+            assert_eq!(bytes, slice);
+            assert_eq!(bytes.to_vec(), slice);
+            assert_eq!(*s, slice);
+            assert_eq!(s, slice);
+
+            assert_ne!(slice, "helloworld_thisisaverylongstrinG");
+            assert_ne!("helloworld_thisisaverylongstrinG", slice);
+        }
+
+        let slice = ByteView::from("helloworld_thisisaverylongstring");
+        assert!(slice < "helloworld_thisisaverylongstrinh");
+        assert!(slice > "helloworld_thisisaverylongstrinf");
+        assert!(slice > "helloworld");
+        assert!(slice < b"z".to_vec());
+        assert!(b"a"[..] < slice);
+        assert!("z" > slice);
+        assert_eq!(
+            Some(std::cmp::Ordering::Equal),
+            slice.partial_cmp("helloworld_thisisaverylongstring")
+        );
+        assert_eq!(
+            Some(std::cmp::Ordering::Less),
+            "abc".partial_cmp(&ByteView::from("abd"))
+        );
+    }
+
+    #[test]
+    fn fmt_hex() {
+        let slice = ByteView::from("helloworld_thisisaverylongstring");
+        let expected = "68656c6c6f776f726c645f74686973697361766572796c6f6e67737472696e67";
+
+        assert_eq!(expected, format!("{slice:x}"));
+        assert_eq!(expected.to_uppercase(), format!("{slice:X}"));
+        assert_eq!(format!("0x{expected}"), format!("{slice:#x}"));
+        assert_eq!("68656c6c...", format!("{slice:.4x}"));
+        assert_eq!("0x68656C6C...", format!("{slice:#.4X}"));
+        assert_eq!(expected, format!("{slice:.32x}"));
+        assert_eq!("...", format!("{slice:.0x}"));
+
+        let slice = ByteView::from(&[0x00, 0x0A, 0xFF][..]);
+        assert_eq!("000aff", format!("{slice:x}"));
+        assert_eq!("", format!("{:x}", ByteView::from("")));
+    }
+
+    #[test]
+    fn index() {
+        let slice = ByteView::from("helloworld_thisisaverylongstring");
+        assert_eq!(b'h', slice[0]);
+        assert_eq!(b'g', slice[31]);
+        assert_eq!(b"llow", &slice[2..6]);
+        assert_eq!(b"llowo", &slice[2..=6]);
+        assert_eq!(b"string", &slice[26..]);
+        assert_eq!(b"he", &slice[..2]);
+        assert_eq!(slice.len(), slice[..].len());
+    }
+
+    #[test]
+    #[should_panic = "range end index 40 out of range for slice of length 3"]
+    fn index_out_of_bounds() {
+        let slice = ByteView::from("abc");
+        let _ = &slice[1..40];
+    }
+
+    #[test]
+    fn first_last_get() {
+        for s in [
+            "",
+            "a",
+            "abc",
+            "helloworld",
+            "helloworld_thisisaverylongstring",
+        ] {
+            let bytes = s.as_bytes();
+            let slice = ByteView::from(s);
+
+            assert_eq!(bytes.first(), slice.first());
+            assert_eq!(bytes.last(), slice.last());
+
+            for idx in 0..=bytes.len() {
+                assert_eq!(bytes.get(idx), slice.get(idx));
+                assert_eq!(bytes.get(idx..), slice.get(idx..));
+                assert_eq!(bytes.get(idx..), slice.get_range(idx..));
+                assert_eq!(bytes.get(..idx), slice.get_range(..idx));
+            }
+
+            assert_eq!(None, slice.get_range(..=bytes.len()));
+
+            // NOTE: Sliced from a heap allocation, so the prefix is taken from the parent
+            let parent = ByteView::from("_helloworld_thisisaverylongstring");
+            let sliced = parent.slice(1..=s.len());
+            let expected = parent.get_range(1..=s.len()).unwrap_or_default();
+            assert_eq!(expected.first(), sliced.first());
+            assert_eq!(expected.last(), sliced.last());
+            assert_eq!(expected.get(3), sliced.get(3));
+        }
+
+        let mut builder = ByteView::with_capacity(100);
+        assert_eq!(None, builder.first());
+        builder.extend_from_slice(b"ab");
+        assert_eq!(Some(&b'a'), builder.first());
+        assert_eq!(Some(&b'b'), builder.get(1));
+        assert_eq!(Some(&b'b'), builder.last());
+    }
+
+    #[test]
+    fn trim_ascii() {
+        let slice = ByteView::from("\t\n helloworld_thisisaverylongstring \x0C\r\n");
+        let trimmed = slice.trim_ascii();
+        let trimmed_start = slice.trim_ascii_start();
+        let trimmed_end = slice.trim_ascii_end();
+
+        assert_eq!(b"helloworld_thisisaverylongstring", &*trimmed);
+        assert_eq!(
+            b"helloworld_thisisaverylongstring \x0C\r\n",
+            &*trimmed_start
+        );
+        assert_eq!(b"\t\n helloworld_thisisaverylongstring", &*trimmed_end);
+        assert_eq!(4, slice.ref_count());
+        assert_eq!(Some(3), trimmed.offset_in_parent());
+
+        for s in ["", " ", " \t\n ", "a", " a ", "a b", "\x0B a"] {
+            let slice = ByteView::from(s);
+            assert_eq!(
+                s.trim_matches(|c: char| c.is_ascii_whitespace()).as_bytes(),
+                &*slice.trim_ascii()
+            );
+            assert_eq!(
+                s.trim_start_matches(|c: char| c.is_ascii_whitespace())
+                    .as_bytes(),
+                &*slice.trim_ascii_start()
+            );
+            assert_eq!(
+                s.trim_end_matches(|c: char| c.is_ascii_whitespace())
+                    .as_bytes(),
+                &*slice.trim_ascii_end()
+            );
+        }
+    }
+
+    #[test]
+    fn ends_with() {
+        let a = ByteView::from("helloworld_thisisaverylongstring");
+        let b = a.slice(7..);
+        assert!(a.ends_with(&b));
+        assert!(!b.ends_with(&a));
+        assert!(a.ends_with(&a));
+        assert!(a.ends_with(""));
+        assert!(a.ends_with("longstring"));
+        assert!(!a.ends_with("Longstring"));
+
+        let c = a.slice(7..31);
+        assert!(!a.ends_with(&c));
+
+        let short = ByteView::from("abc");
+        assert!(short.ends_with("bc"));
+        assert!(!short.ends_with("ab"));
+        assert!(!short.ends_with("zabc"));
+    }
+
+    #[test]
+    fn longest_common_prefix() {
+        let a = ByteView::from("helloworld_thisisaverylongstring");
+        let b = ByteView::from("helloworld_thisisanotherlongstring");
+
+        assert_eq!(18, a.longest_common_prefix(&b));
+        assert_eq!(18, b.longest_common_prefix(&a));
+        assert_eq!(a.len(), a.longest_common_prefix(&a));
+        assert_eq!(25, a.longest_common_prefix(&a.slice(..25)));
+        assert_eq!(0, a.slice(1..).longest_common_prefix(&a));
+
+        assert_eq!(3, a.longest_common_prefix(&ByteView::from("hel")));
+        assert_eq!(4, a.longest_common_prefix(&ByteView::from("hell")));
+        assert_eq!(2, a.longest_common_prefix(&ByteView::from("heap")));
+        assert_eq!(4, a.longest_common_prefix(&ByteView::from("hellO")));
+        assert_eq!(5, a.longest_common_prefix(&ByteView::from("helloW")));
+        assert_eq!(0, a.longest_common_prefix(&ByteView::from("")));
+    }
+
+    #[test]
+    fn prefix_successor() {
+        let prefix = ByteView::from("helloworld_thisisaverylongstring");
+        let successor = prefix.prefix_successor().unwrap();
+        assert_eq!(b"helloworld_thisisaverylongstrinh", &*successor);
+        assert!(prefix < successor);
+        assert!(ByteView::new(b"helloworld_thisisaverylongstring_\xFF") < successor);
+
+        let prefix = ByteView::new(b"helloworld_thisisaverylongstring\xFF\xFF");
+        assert_eq!(Some(successor), prefix.prefix_successor());
+
+        assert_eq!(
+            Some(ByteView::new(b"\x00\x01")),
+            ByteView::new(b"\x00\x00\xFF").prefix_successor(),
+        );
+        assert_eq!(
+            Some(ByteView::new(b"\x01")),
+            ByteView::new(b"\x00").prefix_successor(),
+        );
+        assert_eq!(None, ByteView::new(b"\xFF").prefix_successor());
+        assert_eq!(None, ByteView::from([0xFF; 100]).prefix_successor());
+        assert_eq!(None, ByteView::from("").prefix_successor());
+    }
+
+    #[test]
+    fn read_at() {
+        let mut slice = ByteView::with_size(32);
+
+        {
+            let mut mutator = slice.get_mut().unwrap();
+            mutator.put_u16_le(0, 0x1234);
+            mutator.put_u32_be(2, 0xDEAD_BEEF);
+            mutator.put_i64_le(6, -5);
+            mutator.put_u64_be(24, u64::MAX);
+        }
+
+        assert_eq!(Some(0x34), slice.read_u8_at(0));
+        assert_eq!(Some(0x1234), slice.read_u16_le_at(0));
+        assert_eq!(Some(0x3412), slice.read_u16_be_at(0));
+        assert_eq!(Some(0xDEAD_BEEF), slice.read_u32_be_at(2));
+        assert_eq!(Some(-5), slice.read_i64_le_at(6));
+        assert_eq!(Some(u64::MAX), slice.read_u64_be_at(24));
+
+        assert_eq!(None, slice.read_u64_be_at(25));
+        assert_eq!(None, slice.read_u8_at(32));
+        assert_eq!(None, slice.read_u32_le_at(usize::MAX));
+
+        let inline = slice.slice(2..6);
+        assert!(inline.is_inline());
+        assert_eq!(Some(0xDEAD_BEEF), inline.read_u32_be_at(0));
+    }
+
+    #[test]
+    fn raw_parts() {
+        let slice = ByteView::from("helloworld_thisisaverylongstring");
+
+        let parts = slice.slice(5..).into_raw();
+        let short = ByteView::from("abc").into_raw();
+        assert_eq!(2, slice.ref_count());
+
+        let copy = unsafe { ByteView::from_raw(parts) };
+        assert_eq!(b"world_thisisaverylongstring", &*copy);
+        assert_eq!(2, slice.ref_count());
+
+        drop(copy);
+        assert_eq!(1, slice.ref_count());
+
+        assert_eq!(b"abc", &*unsafe { ByteView::from_raw(short) });
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore = "leaks intentionally")]
+    fn leak() {
+        static DATA: &[u8] = b"helloworld_thisisastaticstring";
+
+        let slice = ByteView::from("helloworld_thisisaverylongstring");
+        let copy = slice.slice(5..);
+
+        let leaked = copy.leak();
+        assert_eq!(b"world_thisisaverylongstring", leaked);
+        assert_eq!(2, slice.ref_count());
+        drop(slice);
+        assert_eq!(b"world_thisisaverylongstring", leaked);
+
+        let leaked = ByteView::from("abc").leak();
+        assert_eq!(b"abc", leaked);
+
+        assert!(std::ptr::eq(DATA, ByteView::from_static(DATA).leak()));
+    }
+
+    #[test]
+    fn contains_view() {
+        let a = ByteView::from("helloworld_thisisaverylongstring_andevenlonger");
+        let b = a.slice(5..40);
+        let c = b.slice(1..30);
+
+        assert!(a.contains_view(&a));
+        assert!(a.contains_view(&b));
+        assert!(a.contains_view(&c));
+        assert!(b.contains_view(&c));
+        assert!(!c.contains_view(&b));
+        assert!(!b.contains_view(&a));
+
+        let d = a.slice(..30);
+        assert!(!b.contains_view(&d));
+        assert!(!a.contains_view(&a.slice(..4)));
+    }
+
+    #[test]
+    fn fan_out() {
+        let slice = ByteView::from("helloworld_thisisaverylongstring");
+
+        let copies = slice.fan_out(10);
+        assert_eq!(11, slice.ref_count());
+        assert!(copies.iter().all(|copy| *copy == slice));
+
+        drop(copies);
+        assert_eq!(1, slice.ref_count());
+
+        assert!(slice.fan_out(0).is_empty());
+        assert_eq!(1, slice.ref_count());
+    }
+
+    #[test]
+    fn fan_out_inline() {
+        let slice = ByteView::from("abc");
+
+        let copies = slice.fan_out(3);
+        assert_eq!(3, copies.len());
+        assert!(copies.iter().all(|copy| *copy == slice));
+        assert_eq!(1, slice.ref_count());
+    }
+
+    #[test]
+    #[cfg(feature = "bytes")]
+    fn bytes_interop() {
+        let bytes = bytes::Bytes::from("helloworld_thisisaverylongstring");
+        let ptr = bytes.as_ptr();
+
+        let slice = ByteView::from(bytes);
+        assert_eq!(b"helloworld_thisisaverylongstring", &*slice);
+        assert_eq!(ptr, slice.as_ptr());
+
+        #[cfg(not(feature = "local"))]
+        {
+            let bytes = bytes::Bytes::from(slice.clone());
+            assert_eq!(ptr, bytes.as_ptr());
+            assert_eq!(2, slice.ref_count());
+
+            drop(bytes);
+            assert_eq!(1, slice.ref_count());
+
+            let bytes = bytes::Bytes::from(ByteView::from("abc"));
+            assert_eq!(b"abc", &*bytes);
+        }
+
+        let slice = ByteView::from(bytes::Bytes::from_static(b"abc"));
+        assert!(slice.is_inline());
+    }
+
+    #[test]
+    #[cfg(all(feature = "bytes", not(feature = "local")))]
+    fn bytes_buf() {
+        use bytes::Buf;
+
+        let mut slice = ByteView::from("helloworld_thisisaverylongstring");
+        let ptr = slice.as_ptr();
+        assert_eq!(32, slice.remaining());
+
+        let head = slice.copy_to_bytes(5);
+        assert_eq!(b"hello", &*head);
+
+        assert_eq!(b'w', slice.get_u8());
+
+        let tail = slice.copy_to_bytes(26);
+        assert_eq!(b"orld_thisisaverylongstring", &*tail);
+        assert_eq!(ptr.wrapping_add(6), tail.as_ptr());
+        assert!(!slice.has_remaining());
+    }
+
+    #[test]
+    #[cfg(feature = "prost")]
+    fn prost_roundtrip() {
+        use prost::Message;
+
+        #[derive(Clone, PartialEq, Message)]
+        struct Payload {
+            #[prost(uint64, tag = "1")]
+            id: u64,
+
+            #[prost(bytes = "bytes", tag = "2")]
+            data: bytes::Bytes,
+        }
+
+        let message = Payload {
+            id: 7,
+            data: "helloworld_thisisaverylongstring".into(),
+        };
+
+        let slice = ByteView::from_message(&message);
+        assert_eq!(message.encode_to_vec(), &*slice);
+
+        #[cfg(not(feature = "local"))]
+        let range = slice.as_ptr_range();
+
+        let decoded = Payload::decode(slice).unwrap();
+        assert_eq!(message, decoded);
+
+        // NOTE: The decoded bytes field shares the slice's heap allocation
+        #[cfg(not(feature = "local"))]
+        assert!(range.contains(&decoded.data.as_ptr()));
+
+        let inline = ByteView::from_message(&Payload::default());
+        assert!(inline.is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "prost")]
+    fn prost_wrong_encoded_len() {
+        /// Claims to be longer than it actually is
+        #[derive(Debug, Default)]
+        struct Liar;
+
+        impl prost::Message for Liar {
+            fn encode_raw(&self, buf: &mut impl bytes::BufMut) {
+                buf.put_slice(b"abc");
+            }
+
+            fn merge_field(
+                &mut self,
+                tag: u32,
+                wire_type: prost::encoding::WireType,
+                buf: &mut impl bytes::Buf,
+                ctx: prost::encoding::DecodeContext,
+            ) -> Result<(), prost::DecodeError> {
+                prost::encoding::skip_field(wire_type, tag, buf, ctx)
+            }
+
+            fn encoded_len(&self) -> usize {
+                64
+            }
+
+            fn clear(&mut self) {}
+        }
+
+        // NOTE: The bytes that were not written are not exposed
+        let slice = ByteView::from_message(&Liar);
+        assert_eq!(b"abc", &*slice);
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "tokio")]
+    async fn from_async_reader() -> std::io::Result<()> {
+        let mut reader: &[u8] = b"helloworld_thisisaverylongstringabc";
+
+        let slice = ByteView::from_async_reader(&mut reader, 32).await?;
+        assert_eq!(b"helloworld_thisisaverylongstring", &*slice);
+
+        let inline = ByteView::from_async_reader(&mut reader, 3).await?;
+        assert_eq!(b"abc", &*inline);
+
+        let err = ByteView::from_async_reader(&mut reader, 1)
+            .await
+            .unwrap_err();
+        assert_eq!(std::io::ErrorKind::UnexpectedEof, err.kind());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "futures")]
+    async fn from_stream() -> std::io::Result<()> {
+        use futures_util::stream;
+
+        let chunks = || {
+            stream::iter(["helloworld_", "thisisavery", "longstring"].map(Ok::<_, std::io::Error>))
+        };
+
+        let slice = ByteView::from_stream(chunks(), 32).await?;
+        assert_eq!(b"helloworld_thisisaverylongstring", &*slice);
+
+        let err = ByteView::from_stream(chunks(), 33).await.unwrap_err();
+        assert_eq!(std::io::ErrorKind::UnexpectedEof, err.kind());
+
+        let err = ByteView::from_stream(chunks(), 31).await.unwrap_err();
+        assert_eq!(std::io::ErrorKind::InvalidData, err.kind());
+
+        let inline =
+            ByteView::from_stream(stream::iter([Ok::<_, std::io::Error>(b"abc")]), 3).await?;
+        assert_eq!(b"abc", &*inline);
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "arbitrary")]
+    fn arbitrary() -> arbitrary::Result<()> {
+        use super::HeapKind;
+        use arbitrary::{Arbitrary, Unstructured};
+        use rand::RngCore;
+
+        let mut rng = rand::thread_rng();
+        let mut layouts = std::collections::HashSet::new();
+
+        for _ in 0..1_000 {
+            let mut data = [0; 256];
+            rng.fill_bytes(&mut data);
+
+            let view = ByteView::arbitrary(&mut Unstructured::new(&data))?;
+            let s = crate::StrView::arbitrary(&mut Unstructured::new(&data))?;
+            assert!(std::str::from_utf8(s.as_bytes()).is_ok());
+
+            let layout = if view.is_inline() {
+                "inline"
+            } else if view.heap_size() == 0 {
+                "static"
+            } else if view.headroom() > 0 {
+                "headroom"
+            } else if view.capacity() > view.len() {
+                "capacity"
+            } else if view
+                .heap_buffer()
+                .is_some_and(|parent| parent.len() > view.len())
+            {
+                "sliced"
+            } else if view
+                .heap_region()
+                .is_some_and(|heap_region| heap_region.kind == HeapKind::Vec)
+            {
+                "vec"
+            } else {
+                "heap"
+            };
+
+            layouts.insert(layout);
+        }
+
+        assert_eq!(7, layouts.len(), "{layouts:?}");
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "heed")]
+    fn heed() -> Result<(), heed_traits::BoxedError> {
+        use crate::StrView;
+        use heed_traits::{BytesDecode, BytesEncode};
+
+        let slice = ByteView::from("helloworld_thisisaverylongstring");
+
+        let bytes = ByteView::bytes_encode(&slice)?;
+        assert_eq!(slice.as_ptr(), bytes.as_ptr());
+        assert_eq!(slice, ByteView::bytes_decode(&bytes)?);
+
+        let bytes = StrView::bytes_encode("helloworld")?;
+        assert_eq!(StrView::from("helloworld"), StrView::bytes_decode(&bytes)?);
+        assert!(StrView::bytes_decode(b"\xFF").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "redb")]
+    fn redb() -> Result<(), Box<dyn std::error::Error>> {
+        use crate::StrView;
+        use redb::{backends::InMemoryBackend, Database, ReadableTable, TableDefinition};
+
+        const TABLE: TableDefinition<ByteView, StrView> = TableDefinition::new("table");
+        const RAW_TABLE: TableDefinition<&[u8], &str> = TableDefinition::new("table");
+
+        let db = Database::builder().create_with_backend(InMemoryBackend::new())?;
+
+        let tx = db.begin_write()?;
+        {
+            let mut table = tx.open_table(TABLE)?;
+            table.insert(
+                ByteView::from("b"),
+                StrView::from("helloworld_thisisaverylongstring"),
+            )?;
+            table.insert(ByteView::from("a"), StrView::from("abc"))?;
+        }
+        tx.commit()?;
+
+        let tx = db.begin_read()?;
+        let table = tx.open_table(TABLE)?;
+
+        let value = table.get(ByteView::from("b"))?.map(|guard| guard.value());
+        assert_eq!(
+            Some(StrView::from("helloworld_thisisaverylongstring")),
+            value
+        );
+
+        let keys = table
+            .iter()?
+            .map(|item| item.map(|(key, _)| key.value()))
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!([ByteView::from("a"), ByteView::from("b")], &*keys);
+
+        // NOTE: The table can also be opened using plain slices
+        let table = tx.open_table(RAW_TABLE)?;
+        assert_eq!(
+            Some("abc"),
+            table.get(&b"a"[..])?.as_ref().map(redb::AccessGuard::value)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "rkyv")]
+    fn rkyv() {
+        use crate::StrView;
+        use rkyv::{Archive, Deserialize, Infallible, Serialize};
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq, Eq)]
+        #[archive(crate = "rkyv")]
+        struct Entry {
+            key: ByteView,
+            value: StrView,
+        }
+
+        let entry = Entry {
+            key: ByteView::from("helloworld_thisisaverylongstring"),
+            value: StrView::from("abc"),
+        };
+
+        let bytes = rkyv::to_bytes::<_, 256>(&entry).unwrap();
+
+        // SAFETY: The bytes were just serialized from an Entry
+        let archived = unsafe { rkyv::archived_root::<Entry>(&bytes) };
+        assert_eq!(b"helloworld_thisisaverylongstring", archived.key.as_slice());
+        assert_eq!("abc", archived.value.as_str());
+
+        let deserialized: Entry = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(entry, deserialized);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_json() -> serde_json::Result<()> {
+        let slice = ByteView::from("helloworld_thisisaverylongstring");
+
+        let json = serde_json::to_string(&slice)?;
+        assert_eq!(
+            "\"68656c6c6f776f726c645f74686973697361766572796c6f6e67737472696e67\"",
+            json,
+        );
+        assert_eq!(slice, serde_json::from_str::<ByteView>(&json)?);
+
+        // NOTE: Byte arrays are still accepted
+        assert_eq!(
+            ByteView::from("abc"),
+            serde_json::from_str::<ByteView>("[97,98,99]")?,
+        );
+        assert_eq!(ByteView::from(""), serde_json::from_str::<ByteView>("[]")?,);
+        assert!(serde_json::from_str::<ByteView>("[256]").is_err());
+        assert!(serde_json::from_str::<ByteView>("\"abc\"").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_shared() -> Result<(), serde::de::value::Error> {
+        use serde::de::{
+            value::{BorrowedBytesDeserializer, BytesDeserializer, Error},
+            Deserialize,
+        };
+
+        let input = ByteView::from("helloworld_thisisaverylongstring");
+
+        let (shared, copied) = input.deserialize_shared(|bytes| {
+            let shared =
+                ByteView::deserialize(BorrowedBytesDeserializer::<Error>::new(&bytes[1..]))?;
+            let copied = ByteView::deserialize(BytesDeserializer::<Error>::new(&bytes[1..]))?;
+            Ok::<_, Error>((shared, copied))
+        })?;
+
+        assert_eq!(shared, copied);
+        assert!(input.contains_view(&shared));
+        assert!(!input.contains_view(&copied));
+
+        // NOTE: Borrowed bytes from elsewhere are copied
+        let other = b"helloworld_thisisanotherlongstring".to_vec();
+        let view = input.deserialize_shared(|_| {
+            ByteView::deserialize(BorrowedBytesDeserializer::<Error>::new(&other))
+        })?;
+        assert!(!input.contains_view(&view));
+
+        // NOTE: The source is only active inside deserialize_shared
+        let view = ByteView::deserialize(BorrowedBytesDeserializer::<Error>::new(&input))?;
+        assert!(!input.contains_view(&view));
+        assert_eq!(2, input.ref_count());
+
+        Ok(())
+    }
+
+    #[test]
+    fn find() {
+        let long = ByteView::from("helloworld_thisisaverylongstring_hello");
+
+        assert_eq!(Some(0), long.find("hello"));
+        assert_eq!(Some(33), long.rfind("hello"));
+        assert_eq!(Some(28), long.find(b"ring"));
+        assert_eq!(None, long.find("hellO"));
+        assert_eq!(None, long.rfind("hellO"));
+        assert_eq!(Some(0), long.find(""));
+        assert_eq!(Some(long.len()), long.rfind(""));
+        assert!(long.contains("verylong"));
+        assert!(!long.contains("helloworld_thisisaverylongstring_hello!"));
+
+        let short = long.slice(5..15);
+        assert!(short.is_inline());
+        assert_eq!(Some(6), short.find("th"));
+        assert_eq!(None, short.find("hello"));
+        assert!(!ByteView::from("").contains("a"));
+    }
+
+    #[test]
+    fn find_byte() {
+        let long = ByteView::from("helloworld_thisisaverylongstring_hello");
+
+        assert_eq!(Some(10), long.find_byte(b'_'));
+        assert_eq!(Some(32), long.rfind_byte(b'_'));
+        assert_eq!(Some(0), long.find_byte(b'h'));
+        assert_eq!(Some(37), long.rfind_byte(b'o'));
+        assert_eq!(None, long.find_byte(b'#'));
+        assert!(long.contains_byte(b'v'));
+        assert!(!long.contains_byte(0));
+
+        let short = long.slice(5..15);
+        assert_eq!(Some(5), short.find_byte(b'_'));
+        assert_eq!(Some(8), short.rfind_byte(b'i'));
+        assert!(!ByteView::from("").contains_byte(0));
+    }
+
+    #[test]
+    fn fast_hash() {
+        let long = ByteView::from("helloworld_thisisaverylongstring");
+        assert_eq!(long.fast_hash(), ByteView::from(&*long).fast_hash());
+        assert_eq!(long.fast_hash(), long.slice(..).fast_hash());
+
+        // NOTE: Only the first 4 bytes and the length are hashed
+        assert_eq!(
+            long.slice(..5).fast_hash(),
+            ByteView::from("hellX").fast_hash(),
+        );
+        assert_ne!(long.fast_hash(), long.slice(..31).fast_hash());
+        assert_ne!(long.fast_hash(), long.slice(1..).fast_hash());
+
+        assert_ne!(
+            ByteView::from("").fast_hash(),
+            ByteView::from("\0").fast_hash()
+        );
+        assert_ne!(
+            ByteView::from("a").fast_hash(),
+            ByteView::from("b").fast_hash()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "hash-cache")]
+    fn full_hash() {
+        use xxhash_rust::xxh3::xxh3_64;
+
+        let mut slice = ByteView::from("helloworld_thisisaverylongstring");
+        assert_eq!(None, slice.cached_hash());
+
+        let hash = slice.full_hash();
+        assert_eq!(xxh3_64(&slice), hash);
+        assert_eq!(Some(hash), slice.cached_hash());
+        assert_eq!(Some(hash), slice.clone().cached_hash());
+
+        // NOTE: Subslices do not use the cached hash
+        let sub = slice.slice(1..);
+        assert_eq!(None, sub.cached_hash());
+        assert_eq!(xxh3_64(&sub), sub.full_hash());
+        drop(sub);
+
+        slice.get_mut().unwrap()[0] = b'j';
+        assert_eq!(None, slice.cached_hash());
+        assert_eq!(xxh3_64(&slice), slice.full_hash());
+
+        // NOTE: Slices with different cached hashes are never equal
+        let other = ByteView::from("helloworld_thisisaverylongstring");
+        let _ = other.full_hash();
+        assert_ne!(slice, other);
+        assert_eq!(other, ByteView::from(&*other));
+
+        let mut built = ByteView::with_capacity(40);
+        built.extend_from_slice(b"helloworld_thisisaverylongstring");
+        built.extend_from_slice(b"_abcdefg");
+        let hash = built.full_hash();
+        assert_eq!(Some(hash), built.cached_hash());
+        built.truncate(32);
+        assert_eq!(None, built.cached_hash());
+    }
+
+    #[test]
+    #[cfg(feature = "equivalent")]
+    fn equivalent() {
+        use std::hash::BuildHasher;
+
+        let mut map = hashbrown::HashMap::new();
+        map.insert(ByteView::from("helloworld_thisisaverylongstring"), 1);
+        map.insert(ByteView::from("abc"), 2);
+
+        let needle: &[u8] = b"helloworld_thisisaverylongstring";
+        assert_eq!(Some(&1), map.get(&needle));
+        assert_eq!(Some(&1), map.get(needle));
+        assert_eq!(Some(&2), map.get(&&b"abc"[..]));
+        assert_eq!(None, map.get(&&b"abcd"[..]));
+
+        assert_eq!(
+            map.hasher().hash_one(ByteView::from(needle)),
+            ByteView::hash_borrowed(map.hasher(), needle),
+        );
+    }
+
+    #[test]
+    fn cmp_inline() {
+        let keys: [&[u8]; 9] = [
+            b"",
+            b"\0",
+            b"a",
+            b"a\0",
+            b"a\0\0\x01",
+            b"abcdefgh",
+            b"abcdefgh\0",
+            b"abcdefghijklmnopqrstuvw",
+            b"abcdefghijklmnopqrstuvx",
+        ];
+
+        for a in keys {
+            for b in keys {
+                let (x, y) = (ByteView::new(a), ByteView::new(b));
+                assert_eq!(a.cmp(b), x.cmp(&y), "{a:?} <=> {b:?}");
+                assert_eq!(a == b, x == y, "{a:?} == {b:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn from_io_slices() {
+        use std::io::IoSlice;
+
+        let slice = ByteView::from_io_slices(&[
+            IoSlice::new(b"helloworld_"),
+            IoSlice::new(b""),
+            IoSlice::new(b"thisisaverylongstring"),
+        ]);
+        assert_eq!(b"helloworld_thisisaverylongstring", &*slice);
+        assert_eq!(slice.as_ptr(), slice.as_io_slice().as_ptr());
+        assert_eq!(32, slice.as_io_slice().len());
+
+        let inline = ByteView::from_io_slices(&[IoSlice::new(b"abc"), IoSlice::new(b"def")]);
+        assert!(inline.is_inline());
+        assert_eq!(b"abcdef", &*inline.as_io_slice());
+
+        assert!(ByteView::from_io_slices(&[]).is_empty());
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn from_file() -> std::io::Result<()> {
+        let path = std::env::temp_dir().join(format!("byteview-from-file-{}", std::process::id()));
+        std::fs::write(&path, b"helloworld_thisisaverylongstring")?;
+
+        let slice = ByteView::from_file(&path)?;
+        assert_eq!(b"helloworld_thisisaverylongstring", &*slice);
+
+        let file = std::fs::File::open(&path)?;
+        std::fs::remove_file(&path)?;
+
+        let slice = ByteView::from_file_range(&file, 10, 22)?;
+        assert_eq!(b"_thisisaverylongstring", &*slice);
+
+        let inline = ByteView::from_file_range(&file, 5, 5)?;
+        assert_eq!(b"world", &*inline);
+
+        let err = ByteView::from_file_range(&file, 30, 3).unwrap_err();
+        assert_eq!(std::io::ErrorKind::UnexpectedEof, err.kind());
+
+        Ok(())
+    }
+
+    #[test]
+    fn heap_size() {
+        let slice = ByteView::from("helloworld_thisisaverylongstring");
+        assert_eq!(
+            std::mem::size_of::<super::HeapAllocationHeader>() + 32,
+            slice.heap_size()
+        );
+        assert_eq!(slice.heap_size(), slice.memory_usage());
+
+        let copies = slice.fan_out(3);
+        assert_eq!(slice.heap_size() / 4, copies[0].memory_usage());
+
+        let inline = ByteView::from("abc");
+        assert_eq!(0, inline.heap_size());
+        assert_eq!(0, inline.memory_usage());
+
+        let slice = ByteView::from_static(b"helloworld_thisisaverylongstring");
+        assert_eq!(0, slice.heap_size());
+
+        let slice = ByteView::from(vec![0; 100]);
+        assert!(slice.heap_size() > 100);
+
+        // NOTE: The spare capacity of an adopted vector is still allocated
+        let mut vec = Vec::with_capacity(1_000);
+        vec.extend_from_slice(&[0; 100]);
+        let slice = ByteView::from(vec);
+        assert!(slice.heap_size() > 1_000);
+        assert_eq!(slice.heap_size(), slice.slice(50..).heap_size());
+    }
+
+    #[test]
+    fn new_many() {
+        let items: [&[u8]; 4] = [
+            b"helloworld_thisisaverylongstring",
+            b"abc",
+            b"",
+            b"helloworld_thisisanotherlongstring",
+        ];
+
+        let slices = ByteView::new_many(items);
+        assert_eq!(4, slices.len());
+
+        for (slice, item) in slices.iter().zip(items) {
+            assert_eq!(item, &**slice);
+            assert_eq!(ByteView::new(item), *slice);
+        }
+
+        assert!(slices[1].is_inline());
+        assert!(slices[2].is_inline());
+        assert_eq!(2, slices[0].ref_count());
+        assert!(slices[0].heap_buffer().unwrap().contains_view(&slices[3]));
+
+        drop(slices);
+
+        let slices = ByteView::new_many([&b"abc"[..], b"def"]);
+        assert!(slices.iter().all(ByteView::is_inline));
+        assert!(ByteView::new_many([]).is_empty());
+    }
+
+    #[test]
+    fn with_capacity() {
+        let mut slice = ByteView::with_capacity(64);
+        assert!(!slice.is_inline());
+        assert!(slice.is_empty());
+        assert_eq!(64, slice.capacity());
+        assert_eq!(ByteView::new(b""), slice);
+
+        slice.extend_from_slice(b"abc");
+        let ptr = slice.as_ptr();
+        assert!(!slice.is_inline());
+        assert_eq!(b"abc", &*slice);
+        assert_eq!(ByteView::from("abc"), slice);
+        assert_eq!(slice, ByteView::from("abc"));
+        assert_eq!(b"bc", &*slice.slice(1..));
+
+        for byte in b"helloworld_thisisaverylongstring" {
+            slice.push(*byte);
         }
+        assert_eq!(ptr, slice.as_ptr());
+        assert_eq!(b"abchelloworld_thisisaverylongstring", &*slice);
+        assert_eq!(ByteView::from("abchelloworld_thisisaverylongstring"), slice);
+        assert_eq!(64, slice.capacity());
+
+        slice.extend_from_slice(&[0; 40]);
+        assert_ne!(ptr, slice.as_ptr());
+        assert_eq!(75, slice.len());
+        assert!(slice.capacity() >= 75);
     }
 
-    fn get_short_slice(&self) -> &[u8] {
-        let len = self.len();
+    #[test]
+    fn extend_from_slice_shared() {
+        let mut slice = ByteView::with_capacity(64);
+        slice.extend_from_slice(b"helloworld_thisisaverylongstring");
 
-        debug_assert!(
-            len <= INLINE_SIZE,
-            "cannot get short slice - slice is not inlined"
-        );
+        let copy = slice.clone();
+        slice.extend_from_slice(b"!");
+        assert_eq!(b"helloworld_thisisaverylongstring", &*copy);
+        assert_eq!(b"helloworld_thisisaverylongstring!", &*slice);
+        assert_eq!(1, copy.ref_count());
+        assert_eq!(1, slice.ref_count());
+    }
 
-        // SAFETY: Shall only be called if slice is inlined
-        unsafe {
-            let base_ptr = (self as *const Self).cast::<u8>();
-            let prefix_offset = base_ptr.add(std::mem::size_of::<u32>());
-            std::slice::from_raw_parts(prefix_offset, len)
+    #[test]
+    fn extend_from_slice_inline() {
+        let mut slice = ByteView::from("hello");
+        assert_eq!(super::INLINE_SIZE, slice.capacity());
+
+        slice.extend_from_slice(b"world");
+        assert!(slice.is_inline());
+        assert_eq!(b"helloworld", &*slice);
+
+        slice.extend_from_slice(b"_thisisaverylongstring");
+        assert!(!slice.is_inline());
+        assert_eq!(b"helloworld_thisisaverylongstring", &*slice);
+        assert_eq!(ByteView::from("helloworld_thisisaverylongstring"), slice);
+
+        let mut slice = ByteView::from_static(b"helloworld_thisisaverylongstring");
+        slice.extend_from_slice(b"!");
+        assert_eq!(b"helloworld_thisisaverylongstring!", &*slice);
+    }
+
+    #[test]
+    fn try_slice() {
+        use std::ops::Bound;
+
+        let slice = ByteView::from("helloworld_thisisaverylongstring");
+
+        let copy = slice.try_slice(1..=31).unwrap();
+        assert_eq!(b"elloworld_thisisaverylongstring", &*copy);
+        assert_eq!(2, slice.ref_count());
+
+        assert!(slice.try_slice(..).is_some());
+        assert!(slice.try_slice(32..).unwrap().is_empty());
+        assert!(slice.try_slice(33..).is_none());
+        assert!(slice.try_slice(..=32).is_none());
+        assert!(slice.try_slice(..=usize::MAX).is_none());
+        assert!(slice
+            .try_slice((Bound::Included(10), Bound::Excluded(5)))
+            .is_none());
+    }
+
+    #[test]
+    fn try_new() {
+        let slice = ByteView::try_new(b"helloworld_thisisaverylongstring").unwrap();
+        assert_eq!(b"helloworld_thisisaverylongstring", &*slice);
+
+        let slice = ByteView::try_with_size(100).unwrap();
+        assert_eq!([0; 100], *slice);
+    }
+
+    #[test]
+    fn try_from_reader() -> std::io::Result<()> {
+        let mut reader = Cursor::new(b"helloworld_thisisaverylongstring");
+
+        let slice = ByteView::try_from_reader(&mut reader, 32)?;
+        assert_eq!(b"helloworld_thisisaverylongstring", &*slice);
+
+        let err = ByteView::try_from_reader(&mut reader, 1).unwrap_err();
+        assert_eq!(std::io::ErrorKind::UnexpectedEof, err.kind());
+
+        #[cfg(target_pointer_width = "64")]
+        {
+            let err = ByteView::try_from_reader(&mut reader, u32::MAX as usize + 1).unwrap_err();
+            assert_eq!(std::io::ErrorKind::InvalidInput, err.kind());
         }
+
+        Ok(())
     }
 
-    fn get_long_slice(&self) -> &[u8] {
-        let len = self.len();
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn try_with_size_too_long() {
+        let len = u32::MAX as usize + 1;
+        assert_eq!(
+            Err(crate::Error::TooLong(len)),
+            ByteView::try_with_size(len).map(|_| ())
+        );
+    }
 
-        debug_assert!(
-            len > INLINE_SIZE,
-            "cannot get long slice - slice is inlined"
+    #[test]
+    fn heap_buffer() {
+        let slice = ByteView::with_size_and_headroom(32, 8);
+        let copy = slice.slice(4..28);
+
+        let parent = copy.heap_buffer().unwrap();
+        assert_eq!(40, parent.len());
+        assert_eq!(3, slice.ref_count());
+        assert_eq!(Some(0), parent.offset_in_parent());
+        assert_eq!(Some(8), slice.offset_in_parent());
+        assert_eq!(Some(12), copy.offset_in_parent());
+
+        let inline = copy.slice(0..4);
+        assert!(inline.heap_buffer().is_none());
+        assert!(inline.offset_in_parent().is_none());
+
+        let stat = ByteView::from_static(b"helloworld_thisisaverylongstring");
+        assert!(stat.heap_buffer().is_none());
+        assert!(stat.offset_in_parent().is_none());
+
+        let owned = ByteView::from(b"helloworld_thisisaverylongstring".to_vec()).slice(1..);
+        assert_eq!(
+            b"helloworld_thisisaverylongstring",
+            &*owned.heap_buffer().unwrap()
         );
+        assert_eq!(Some(1), owned.offset_in_parent());
+    }
 
-        // SAFETY: Shall only be called if slice is heap allocated
-        unsafe { std::slice::from_raw_parts(self.trailer.long.data, len) }
+    #[test]
+    fn compact() {
+        let slice = ByteView::from("helloworld_thisisaverylongstring");
+
+        let mut copy = slice.clone();
+        assert!(!copy.compact());
+        assert_eq!(2, slice.ref_count());
+
+        copy = slice.slice(1..);
+        assert!(copy.compact());
+        assert_eq!(b"elloworld_thisisaverylongstring", &*copy);
+        assert_eq!(1, slice.ref_count());
+        assert_eq!(1, copy.ref_count());
+
+        copy = slice.slice(..5);
+        assert!(!copy.compact());
+
+        copy = ByteView::from_static(b"helloworld_thisisaverylongstring").slice(1..);
+        assert!(!copy.compact());
     }
-}
 
-impl std::borrow::Borrow<[u8]> for ByteView {
-    fn borrow(&self) -> &[u8] {
-        self
+    #[test]
+    fn compact_with_ratio() {
+        let slice = ByteView::with_size(100);
+
+        let mut copy = slice.slice(..50);
+        assert!(!copy.compact_with_ratio(2));
+        assert_eq!(2, slice.ref_count());
+
+        copy = slice.slice(..49);
+        assert!(copy.compact_with_ratio(2));
+        assert_eq!(1, slice.ref_count());
     }
-}
 
-impl AsRef<[u8]> for ByteView {
-    fn as_ref(&self) -> &[u8] {
-        self
+    #[test]
+    fn truncate() {
+        let mut slice = ByteView::from("helloworld_thisisaverylongstring");
+        let copy = slice.clone();
+
+        slice.truncate(100);
+        assert_eq!(copy, slice);
+
+        slice.truncate(25);
+        assert!(!slice.is_inline());
+        assert_eq!(b"helloworld_thisisaverylon", &*slice);
+        assert_eq!(ByteView::from("helloworld_thisisaverylon"), slice);
+        assert_eq!(2, copy.ref_count());
+
+        slice.truncate(5);
+        assert!(slice.is_inline());
+        assert_eq!(b"hello", &*slice);
+        assert_eq!(1, copy.ref_count());
     }
-}
 
-impl FromIterator<u8> for ByteView {
-    fn from_iter<T>(iter: T) -> Self
-    where
-        T: IntoIterator<Item = u8>,
-    {
-        Self::from(iter.into_iter().collect::<Vec<u8>>())
+    #[test]
+    fn advance() {
+        let mut slice = ByteView::from("helloworld_thisisaverylongstring");
+        let copy = slice.clone();
+
+        slice.advance(0);
+        assert_eq!(copy, slice);
+
+        slice.advance(5);
+        assert!(!slice.is_inline());
+        assert_eq!(b"world_thisisaverylongstring", &*slice);
+        assert_eq!(ByteView::from("world_thisisaverylongstring"), slice);
+        assert_eq!(2, copy.ref_count());
+
+        slice.advance(13);
+        assert!(slice.is_inline());
+        assert_eq!(b"verylongstring", &*slice);
+        assert_eq!(1, copy.ref_count());
+
+        slice.advance(slice.len());
+        assert!(slice.is_empty());
     }
-}
 
-impl From<&[u8]> for ByteView {
-    fn from(value: &[u8]) -> Self {
-        Self::new(value)
+    #[test]
+    #[should_panic = "cannot advance past end"]
+    fn advance_out_of_bounds() {
+        let mut slice = ByteView::from("abc");
+        slice.advance(4);
     }
-}
 
-impl From<Arc<[u8]>> for ByteView {
-    fn from(value: Arc<[u8]>) -> Self {
-        Self::new(&value)
+    #[test]
+    fn split_off() {
+        let mut slice = ByteView::from("helloworld_thisisaverylongstring_andevenlonger1234");
+
+        let tail = slice.split_off(25);
+        assert_eq!(b"helloworld_thisisaverylon", &*slice);
+        assert_eq!(b"gstring_andevenlonger1234", &*tail);
+        assert_eq!(2, slice.ref_count());
+
+        let tail2 = slice.split_off(25);
+        assert!(tail2.is_empty());
+
+        let mut short = ByteView::from("abc");
+        let tail = short.split_off(1);
+        assert_eq!(b"a", &*short);
+        assert_eq!(b"bc", &*tail);
     }
-}
 
-impl From<Vec<u8>> for ByteView {
-    fn from(value: Vec<u8>) -> Self {
-        Self::new(&value)
+    #[test]
+    fn split_to() {
+        let mut slice = ByteView::from("helloworld_thisisaverylongstring_andevenlonger");
+
+        let head = slice.split_to(5);
+        assert!(head.is_inline());
+        assert_eq!(b"hello", &*head);
+        assert_eq!(b"world_thisisaverylongstring_andevenlonger", &*slice);
+
+        let head = slice.split_to(slice.len());
+        assert_eq!(b"world_thisisaverylongstring_andevenlonger", &*head);
+        assert!(slice.is_empty());
+        assert_eq!(1, head.ref_count());
     }
-}
 
-impl From<&str> for ByteView {
-    fn from(value: &str) -> Self {
-        Self::from(value.as_bytes())
+    #[test]
+    #[should_panic = "range end out of bounds"]
+    fn split_to_out_of_bounds() {
+        let mut slice = ByteView::from("abc");
+        let _ = slice.split_to(4);
     }
-}
 
-impl From<String> for ByteView {
-    fn from(value: String) -> Self {
-        Self::from(value.as_bytes())
+    #[test]
+    fn split_at() {
+        let slice = ByteView::from("helloworld_thisisaverylongstring_andevenlonger1234");
+
+        let (head, tail) = slice.split_at(25);
+        assert_eq!(b"helloworld_thisisaverylon", &*head);
+        assert_eq!(b"gstring_andevenlonger1234", &*tail);
+        assert_eq!(3, slice.ref_count());
+        assert_eq!(Some(25), tail.offset_in_parent());
+
+        let (head, tail) = slice.split_at(5);
+        assert!(head.is_inline());
+        assert_eq!(b"hello", &*head);
+        assert_eq!(4, slice.ref_count());
+        drop(tail);
+        assert_eq!(3, slice.ref_count());
+
+        let (head, tail) = slice.split_at(slice.len());
+        assert_eq!(slice, head);
+        assert!(tail.is_empty());
+
+        let short = ByteView::from("abc");
+        let (head, tail) = short.split_at(1);
+        assert_eq!(b"a", &*head);
+        assert_eq!(b"bc", &*tail);
+
+        assert!(short.split_at_checked(3).is_some());
+        assert!(short.split_at_checked(4).is_none());
     }
-}
 
-impl From<Arc<str>> for ByteView {
-    fn from(value: Arc<str>) -> Self {
-        Self::from(&*value)
+    #[test]
+    #[should_panic = "range end out of bounds"]
+    fn split_at_out_of_bounds() {
+        let slice = ByteView::from("abc");
+        let _ = slice.split_at(4);
     }
-}
 
-impl<const N: usize> From<[u8; N]> for ByteView {
-    fn from(value: [u8; N]) -> Self {
-        Self::from(value.as_slice())
+    #[test]
+    fn from_conversions() {
+        use std::{borrow::Cow, rc::Rc};
+
+        let s = "helloworld_thisisaverylongstring";
+        let expected = ByteView::from(s);
+
+        assert_eq!(expected, ByteView::from(&s.as_bytes().to_vec()));
+        assert_eq!(expected, ByteView::from(&s.to_owned()));
+        assert_eq!(expected, ByteView::from(Rc::<[u8]>::from(s.as_bytes())));
+        assert_eq!(expected, ByteView::from(Cow::Borrowed(s.as_bytes())));
+
+        // NOTE: Owned data is adopted without copying
+        let vec = s.as_bytes().to_vec();
+        let ptr = vec.as_ptr();
+        let slice = ByteView::from(Cow::<[u8]>::Owned(vec));
+        assert_eq!(expected, slice);
+        assert_eq!(ptr, slice.as_ptr());
     }
-}
 
-#[cfg(feature = "serde")]
-mod serde {
-    use super::ByteView;
-    use serde::de::{self, Visitor};
-    use serde::{Deserialize, Deserializer, Serialize, Serializer};
-    use std::fmt;
-    use std::ops::Deref;
+    #[test]
+    fn as_array() {
+        let slice = ByteView::from("helloworld_thisisaverylongstring");
+        assert_eq!(Some(b"helloworld_thisisaverylongstring"), slice.as_array());
+        assert_eq!(None, slice.as_array::<31>());
+        assert_eq!(None, slice.as_array::<33>());
+        assert_eq!(
+            b"helloworld_thisisaverylongstring",
+            &<[u8; 32]>::try_from(&slice).unwrap()
+        );
+        assert!(<[u8; 4]>::try_from(&slice).is_err());
 
-    impl Serialize for ByteView {
-        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-        where
-            S: Serializer,
-        {
-            serializer.serialize_bytes(self.deref())
+        let slice = ByteView::from([1, 2, 3, 4]);
+        assert_eq!(Some(&[1, 2, 3, 4]), slice.as_array());
+        assert_eq!(Some([1, 2, 3, 4]), <[u8; 4]>::try_from(&slice).ok());
+        assert_eq!(Some(&[]), ByteView::from("").as_array::<0>());
+    }
+
+    #[test]
+    fn filled() {
+        for len in [0, 1, 4, super::INLINE_SIZE, super::INLINE_SIZE + 1, 100] {
+            for byte in [0, 1, 0xFF] {
+                let slice = ByteView::filled(len, byte);
+                assert_eq!(vec![byte; len], &*slice);
+                assert_eq!(ByteView::from(vec![byte; len]), slice);
+                assert_eq!(len <= super::INLINE_SIZE, slice.is_inline());
+            }
         }
     }
 
-    impl<'de> Deserialize<'de> for ByteView {
-        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-        where
-            D: Deserializer<'de>,
-        {
-            struct ByteViewVisitor;
+    #[test]
+    fn from_fn() {
+        for len in [0, 1, 4, 23, 24, 100] {
+            #[allow(clippy::cast_possible_truncation)]
+            let expected = (0..len).map(|idx| (idx * 7) as u8).collect::<Vec<_>>();
+
+            #[allow(clippy::cast_possible_truncation)]
+            let slice = ByteView::from_fn(len, |idx| (idx * 7) as u8);
+            assert_eq!(expected, &*slice);
+            assert_eq!(ByteView::from(expected), slice);
+        }
 
-            impl<'de> Visitor<'de> for ByteViewVisitor {
-                type Value = ByteView;
+        let mut calls = vec![];
+        let _ = ByteView::from_fn(30, |idx| {
+            calls.push(idx);
+            0
+        });
+        assert_eq!((0..30).collect::<Vec<_>>(), calls);
+    }
 
-                fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-                    formatter.write_str("a byte array")
-                }
+    #[test]
+    #[should_panic = "oops"]
+    fn from_fn_panic() {
+        let _ = ByteView::from_fn(100, |idx| {
+            assert!(idx < 50, "oops");
+            0
+        });
+    }
 
-                fn visit_bytes<E>(self, v: &[u8]) -> Result<ByteView, E>
-                where
-                    E: de::Error,
-                {
-                    Ok(ByteView::new(v))
-                }
-            }
+    #[test]
+    fn new_inline() {
+        const KEYS: [ByteView; 3] = [
+            ByteView::EMPTY,
+            ByteView::new_inline(b"abc"),
+            ByteView::new_inline(&[7; super::INLINE_SIZE]),
+        ];
+
+        assert_eq!(ByteView::from(""), KEYS[0]);
+        assert_eq!(ByteView::from("abc"), KEYS[1]);
+        assert_eq!(ByteView::from(vec![7; super::INLINE_SIZE]), KEYS[2]);
+        assert!(KEYS.iter().all(ByteView::is_inline));
+        assert_eq!(ByteView::default(), ByteView::EMPTY);
+    }
 
-            deserializer.deserialize_bytes(ByteViewVisitor)
-        }
+    #[test]
+    #[should_panic = "slice too long to be inlined"]
+    fn new_inline_too_long() {
+        let _ = ByteView::new_inline(&[0; super::INLINE_SIZE + 1]);
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::{ByteView, HeapAllocationHeader};
-    use std::io::Cursor;
+    #[test]
+    fn repeat() {
+        for s in ["", "a", "abc", "helloworld_thisisaverylongstring"] {
+            let slice = ByteView::from(s);
+
+            for n in [0, 1, 2, 3, 7, 8, 100] {
+                let repeated = slice.repeat(n);
+                assert_eq!(s.repeat(n).as_bytes(), &*repeated);
+                assert_eq!(ByteView::from(s.repeat(n)), repeated);
+            }
+        }
+    }
 
     #[test]
-    #[cfg(target_pointer_width = "64")]
-    fn memsize() {
-        use crate::byteview::{LongRepr, ShortRepr, Trailer};
+    #[should_panic = "capacity overflow"]
+    fn repeat_overflow() {
+        let _ = ByteView::from("abc").repeat(usize::MAX);
+    }
 
+    #[test]
+    fn into_vec() {
+        let data = b"helloworld_thisisaverylongstring".to_vec();
+
+        // NOTE: A uniquely referenced vector is taken back
+        let vec = data.clone();
+        let ptr = vec.as_ptr();
+        let vec = ByteView::from(vec).into_vec();
+        assert_eq!(ptr, vec.as_ptr());
+        assert_eq!(data, vec);
+
+        // NOTE: Only the bytes of the slice are kept
+        let vec = data.clone();
+        let ptr = vec.as_ptr();
+        let mut slice = ByteView::from(vec);
+        slice.truncate(25);
+        let vec = slice.slice(1..).into_vec();
+        assert_ne!(ptr, vec.as_ptr());
+        let vec = slice.into_vec();
+        assert_eq!(ptr, vec.as_ptr());
+        assert_eq!(data.get(..25), Some(&*vec));
+
+        let mut slice = ByteView::from(data.clone());
+        slice.advance(2);
+        let vec = slice.into_vec();
+        assert_eq!(data.get(2..), Some(&*vec));
+
+        // NOTE: Shared or weakly referenced slices are copied
+        let vec = data.clone();
+        let ptr = vec.as_ptr();
+        let slice = ByteView::from(vec);
+        let copy = slice.clone();
+        let vec = slice.into_vec();
+        assert_ne!(ptr, vec.as_ptr());
+        let weak = copy.downgrade();
+        let vec = copy.clone().into_vec();
+        assert_ne!(ptr, vec.as_ptr());
+        assert_eq!(data, copy.to_vec());
+        drop(weak);
+        let vec = copy.into_vec();
+        assert_eq!(ptr, vec.as_ptr());
+
+        // NOTE: Other kinds of slices are always copied
+        assert_eq!(data, ByteView::from(&*data).into_vec());
+        assert_eq!(b"abc".to_vec(), ByteView::from("abc").into_vec());
+        assert_eq!(b"abc".to_vec(), Vec::from(ByteView::from(b"abc".to_vec())));
         assert_eq!(
-            std::mem::size_of::<ShortRepr>(),
-            std::mem::size_of::<LongRepr>()
-        );
-        assert_eq!(
-            std::mem::size_of::<Trailer>(),
-            std::mem::size_of::<LongRepr>()
+            data.clone().into_boxed_slice(),
+            Box::<[u8]>::from(ByteView::from(data))
         );
+    }
 
-        assert_eq!(24, std::mem::size_of::<ByteView>());
-        assert_eq!(
-            32,
-            std::mem::size_of::<ByteView>() + std::mem::size_of::<HeapAllocationHeader>()
-        );
+    #[test]
+    fn make_mut() {
+        let a = ByteView::from("helloworld_thisisaverylongstring");
+        let mut b = a.clone();
+        assert_eq!(2, a.ref_count());
+
+        b.make_mut()[0] = b'j';
+        assert_eq!(1, a.ref_count());
+        assert_eq!(1, b.ref_count());
+        assert_eq!(b"helloworld_thisisaverylongstring", &*a);
+        assert_eq!(b"jelloworld_thisisaverylongstring", &*b);
+        assert_eq!(ByteView::from("jelloworld_thisisaverylongstring"), b);
+
+        // NOTE: Unique, so no copy needed
+        let ptr = b.as_ptr();
+        b.make_mut()[1] = b'a';
+        assert_eq!(ptr, b.as_ptr());
+        assert_eq!(b"jallo", &b[..5]);
+
+        let mut c = ByteView::from_static(b"helloworld_thisisaverylongstring");
+        c.make_mut()[0] = b'j';
+        assert_eq!(b"jelloworld_thisisaverylongstring", &*c);
+        assert_eq!(1, c.ref_count());
+    }
+
+    #[test]
+    fn from_static() {
+        static DATA: &[u8] = b"helloworld_thisisaverylongstring";
+
+        let mut slice = ByteView::from_static(DATA);
+        assert!(!slice.is_inline());
+        assert_eq!(DATA, &*slice);
+        assert_eq!(DATA.as_ptr(), slice.as_ptr());
+        assert_eq!(1, slice.ref_count());
+        assert_eq!(0, slice.headroom());
+        assert!(slice.get_mut().is_none());
+
+        let copy = slice.clone();
+        assert_eq!(slice, copy);
+        assert_eq!(1, copy.ref_count());
+
+        let sub = slice.slice(1..);
+        assert_eq!(&DATA[1..], &*sub);
+        assert_eq!(DATA[1..].as_ptr(), sub.as_ptr());
+
+        let copies = slice.fan_out(3);
+        assert!(copies.iter().all(|copy| *copy == slice));
+    }
+
+    #[test]
+    fn from_static_inline() {
+        const SLICE: ByteView = ByteView::from_static(b"abc");
+
+        assert!(SLICE.is_inline());
+        assert_eq!(b"abc", &*SLICE);
+    }
+
+    #[test]
+    fn mutator_put() {
+        let mut slice = ByteView::with_size(30);
+
+        {
+            let mut mutator = slice.get_mut().unwrap();
+            mutator.put_u32_be(0, 0x0102_0304);
+            mutator.put_u16_le(4, 0x0506);
+            mutator.put_u8(6, 7);
+            mutator.put_u64_le(7, u64::MAX);
+            mutator.put_slice(15, b"abc");
+            mutator.put_f32_be(26, 1.0);
+        }
+
+        assert_eq!([1, 2, 3, 4], slice.prefix());
+        assert_eq!([6, 5, 7], slice[4..7]);
+        assert_eq!([255; 8], slice[7..15]);
+        assert_eq!(b"abc", &slice[15..18]);
+        assert_eq!(1.0_f32.to_be_bytes(), slice[26..30]);
+    }
+
+    #[test]
+    #[should_panic = "write out of bounds"]
+    fn mutator_put_out_of_bounds() {
+        let mut slice = ByteView::with_size(4);
+        slice.get_mut().unwrap().put_u32_le(1, 0);
+    }
+
+    #[test]
+    #[should_panic = "write out of bounds"]
+    fn mutator_put_overflow() {
+        let mut slice = ByteView::with_size(4);
+        slice.get_mut().unwrap().put_slice(usize::MAX, b"a");
+    }
+
+    #[test]
+    fn headroom_prepend() {
+        let mut slice = ByteView::with_size_and_headroom(30, 10);
+        assert_eq!(30, slice.len());
+        assert_eq!(10, slice.headroom());
+        let heap = unsafe { slice.trailer.long.heap };
+
+        slice.prepend(b"abcd");
+        assert_eq!(34, slice.len());
+        assert_eq!(6, slice.headroom());
+        assert_eq!(b"abcd", slice.prefix());
+        assert_eq!(heap, unsafe { slice.trailer.long.heap });
+
+        slice.prepend(b"012345");
+        assert_eq!(40, slice.len());
+        assert_eq!(0, slice.headroom());
+        assert_eq!(b"012345abcd", &slice[..10]);
+        assert!(slice[10..].iter().all(|&b| b == 0));
+        assert_eq!(heap, unsafe { slice.trailer.long.heap });
+
+        // NOTE: Out of headroom, so we need to reallocate
+        slice.prepend(b"x");
+        assert_eq!(41, slice.len());
+        assert_eq!(b"x012345abcd", &slice[..11]);
+        assert_ne!(heap, unsafe { slice.trailer.long.heap });
+    }
+
+    #[test]
+    fn headroom_prepend_shared() {
+        let mut slice = ByteView::with_size_and_headroom(30, 10);
+        let copy = slice.clone();
+
+        slice.prepend(b"abcd");
+        assert_eq!(34, slice.len());
+        assert_eq!(30, copy.len());
+        assert_eq!(1, slice.ref_count());
+        assert_eq!(1, copy.ref_count());
+    }
+
+    #[test]
+    fn headroom_prepend_inline() {
+        let mut slice = ByteView::with_size_and_headroom(4, 10);
+        assert_eq!(0, slice.headroom());
+
+        slice.prepend(b"abcd");
+        assert_eq!(b"abcd\0\0\0\0", &*slice);
+        assert!(slice.is_inline());
+
+        slice.prepend(b"helloworld_thisisaverylongstring");
+        assert_eq!(b"helloworld_thisisaverylongstringabcd\0\0\0\0", &*slice);
+        assert!(!slice.is_inline());
     }
 
     #[test]
@@ -0,0 +1,216 @@
+// Copyright (c) 2024-present, fjall-rs
+// This source code is licensed under both the Apache 2.0 and MIT License
+// (found in the LICENSE-* files in the repository)
+
+use crate::ByteView;
+use std::ops::{Deref, DerefMut};
+
+/// A growable, mutable byte buffer
+///
+/// Can be built incrementally, and then frozen into an immutable [`ByteView`]
+/// that takes over the buffer's heap allocation without copying.
+///
+/// # Examples
+///
+/// ```
+/// # use byteview::ByteViewMut;
+/// let mut buffer = ByteViewMut::with_capacity(64);
+/// buffer.extend_from_slice(b"helloworld_");
+/// buffer.put_u32_be(1);
+/// buffer.extend_from_slice(b"thisisaverylongstring");
+///
+/// let slice = buffer.freeze();
+/// assert_eq!(b"helloworld_\0\0\0\x01thisisaverylongstring", &*slice);
+/// ```
+#[derive(Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ByteViewMut(Vec<u8>);
+
+/// Implements the appending `put_*` methods for all primitive number types,
+/// on top of a `put_slice(&mut self, &[u8])` method
+///
+/// Any given attributes (e.g. doc comments) are added to every method.
+macro_rules! impl_put_appending {
+    ($(#[$attr:meta])*) => {
+        impl_put_appending!(@put [$(#[$attr])*] put_u8, u8, to_le_bytes, "");
+        impl_put_appending!(@put [$(#[$attr])*] put_i8, i8, to_le_bytes, "");
+        impl_put_appending!(@put [$(#[$attr])*] put_u16_le, u16, to_le_bytes, "little-endian ");
+        impl_put_appending!(@put [$(#[$attr])*] put_u16_be, u16, to_be_bytes, "big-endian ");
+        impl_put_appending!(@put [$(#[$attr])*] put_i16_le, i16, to_le_bytes, "little-endian ");
+        impl_put_appending!(@put [$(#[$attr])*] put_i16_be, i16, to_be_bytes, "big-endian ");
+        impl_put_appending!(@put [$(#[$attr])*] put_u32_le, u32, to_le_bytes, "little-endian ");
+        impl_put_appending!(@put [$(#[$attr])*] put_u32_be, u32, to_be_bytes, "big-endian ");
+        impl_put_appending!(@put [$(#[$attr])*] put_i32_le, i32, to_le_bytes, "little-endian ");
+        impl_put_appending!(@put [$(#[$attr])*] put_i32_be, i32, to_be_bytes, "big-endian ");
+        impl_put_appending!(@put [$(#[$attr])*] put_u64_le, u64, to_le_bytes, "little-endian ");
+        impl_put_appending!(@put [$(#[$attr])*] put_u64_be, u64, to_be_bytes, "big-endian ");
+        impl_put_appending!(@put [$(#[$attr])*] put_i64_le, i64, to_le_bytes, "little-endian ");
+        impl_put_appending!(@put [$(#[$attr])*] put_i64_be, i64, to_be_bytes, "big-endian ");
+        impl_put_appending!(@put [$(#[$attr])*] put_u128_le, u128, to_le_bytes, "little-endian ");
+        impl_put_appending!(@put [$(#[$attr])*] put_u128_be, u128, to_be_bytes, "big-endian ");
+        impl_put_appending!(@put [$(#[$attr])*] put_f32_le, f32, to_le_bytes, "little-endian ");
+        impl_put_appending!(@put [$(#[$attr])*] put_f32_be, f32, to_be_bytes, "big-endian ");
+        impl_put_appending!(@put [$(#[$attr])*] put_f64_le, f64, to_le_bytes, "little-endian ");
+        impl_put_appending!(@put [$(#[$attr])*] put_f64_be, f64, to_be_bytes, "big-endian ");
+    };
+    (@put [$(#[$attr:meta])*] $name:ident, $ty:ty, $conv:ident, $endian:literal) => {
+        #[doc = concat!("Appends a ", $endian, "`", stringify!($ty), "`.")]
+        $(#[$attr])*
+        pub fn $name(&mut self, value: $ty) {
+            self.put_slice(&value.$conv());
+        }
+    };
+}
+
+pub(crate) use impl_put_appending;
+
+impl ByteViewMut {
+    /// Creates a new, empty buffer.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    /// Creates a new, empty buffer that can hold at least `capacity` bytes without reallocating.
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self(Vec::with_capacity(capacity))
+    }
+
+    /// Returns the amount of bytes the buffer can hold without reallocating.
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.0.capacity()
+    }
+
+    /// Reserves capacity for at least `additional` more bytes.
+    pub fn reserve(&mut self, additional: usize) {
+        self.0.reserve(additional);
+    }
+
+    /// Shortens the buffer to `len` bytes.
+    ///
+    /// Has no effect if `len` is greater than the buffer's current length.
+    pub fn truncate(&mut self, len: usize) {
+        self.0.truncate(len);
+    }
+
+    /// Removes all bytes from the buffer, keeping its capacity.
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    /// Appends the given bytes to the buffer.
+    pub fn extend_from_slice(&mut self, bytes: &[u8]) {
+        self.0.extend_from_slice(bytes);
+    }
+
+    /// Appends the given bytes to the buffer.
+    pub fn put_slice(&mut self, bytes: &[u8]) {
+        self.extend_from_slice(bytes);
+    }
+
+    impl_put_appending!();
+
+    /// Converts the buffer into an immutable slice.
+    ///
+    /// The slice takes over the buffer's heap allocation, so no bytes are copied,
+    /// unless the slice is short enough to be inlined.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length does not fit in a u32 (4 GiB).
+    #[must_use]
+    pub fn freeze(self) -> ByteView {
+        ByteView::from_vec(self.0)
+    }
+}
+
+impl std::fmt::Debug for ByteViewMut {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", &**self)
+    }
+}
+
+impl Deref for ByteViewMut {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for ByteViewMut {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl Extend<u8> for ByteViewMut {
+    fn extend<T: IntoIterator<Item = u8>>(&mut self, iter: T) {
+        self.0.extend(iter);
+    }
+}
+
+impl std::io::Write for ByteViewMut {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl From<&[u8]> for ByteViewMut {
+    fn from(value: &[u8]) -> Self {
+        Self(value.to_vec())
+    }
+}
+
+impl From<Vec<u8>> for ByteViewMut {
+    fn from(value: Vec<u8>) -> Self {
+        Self(value)
+    }
+}
+
+impl From<ByteViewMut> for ByteView {
+    fn from(value: ByteViewMut) -> Self {
+        value.freeze()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ByteViewMut;
+
+    #[test]
+    fn freeze_no_copy() {
+        let mut buffer = ByteViewMut::new();
+        buffer.reserve(100);
+        assert!(buffer.capacity() >= 100);
+
+        buffer.extend_from_slice(b"helloworld_");
+        buffer.put_u8(b'!');
+        buffer.put_u16_le(0x0201);
+        buffer.extend_from_slice(b"thisisaverylongstring");
+        buffer[0] = b'j';
+
+        let ptr = buffer.as_ptr();
+
+        let mut slice = buffer.freeze();
+        assert_eq!(b"jelloworld_!\x01\x02thisisaverylongstring", &*slice);
+        assert_eq!(ptr, slice.as_ptr());
+        assert!(slice.get_mut().is_some());
+    }
+
+    #[test]
+    fn freeze_inline() {
+        let mut buffer = ByteViewMut::with_capacity(100);
+        buffer.put_u32_be(1);
+
+        let slice = buffer.freeze();
+        assert!(slice.is_inline());
+        assert_eq!([0, 0, 0, 1], *slice);
+    }
+}
@@ -0,0 +1,278 @@
+// Copyright (c) 2024-present, fjall-rs
+// This source code is licensed under both the Apache 2.0 and MIT License
+// (found in the LICENSE-* files in the repository)
+
+use crate::ByteView;
+use std::iter::FusedIterator;
+
+/// Iterator over fixed-size chunks of a [`ByteView`], see [`ByteView::chunks`]
+///
+/// The iterator holds its own reference to the slice,
+/// so it is not bound to the lifetime of the original [`ByteView`].
+#[derive(Clone, Debug)]
+pub struct Chunks {
+    view: ByteView,
+    chunk_size: usize,
+    start: usize,
+    end: usize,
+}
+
+impl Iterator for Chunks {
+    type Item = ByteView;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.start >= self.end {
+            return None;
+        }
+
+        let start = self.start;
+        self.start = self.end.min(start + self.chunk_size);
+        Some(self.view.slice(start..self.start))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.end - self.start).div_ceil(self.chunk_size);
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for Chunks {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.start >= self.end {
+            return None;
+        }
+
+        // NOTE: The last chunk may be shorter than the chunk size
+        let remainder = (self.end - self.start) % self.chunk_size;
+        let len = if remainder == 0 {
+            self.chunk_size
+        } else {
+            remainder
+        };
+
+        let end = self.end;
+        self.end -= len;
+        Some(self.view.slice(self.end..end))
+    }
+}
+
+impl ExactSizeIterator for Chunks {}
+
+impl FusedIterator for Chunks {}
+
+/// Iterator over overlapping windows of a [`ByteView`], see [`ByteView::windows`]
+///
+/// Like [`Chunks`], the iterator holds its own reference to the slice.
+#[derive(Clone, Debug)]
+pub struct Windows {
+    view: ByteView,
+    size: usize,
+    start: usize,
+    end: usize,
+}
+
+impl Iterator for Windows {
+    type Item = ByteView;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.start + self.size > self.end {
+            return None;
+        }
+
+        let start = self.start;
+        self.start += 1;
+        Some(self.view.slice(start..(start + self.size)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.end + 1).saturating_sub(self.start + self.size);
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for Windows {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.start + self.size > self.end {
+            return None;
+        }
+
+        let end = self.end;
+        self.end -= 1;
+        Some(self.view.slice((end - self.size)..end))
+    }
+}
+
+impl ExactSizeIterator for Windows {}
+
+impl FusedIterator for Windows {}
+
+impl ByteView {
+    /// Returns an iterator over chunks of `chunk_size` bytes, starting at the beginning of the slice.
+    ///
+    /// The last chunk is shorter if the length is not divisible by `chunk_size`.
+    ///
+    /// Like [`ByteView::slice`], the chunks reference the same heap allocation
+    /// (or are inlined, if short enough), without copying.
+    /// Because the chunks (and the iterator itself) are owned,
+    /// they are not bound to the lifetime of this slice
+    /// (and can be sent to other threads, unless the `local` feature is enabled).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use byteview::ByteView;
+    /// let blob = ByteView::from(vec![0; 1_000]);
+    /// let chunks = blob.chunks(256).collect::<Vec<_>>();
+    /// drop(blob);
+    ///
+    /// assert_eq!(4, chunks.len());
+    /// assert_eq!(232, chunks[3].len());
+    /// assert_eq!(4, chunks[0].ref_count());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is 0.
+    #[must_use]
+    pub fn chunks(&self, chunk_size: usize) -> Chunks {
+        assert!(chunk_size > 0, "chunk size must be non-zero");
+
+        Chunks {
+            view: self.clone(),
+            chunk_size,
+            start: 0,
+            end: self.len(),
+        }
+    }
+
+    /// Returns an iterator over all overlapping windows of `size` bytes.
+    ///
+    /// If the slice is shorter than `size`, the iterator yields nothing.
+    ///
+    /// Like [`ByteView::slice`], the windows reference the same heap allocation
+    /// (or are inlined, if short enough), without copying.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use byteview::ByteView;
+    /// let slice = ByteView::from("abcd");
+    /// let windows = slice.windows(3).collect::<Vec<_>>();
+    ///
+    /// assert_eq!(vec![ByteView::from("abc"), ByteView::from("bcd")], windows);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    #[must_use]
+    pub fn windows(&self, size: usize) -> Windows {
+        assert!(size > 0, "window size must be non-zero");
+
+        Windows {
+            view: self.clone(),
+            size,
+            start: 0,
+            end: self.len(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ByteView;
+
+    #[test]
+    fn chunks() {
+        let bytes = (0..100u8).collect::<Vec<_>>();
+        let slice = ByteView::from(&*bytes);
+
+        for chunk_size in [1, 7, 10, 33, 99, 100, 101, 1_000] {
+            let chunks = slice.chunks(chunk_size);
+            let expected = bytes.chunks(chunk_size).collect::<Vec<_>>();
+
+            assert_eq!(expected.len(), chunks.len());
+            assert_eq!(
+                expected,
+                chunks.clone().map(|c| c.to_vec()).collect::<Vec<_>>()
+            );
+            assert_eq!(
+                bytes.chunks(chunk_size).rev().collect::<Vec<_>>(),
+                chunks.rev().map(|c| c.to_vec()).collect::<Vec<_>>()
+            );
+        }
+
+        assert_eq!(0, ByteView::from("").chunks(4).count());
+    }
+
+    #[test]
+    fn chunks_shared() {
+        let slice = ByteView::from(vec![7; 1_000]);
+        let chunks = slice.chunks(100);
+        assert_eq!(2, slice.ref_count());
+
+        let chunks = chunks.collect::<Vec<_>>();
+        assert_eq!(11, slice.ref_count());
+
+        for (idx, chunk) in chunks.iter().enumerate() {
+            assert_eq!(Some(idx * 100), chunk.offset_in_parent());
+            assert!(slice.contains_view(chunk));
+        }
+
+        // NOTE: The iterator is not tied to the original view
+        let chunks = {
+            let slice = ByteView::from(vec![7; 1_000]);
+            slice.chunks(300)
+        };
+        assert_eq!(
+            vec![300, 300, 300, 100],
+            chunks.map(|c| c.len()).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn windows() {
+        let bytes = (0..100u8).collect::<Vec<_>>();
+        let slice = ByteView::from(&*bytes);
+
+        for size in [1, 2, 24, 50, 99, 100, 101] {
+            let windows = slice.windows(size);
+            let expected = bytes.windows(size).collect::<Vec<_>>();
+
+            assert_eq!(expected.len(), windows.len());
+            assert_eq!(
+                expected,
+                windows.clone().map(|w| w.to_vec()).collect::<Vec<_>>()
+            );
+            assert_eq!(
+                bytes.windows(size).rev().collect::<Vec<_>>(),
+                windows.rev().map(|w| w.to_vec()).collect::<Vec<_>>()
+            );
+        }
+
+        let windows = slice.windows(50).collect::<Vec<_>>();
+        assert_eq!(52, slice.ref_count());
+
+        for (idx, window) in windows.iter().enumerate() {
+            assert_eq!(Some(idx), window.offset_in_parent());
+        }
+
+        let mut windows = slice.windows(98);
+        assert_eq!(Some(ByteView::from(&bytes[2..])), windows.next_back());
+        assert_eq!(Some(ByteView::from(&bytes[..98])), windows.next());
+        assert_eq!(Some(ByteView::from(&bytes[1..99])), windows.next());
+        assert_eq!(None, windows.next_back());
+    }
+
+    #[test]
+    #[should_panic = "window size must be non-zero"]
+    fn windows_zero() {
+        let _ = ByteView::from("abc").windows(0);
+    }
+
+    #[test]
+    #[should_panic = "chunk size must be non-zero"]
+    fn chunks_zero() {
+        let _ = ByteView::from("abc").chunks(0);
+    }
+}
@@ -0,0 +1,38 @@
+// Copyright (c) 2024-present, fjall-rs
+// This source code is licensed under both the Apache 2.0 and MIT License
+// (found in the LICENSE-* files in the repository)
+
+/// Error returned by the fallible constructors of [`crate::ByteView`]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Error {
+    /// The requested length does not fit in a u32 (4 GiB)
+    TooLong(usize),
+
+    /// The allocator could not allocate the given layout
+    OutOfMemory(std::alloc::Layout),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::TooLong(len) => write!(f, "byte slice too long: {len} > {}", u32::MAX),
+            Self::OutOfMemory(layout) => {
+                write!(f, "memory allocation of {} bytes failed", layout.size())
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<Error> for std::io::Error {
+    fn from(value: Error) -> Self {
+        let kind = match value {
+            Error::TooLong(_) => std::io::ErrorKind::InvalidInput,
+            Error::OutOfMemory(_) => std::io::ErrorKind::OutOfMemory,
+        };
+
+        Self::new(kind, value)
+    }
+}
@@ -0,0 +1,68 @@
+// Copyright (c) 2024-present, fjall-rs
+// This source code is licensed under both the Apache 2.0 and MIT License
+// (found in the LICENSE-* files in the repository)
+
+use crate::ByteView;
+
+/// Displays the bytes of a [`ByteView`] as printable ASCII,
+/// escaping everything else, see [`ByteView::display_escaped`]
+#[derive(Clone, Copy)]
+pub struct DisplayEscaped<'a>(&'a [u8]);
+
+impl std::fmt::Display for DisplayEscaped<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0.escape_ascii())
+    }
+}
+
+impl std::fmt::Debug for DisplayEscaped<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "b\"{self}\"")
+    }
+}
+
+impl ByteView {
+    /// Returns an adapter that displays the slice as printable ASCII.
+    ///
+    /// Other bytes (and quotes and backslashes) are escaped
+    /// like in a Rust byte string literal, see `<[u8]>::escape_ascii`.
+    ///
+    /// The [`Debug`](std::fmt::Debug) output of [`ByteView`] uses the same escaping.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use byteview::ByteView;
+    /// let key = ByteView::from(&b"user#\x00\x01\xFF\"name\""[..]);
+    /// assert_eq!(r#"user#\x00\x01\xff\"name\""#, key.display_escaped().to_string());
+    /// assert_eq!(r#"b"user#\x00\x01\xff\"name\"""#, format!("{key:?}"));
+    /// ```
+    #[must_use]
+    pub fn display_escaped(&self) -> DisplayEscaped<'_> {
+        DisplayEscaped(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ByteView;
+
+    #[test]
+    fn display_escaped() {
+        let slice = ByteView::from("helloworld_thisisaverylongstring");
+        assert_eq!(
+            "helloworld_thisisaverylongstring",
+            slice.display_escaped().to_string()
+        );
+        assert_eq!(
+            "b\"helloworld_thisisaverylongstring\"",
+            format!("{slice:?}")
+        );
+
+        let slice = ByteView::from(&b"\ta\\b'c\n\x7F\x80"[..]);
+        assert_eq!(r"\ta\\b\'c\n\x7f\x80", slice.display_escaped().to_string());
+
+        assert_eq!("", ByteView::from("").display_escaped().to_string());
+        assert_eq!("b\"\"", format!("{:?}", ByteView::from("")));
+    }
+}
@@ -0,0 +1,200 @@
+// Copyright (c) 2024-present, fjall-rs
+// This source code is licensed under both the Apache 2.0 and MIT License
+// (found in the LICENSE-* files in the repository)
+
+//! C interface for creating, sharing and reading slices
+//!
+//! Slices are passed by value as a [`ByteViewHandle`], which has the same size and alignment
+//! as a [`ByteView`] (24 bytes, aligned to 8 bytes on 64-bit targets), so C code can declare it as:
+//!
+//! ```c
+//! typedef struct { _Alignas(8) unsigned char _private[24]; } byteview_t;
+//! ```
+//!
+//! A handle owns a reference to the slice's heap allocation (if any),
+//! so it must not be copied byte-wise: use [`byteview_clone`] to get another handle,
+//! and release every handle exactly once using [`byteview_free`].
+//!
+//! ```
+//! # use byteview::ffi::*;
+//! let bytes = b"helloworld_thisisaverylongstring";
+//!
+//! unsafe {
+//!     let mut view = byteview_empty();
+//!     assert!(byteview_new(bytes.as_ptr(), bytes.len(), &mut view));
+//!
+//!     let mut key = byteview_empty();
+//!     assert!(byteview_slice(&view, 11, 32, &mut key));
+//!     assert_eq!(21, byteview_len(&key));
+//!
+//!     byteview_free(view);
+//!     byteview_free(key);
+//! }
+//! ```
+
+use crate::ByteView;
+
+/// A slice that is passed across the C interface by value
+///
+/// See the [module-level documentation](self) for how to handle it.
+#[repr(C)]
+pub struct ByteViewHandle {
+    view: ByteView,
+}
+
+/// Returns a handle to an empty slice, which does not need to be released.
+#[no_mangle]
+pub extern "C" fn byteview_empty() -> ByteViewHandle {
+    ByteViewHandle {
+        view: ByteView::default(),
+    }
+}
+
+/// Writes a handle to a new slice into `out`, copying `len` bytes from `data`.
+///
+/// Returns `false` (and leaves `out` untouched) if the length does not fit in a u32 (4 GiB),
+/// or the allocation failed.
+///
+/// # Safety
+///
+/// `data` needs to be valid for reads of `len` bytes, or `len` needs to be 0.
+/// `out` needs to be valid for writes.
+/// If `out` contains a handle, it is overwritten without being released.
+#[no_mangle]
+pub unsafe extern "C" fn byteview_new(
+    data: *const u8,
+    len: usize,
+    out: *mut ByteViewHandle,
+) -> bool {
+    let bytes = if len == 0 {
+        &[]
+    } else {
+        std::slice::from_raw_parts(data, len)
+    };
+
+    let Ok(view) = ByteView::try_new(bytes) else {
+        return false;
+    };
+
+    out.write(ByteViewHandle { view });
+    true
+}
+
+/// Returns another handle to the same slice, without copying its bytes.
+///
+/// # Safety
+///
+/// `view` needs to point to a valid handle.
+#[no_mangle]
+pub unsafe extern "C" fn byteview_clone(view: *const ByteViewHandle) -> ByteViewHandle {
+    ByteViewHandle {
+        view: (*view).view.clone(),
+    }
+}
+
+/// Writes a handle to the range `[start, end)` of the slice into `out`, without copying its bytes.
+///
+/// Returns `false` (and leaves `out` untouched) if the range is out of bounds.
+///
+/// # Safety
+///
+/// `view` needs to point to a valid handle, and `out` needs to be valid for writes.
+/// If `out` contains a handle, it is overwritten without being released.
+#[no_mangle]
+pub unsafe extern "C" fn byteview_slice(
+    view: *const ByteViewHandle,
+    start: usize,
+    end: usize,
+    out: *mut ByteViewHandle,
+) -> bool {
+    let Some(slice) = (*view).view.try_slice(start..end) else {
+        return false;
+    };
+
+    out.write(ByteViewHandle { view: slice });
+    true
+}
+
+/// Returns a pointer to the slice's bytes.
+///
+/// The pointer is valid as long as the handle is not released or moved
+/// (short slices are stored inside the handle itself).
+///
+/// # Safety
+///
+/// `view` needs to point to a valid handle.
+#[no_mangle]
+pub unsafe extern "C" fn byteview_data(view: *const ByteViewHandle) -> *const u8 {
+    (*view).view.as_ptr()
+}
+
+/// Returns the amount of bytes in the slice.
+///
+/// # Safety
+///
+/// `view` needs to point to a valid handle.
+#[no_mangle]
+pub unsafe extern "C" fn byteview_len(view: *const ByteViewHandle) -> usize {
+    (*view).view.len()
+}
+
+/// Releases the handle, freeing the heap allocation if it was the last reference.
+///
+/// # Safety
+///
+/// The handle (or any byte-wise copy of it) must not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn byteview_free(view: ByteViewHandle) {
+    drop(view);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        byteview_clone, byteview_data, byteview_empty, byteview_free, byteview_len, byteview_new,
+        byteview_slice, ByteViewHandle,
+    };
+    use crate::ByteView;
+
+    #[test]
+    fn ffi_layout() {
+        assert_eq!(
+            std::mem::size_of::<ByteView>(),
+            std::mem::size_of::<ByteViewHandle>(),
+        );
+        assert_eq!(
+            std::mem::align_of::<ByteView>(),
+            std::mem::align_of::<ByteViewHandle>(),
+        );
+    }
+
+    #[test]
+    fn ffi_roundtrip() {
+        let bytes = b"helloworld_thisisaverylongstring";
+
+        unsafe {
+            let mut view = byteview_empty();
+            assert!(byteview_new(bytes.as_ptr(), bytes.len(), &mut view));
+            let copy = byteview_clone(&view);
+            assert_eq!(2, view.view.ref_count());
+
+            let mut slice = byteview_empty();
+            assert!(byteview_new(std::ptr::null(), 0, &mut slice));
+            assert_eq!(0, byteview_len(&slice));
+
+            assert!(byteview_slice(&copy, 5, 30, &mut slice));
+            assert!(!byteview_slice(&copy, 5, 33, &mut slice));
+            assert!(!byteview_slice(&copy, 30, 5, &mut slice));
+            assert_eq!(3, view.view.ref_count());
+
+            let data = std::slice::from_raw_parts(byteview_data(&slice), byteview_len(&slice));
+            assert_eq!(&bytes[5..30], data);
+            assert_eq!(byteview_data(&view).wrapping_add(5), byteview_data(&slice));
+
+            byteview_free(copy);
+            byteview_free(slice);
+            assert_eq!(1, view.view.ref_count());
+            byteview_free(view);
+        }
+    }
+}
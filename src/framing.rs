@@ -0,0 +1,404 @@
+// Copyright (c) 2024-present, fjall-rs
+// This source code is licensed under both the Apache 2.0 and MIT License
+// (found in the LICENSE-* files in the repository)
+
+//! Length-prefixed framing of byte slices
+//!
+//! Each frame is encoded as a little-endian `u32` length, followed by the frame's bytes.
+//!
+//! ```
+//! # use byteview::framing::{FrameReader, FrameWriter};
+//! let mut writer = FrameWriter::new(vec![]);
+//! writer.write_frame(b"hello")?;
+//! writer.write_frame(b"helloworld_thisisaverylongstring")?;
+//!
+//! let buf = writer.into_inner();
+//!
+//! let mut reader = FrameReader::new(&*buf);
+//! assert_eq!(b"hello", &*reader.read_frame()?.unwrap());
+//! assert_eq!(b"helloworld_thisisaverylongstring", &*reader.read_frame()?.unwrap());
+//! assert!(reader.read_frame()?.is_none());
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use crate::ByteView;
+use std::io::{Read, Write};
+
+/// Size of the length prefix of every frame
+const LEN_SIZE: usize = std::mem::size_of::<u32>();
+
+/// Writes length-prefixed frames into a writer
+pub struct FrameWriter<W: Write> {
+    inner: W,
+}
+
+impl<W: Write> FrameWriter<W> {
+    /// Creates a new frame writer.
+    pub const fn new(inner: W) -> Self {
+        Self { inner }
+    }
+
+    /// Writes a single frame.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an I/O error occurred, or the frame does not fit in a u32 (4 GiB).
+    pub fn write_frame(&mut self, bytes: &[u8]) -> std::io::Result<()> {
+        let Ok(len) = u32::try_from(bytes.len()) else {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "frame too long",
+            ));
+        };
+
+        self.inner.write_all(&len.to_le_bytes())?;
+        self.inner.write_all(bytes)
+    }
+
+    /// Flushes the underlying writer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an I/O error occurred.
+    pub fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub const fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+/// Reads length-prefixed frames from a reader
+///
+/// Every frame is read into a single allocation (or inlined, if short enough).
+///
+/// Because the length prefix is read from the input, a reader over untrusted input
+/// should limit the frame length using [`FrameReader::with_max_frame_len`].
+pub struct FrameReader<R: Read> {
+    inner: R,
+    max_frame_len: usize,
+}
+
+impl<R: Read> FrameReader<R> {
+    /// Creates a new frame reader.
+    ///
+    /// By default, frames may be up to 4 GiB long.
+    pub const fn new(inner: R) -> Self {
+        Self {
+            inner,
+            max_frame_len: u32::MAX as usize,
+        }
+    }
+
+    /// Sets the maximum length of a frame.
+    ///
+    /// Reading a frame that is longer fails with an error of kind
+    /// [`std::io::ErrorKind::InvalidData`], before anything is allocated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use byteview::framing::{FrameReader, FrameWriter};
+    /// let mut writer = FrameWriter::new(vec![]);
+    /// writer.write_frame(b"helloworld_thisisaverylongstring")?;
+    ///
+    /// let buf = writer.into_inner();
+    ///
+    /// let mut reader = FrameReader::new(&*buf).with_max_frame_len(16);
+    /// assert_eq!(
+    ///     std::io::ErrorKind::InvalidData,
+    ///     reader.read_frame().unwrap_err().kind(),
+    /// );
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    #[must_use]
+    pub const fn with_max_frame_len(mut self, max_frame_len: usize) -> Self {
+        self.max_frame_len = max_frame_len;
+        self
+    }
+
+    /// Reads the next frame.
+    ///
+    /// Returns `None` if the reader ended at a frame boundary.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an I/O error occurred, the reader ended inside a frame,
+    /// or the frame is longer than the maximum frame length.
+    ///
+    /// If the frame cannot be allocated, an error of kind [`std::io::ErrorKind::OutOfMemory`] is returned.
+    pub fn read_frame(&mut self) -> std::io::Result<Option<ByteView>> {
+        let Some(len) = self.read_len()? else {
+            return Ok(None);
+        };
+
+        ByteView::try_from_reader(&mut self.inner, len).map(Some)
+    }
+
+    /// Reads up to `count` frames into a single heap allocation, which the returned slices share.
+    ///
+    /// Returns less than `count` frames if the reader ended at a frame boundary.
+    ///
+    /// Every frame is checked against the maximum frame length, like in [`FrameReader::read_frame`].
+    ///
+    /// If an error occurs, the frames that were read before the failing frame are discarded,
+    /// so reading should not be continued after an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use byteview::framing::{FrameReader, FrameWriter};
+    /// let mut writer = FrameWriter::new(vec![]);
+    /// writer.write_frame(b"helloworld_thisisaverylongstring")?;
+    /// writer.write_frame(b"helloworld_thisisanotherlongstring")?;
+    ///
+    /// let buf = writer.into_inner();
+    ///
+    /// let frames = FrameReader::new(&*buf).read_frames(100)?;
+    /// assert_eq!(2, frames.len());
+    /// assert_eq!(b"helloworld_thisisanotherlongstring", &*frames[1]);
+    /// assert_eq!(2, frames[0].ref_count());
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an I/O error occurred, the reader ended inside a frame,
+    /// a frame is longer than the maximum frame length,
+    /// or the frames do not fit in a u32 (4 GiB) together.
+    ///
+    /// If the frames cannot be allocated, an error of kind [`std::io::ErrorKind::OutOfMemory`] is returned.
+    pub fn read_frames(&mut self, count: usize) -> std::io::Result<Vec<ByteView>> {
+        let mut buf = Vec::new();
+        let mut ranges = Vec::new();
+
+        while ranges.len() < count {
+            let Some(len) = self.read_len()? else {
+                break;
+            };
+
+            let start = buf.len();
+
+            let Some(end) = start
+                .checked_add(len)
+                .filter(|&end| u32::try_from(end).is_ok())
+            else {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    "frames too long",
+                ));
+            };
+
+            buf.try_reserve(len)
+                .map_err(|_| std::io::Error::from(std::io::ErrorKind::OutOfMemory))?;
+
+            // NOTE: Reads straight into the spare capacity, without zeroing it first
+            if (&mut self.inner).take(len as u64).read_to_end(&mut buf)? < len {
+                return Err(std::io::ErrorKind::UnexpectedEof.into());
+            }
+
+            ranges.push(start..end);
+        }
+
+        // NOTE: The buffer grows by doubling, so give back the spare capacity
+        // instead of keeping it alive as part of the adopted allocation
+        buf.shrink_to_fit();
+
+        let buffer = ByteView::from_vec(buf);
+
+        Ok(ranges
+            .into_iter()
+            .map(|range| buffer.slice(range))
+            .collect())
+    }
+
+    /// Reads the length prefix of the next frame, and checks it against the maximum frame length.
+    ///
+    /// Returns `None` if the reader ended at a frame boundary.
+    fn read_len(&mut self) -> std::io::Result<Option<usize>> {
+        let mut len_buf = [0; LEN_SIZE];
+        let mut filled = 0;
+
+        while filled < LEN_SIZE {
+            // SAFETY: filled < LEN_SIZE
+            #[allow(clippy::indexing_slicing)]
+            match self.inner.read(&mut len_buf[filled..]) {
+                Ok(0) if filled == 0 => return Ok(None),
+                Ok(0) => return Err(std::io::ErrorKind::UnexpectedEof.into()),
+                Ok(n) => filled += n,
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        let len = u32::from_le_bytes(len_buf) as usize;
+
+        if len > self.max_frame_len {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "frame too long",
+            ));
+        }
+
+        Ok(Some(len))
+    }
+
+    /// Returns a reference to the underlying reader.
+    pub const fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read> Iterator for FrameReader<R> {
+    type Item = std::io::Result<ByteView>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_frame().transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FrameReader, FrameWriter};
+    use crate::ByteView;
+
+    #[test]
+    fn framing_roundtrip() -> std::io::Result<()> {
+        let frames: &[&[u8]] = &[
+            b"",
+            b"a",
+            b"abcdef",
+            b"helloworld_thisisaverylongstring",
+            &[0; 1_000],
+        ];
+
+        let mut writer = FrameWriter::new(vec![]);
+        for frame in frames {
+            writer.write_frame(frame)?;
+        }
+        writer.flush()?;
+
+        let buf = writer.into_inner();
+
+        let reader = FrameReader::new(&*buf);
+        let read = reader.collect::<std::io::Result<Vec<_>>>()?;
+
+        assert_eq!(frames.len(), read.len());
+        for (frame, view) in frames.iter().zip(&read) {
+            assert_eq!(*frame, &**view);
+            assert_eq!(1, view.ref_count());
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn framing_read_frames() -> std::io::Result<()> {
+        let mut writer = FrameWriter::new(vec![]);
+        for i in 0..10 {
+            writer.write_frame(format!("helloworld_thisisaverylongstring_{i}").as_bytes())?;
+        }
+        writer.write_frame(b"abc")?;
+
+        let buf = writer.into_inner();
+        let mut reader = FrameReader::new(&*buf);
+
+        let frames = reader.read_frames(4)?;
+        assert_eq!(4, frames.len());
+        assert_eq!(b"helloworld_thisisaverylongstring_3", &*frames[3]);
+        assert_eq!(4, frames[0].ref_count());
+
+        let frames = reader.read_frames(100)?;
+        assert_eq!(7, frames.len());
+        assert_eq!(b"abc", &*frames[6]);
+        assert_eq!(6, frames[0].ref_count());
+
+        assert!(reader.read_frames(100)?.is_empty());
+
+        let mut reader = FrameReader::new(&*buf);
+        let frames = reader.read_frames(100)?;
+
+        // NOTE: The shared allocation is sized exactly
+        let len = frames.iter().map(ByteView::len).sum::<usize>();
+        assert_eq!(
+            ByteView::from(vec![0; len]).heap_size(),
+            frames[0].heap_size(),
+        );
+
+        let mut reader = FrameReader::new(&buf[..buf.len() - 1]);
+        assert_eq!(
+            std::io::ErrorKind::UnexpectedEof,
+            reader.read_frames(100).unwrap_err().kind(),
+        );
+
+        let mut reader = FrameReader::new(&*buf).with_max_frame_len(33);
+        assert_eq!(
+            std::io::ErrorKind::InvalidData,
+            reader.read_frames(100).unwrap_err().kind(),
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn framing_max_frame_len() -> std::io::Result<()> {
+        let mut writer = FrameWriter::new(vec![]);
+        writer.write_frame(&[0; 100])?;
+        writer.write_frame(&[0; 101])?;
+
+        let buf = writer.into_inner();
+        let mut reader = FrameReader::new(&*buf).with_max_frame_len(100);
+
+        assert_eq!(100, reader.read_frame()?.unwrap().len());
+        assert_eq!(
+            std::io::ErrorKind::InvalidData,
+            reader.read_frame().unwrap_err().kind(),
+        );
+
+        // NOTE: A bogus length prefix must not allocate 4 GiB
+        let mut reader = FrameReader::new(&[255, 255, 255, 255][..]).with_max_frame_len(1_024);
+        assert_eq!(
+            std::io::ErrorKind::InvalidData,
+            reader.read_frame().unwrap_err().kind(),
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn framing_empty() -> std::io::Result<()> {
+        let mut reader = FrameReader::new(&[][..]);
+        assert!(reader.read_frame()?.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn framing_truncated_len() {
+        let mut reader = FrameReader::new(&[5, 0][..]);
+        assert_eq!(
+            std::io::ErrorKind::UnexpectedEof,
+            reader.read_frame().unwrap_err().kind(),
+        );
+    }
+
+    #[test]
+    fn framing_truncated_frame() {
+        let mut reader = FrameReader::new(&[5, 0, 0, 0, b'a', b'b'][..]);
+        assert_eq!(
+            std::io::ErrorKind::UnexpectedEof,
+            reader.read_frame().unwrap_err().kind(),
+        );
+    }
+}
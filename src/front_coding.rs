@@ -0,0 +1,350 @@
+// Copyright (c) 2024-present, fjall-rs
+// This source code is licensed under both the Apache 2.0 and MIT License
+// (found in the LICENSE-* files in the repository)
+
+//! Front coding of sorted byte slices
+//!
+//! Every key is encoded as the length of the prefix it shares with the previous key,
+//! followed by the remaining suffix, so sorted keys with long common prefixes
+//! (e.g. the keys of an index block) take up much less space.
+//!
+//! Every `restart_interval` keys, a key is stored in full (a restart point),
+//! so a corrupted block cannot affect more than a single restart interval.
+//!
+//! The block is encoded as:
+//!
+//! - every entry, as a varint shared prefix length, a varint suffix length, and the suffix
+//! - the offset of every restart point, as little-endian `u32`
+//! - the amount of restart points, as little-endian `u32`
+//!
+//! ```
+//! # use byteview::front_coding::{decode, FrontEncoder};
+//! let mut encoder = FrontEncoder::new(16);
+//! encoder.push(b"user:1000:email");
+//! encoder.push(b"user:1000:name");
+//! encoder.push(b"user:1001:email");
+//!
+//! let block = encoder.finish();
+//!
+//! let keys = decode(&block)?;
+//! assert_eq!(3, keys.len());
+//! assert_eq!(b"user:1000:name", &*keys[1]);
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use crate::{varint, ByteView};
+use std::io::{Error, ErrorKind};
+
+/// Size of the restart offsets and count
+const U32_SIZE: usize = std::mem::size_of::<u32>();
+
+fn write_varint(buf: &mut Vec<u8>, value: u32) {
+    let mut scratch = [0; varint::MAX_LEN];
+    buf.extend_from_slice(varint::encode(value.into(), &mut scratch));
+}
+
+fn read_varint(buf: &[u8], pos: &mut usize) -> Option<u32> {
+    let (value, len) = varint::decode_u32(buf.get(*pos..)?).ok()?;
+    *pos += len;
+    Some(value)
+}
+
+fn read_u32(buf: &[u8], pos: usize) -> Option<u32> {
+    let bytes = buf.get(pos..(pos + U32_SIZE))?;
+    bytes.try_into().ok().map(u32::from_le_bytes)
+}
+
+fn invalid_data() -> Error {
+    Error::new(ErrorKind::InvalidData, "malformed front-coded block")
+}
+
+/// Encodes a sorted run of byte slices using front coding
+///
+/// See the [module-level documentation](self) for the block format.
+#[derive(Clone, Debug)]
+pub struct FrontEncoder {
+    buffer: Vec<u8>,
+    restarts: Vec<u32>,
+    restart_interval: usize,
+    last: Vec<u8>,
+    count: usize,
+}
+
+impl FrontEncoder {
+    /// Creates a new encoder, that stores every `restart_interval`-th key in full.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `restart_interval` is 0.
+    #[must_use]
+    pub fn new(restart_interval: usize) -> Self {
+        assert!(restart_interval > 0, "restart interval must not be 0");
+
+        Self {
+            buffer: Vec::new(),
+            restarts: Vec::new(),
+            restart_interval,
+            last: Vec::new(),
+            count: 0,
+        }
+    }
+
+    /// Appends a key, which needs to be greater than or equal to the previous key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is smaller than the previous key,
+    /// or the block grows beyond 4 GiB.
+    pub fn push(&mut self, key: &[u8]) {
+        assert!(
+            self.count == 0 || *self.last <= *key,
+            "keys must be pushed in sorted order",
+        );
+
+        let shared = if self.count % self.restart_interval == 0 {
+            let Ok(offset) = u32::try_from(self.buffer.len()) else {
+                panic!("front-coded block too long");
+            };
+            self.restarts.push(offset);
+
+            0
+        } else {
+            self.last
+                .iter()
+                .zip(key)
+                .take_while(|(a, b)| a == b)
+                .count()
+        };
+
+        let suffix = key.get(shared..).unwrap_or_default();
+
+        let (Ok(shared_len), Ok(suffix_len)) = (u32::try_from(shared), u32::try_from(suffix.len()))
+        else {
+            panic!("key too long");
+        };
+
+        write_varint(&mut self.buffer, shared_len);
+        write_varint(&mut self.buffer, suffix_len);
+        self.buffer.extend_from_slice(suffix);
+
+        self.last.truncate(shared);
+        self.last.extend_from_slice(suffix);
+        self.count += 1;
+    }
+
+    /// Returns the amount of keys.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.count
+    }
+
+    /// Returns `true` if no keys were pushed.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Finishes the block.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the block grows beyond 4 GiB.
+    #[must_use]
+    pub fn finish(mut self) -> ByteView {
+        for offset in &self.restarts {
+            self.buffer.extend_from_slice(&offset.to_le_bytes());
+        }
+
+        let Ok(restart_count) = u32::try_from(self.restarts.len()) else {
+            panic!("front-coded block too long");
+        };
+        self.buffer.extend_from_slice(&restart_count.to_le_bytes());
+
+        ByteView::from_vec(self.buffer)
+    }
+}
+
+/// Decodes a front-coded block into its keys.
+///
+/// All keys are decoded into a single heap allocation, which the returned slices reference.
+///
+/// # Errors
+///
+/// Returns an error of kind [`ErrorKind::InvalidData`] if the block is malformed.
+pub fn decode(block: &[u8]) -> std::io::Result<Vec<ByteView>> {
+    let restart_count = block
+        .len()
+        .checked_sub(U32_SIZE)
+        .and_then(|pos| read_u32(block, pos))
+        .ok_or_else(invalid_data)? as usize;
+
+    let entries_len = restart_count
+        .checked_add(1)
+        .and_then(|n| n.checked_mul(U32_SIZE))
+        .and_then(|trailer_len| block.len().checked_sub(trailer_len))
+        .ok_or_else(invalid_data)?;
+
+    let (entries, restarts) = block.split_at(entries_len);
+
+    // NOTE: First, parse all entries, so we know how large the decoded keys are
+    let mut parsed = Vec::new();
+    let mut pos = 0;
+    let mut restart_idx = 0;
+    let mut last_len = 0;
+    let mut total_len = 0usize;
+
+    while pos < entries.len() {
+        let is_restart = restart_idx < restart_count
+            && read_u32(restarts, restart_idx * U32_SIZE)
+                .is_some_and(|offset| offset as usize == pos);
+
+        let shared = read_varint(entries, &mut pos).ok_or_else(invalid_data)? as usize;
+        let suffix_len = read_varint(entries, &mut pos).ok_or_else(invalid_data)? as usize;
+
+        // NOTE: The first key needs to be a restart point, and restart points store the full key
+        if (parsed.is_empty() && !is_restart) || (is_restart && shared != 0) || shared > last_len {
+            return Err(invalid_data());
+        }
+
+        if is_restart {
+            restart_idx += 1;
+        }
+
+        let suffix_end = pos.checked_add(suffix_len).ok_or_else(invalid_data)?;
+        let suffix = entries.get(pos..suffix_end).ok_or_else(invalid_data)?;
+        pos = suffix_end;
+
+        last_len = shared + suffix_len;
+        total_len = total_len.checked_add(last_len).ok_or_else(invalid_data)?;
+        parsed.push((shared, suffix));
+    }
+
+    if restart_idx != restart_count || total_len > u32::MAX as usize {
+        return Err(invalid_data());
+    }
+
+    // NOTE: We can use _unchecked to skip zeroing of the heap allocated slice
+    // because the keys fill it completely
+    let mut buffer = ByteView::with_size_unchecked(total_len);
+    let mut ranges = Vec::with_capacity(parsed.len());
+
+    {
+        let data = buffer.get_mut_slice();
+        let mut start = 0;
+        let mut last_start = 0;
+
+        for (shared, suffix) in parsed {
+            // NOTE: The shared prefix is copied from the previous key, which is already decoded
+            data.copy_within(last_start..(last_start + shared), start);
+
+            let suffix_start = start + shared;
+            let end = suffix_start + suffix.len();
+            data.get_mut(suffix_start..end)
+                .ok_or_else(invalid_data)?
+                .copy_from_slice(suffix);
+
+            ranges.push(start..end);
+            last_start = start;
+            start = end;
+        }
+    }
+
+    buffer.update_prefix();
+
+    Ok(ranges
+        .into_iter()
+        .map(|range| buffer.slice(range))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode, FrontEncoder};
+    use std::io::ErrorKind;
+
+    #[test]
+    fn front_coding_roundtrip() -> std::io::Result<()> {
+        let keys = (0..100)
+            .map(|i| format!("helloworld_thisisaverylongstring_{i:04}"))
+            .collect::<Vec<_>>();
+
+        for restart_interval in [1, 2, 16, 1_000] {
+            let mut encoder = FrontEncoder::new(restart_interval);
+            assert!(encoder.is_empty());
+
+            encoder.push(b"");
+            encoder.push(b"abc");
+            for key in &keys {
+                encoder.push(key.as_bytes());
+                encoder.push(key.as_bytes());
+            }
+            assert_eq!(202, encoder.len());
+
+            let block = encoder.finish();
+
+            let decoded = decode(&block)?;
+            assert_eq!(202, decoded.len());
+            assert_eq!(b"", &*decoded[0]);
+            assert_eq!(b"abc", &*decoded[1]);
+
+            for (i, key) in keys.iter().enumerate() {
+                assert_eq!(key.as_bytes(), &*decoded[2 + i * 2]);
+                assert_eq!(key.as_bytes(), &*decoded[3 + i * 2]);
+            }
+
+            // NOTE: All long keys share the same heap allocation
+            assert_eq!(
+                decoded[2].as_ptr().wrapping_add(decoded[2].len()),
+                decoded[3].as_ptr(),
+            );
+        }
+
+        // NOTE: Keys are much shorter than their full size
+        let mut encoder = FrontEncoder::new(16);
+        for key in &keys {
+            encoder.push(key.as_bytes());
+        }
+        assert!(encoder.finish().len() < keys.len() * 10);
+
+        Ok(())
+    }
+
+    #[test]
+    fn front_coding_empty() -> std::io::Result<()> {
+        let block = FrontEncoder::new(16).finish();
+        assert_eq!(4, block.len());
+        assert!(decode(&block)?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn front_coding_malformed() {
+        let mut encoder = FrontEncoder::new(2);
+        encoder.push(b"helloworld_thisisaverylongstring_1");
+        encoder.push(b"helloworld_thisisaverylongstring_2");
+        encoder.push(b"helloworld_thisisaverylongstring_3");
+        let block = encoder.finish();
+
+        for len in 0..block.len() {
+            assert_eq!(
+                ErrorKind::InvalidData,
+                decode(&block[..len]).unwrap_err().kind(),
+            );
+        }
+
+        let mut corrupted = block.to_vec();
+        corrupted[36] = 40;
+        assert_eq!(
+            ErrorKind::InvalidData,
+            decode(&corrupted).unwrap_err().kind(),
+        );
+    }
+
+    #[test]
+    #[should_panic = "keys must be pushed in sorted order"]
+    fn front_coding_unsorted() {
+        let mut encoder = FrontEncoder::new(16);
+        encoder.push(b"b");
+        encoder.push(b"a");
+    }
+}
@@ -0,0 +1,112 @@
+// Copyright (c) 2024-present, fjall-rs
+// This source code is licensed under both the Apache 2.0 and MIT License
+// (found in the LICENSE-* files in the repository)
+
+use std::sync::OnceLock;
+
+static HOOKS: OnceLock<MemoryHooks> = OnceLock::new();
+
+/// Callbacks that are invoked whenever a heap allocation backing slices is created or freed
+///
+/// The callbacks receive the size of the allocation in bytes,
+/// which is the same value [`crate::ByteView::heap_size`] returns.
+/// Allocations are reported as freed once no strong or weak references are left.
+///
+/// Inlined slices and slices referencing static data never allocate, so they are not reported.
+///
+/// The callbacks may be called from any thread, and should be cheap (e.g. update an atomic counter).
+///
+/// # Examples
+///
+/// ```
+/// # use byteview::{set_memory_hooks, ByteView, MemoryHooks};
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+///
+/// static MEMORY_USAGE: AtomicUsize = AtomicUsize::new(0);
+///
+/// set_memory_hooks(MemoryHooks {
+///     on_alloc: |size| {
+///         MEMORY_USAGE.fetch_add(size, Ordering::Relaxed);
+///     },
+///     on_dealloc: |size| {
+///         MEMORY_USAGE.fetch_sub(size, Ordering::Relaxed);
+///     },
+/// })
+/// .unwrap();
+///
+/// let slice = ByteView::from("helloworld_thisisaverylongstring");
+/// assert!(MEMORY_USAGE.load(Ordering::Relaxed) > 0);
+///
+/// drop(slice);
+/// assert_eq!(0, MEMORY_USAGE.load(Ordering::Relaxed));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct MemoryHooks {
+    /// Called after a heap allocation was created
+    pub on_alloc: fn(usize),
+
+    /// Called before a heap allocation is freed
+    pub on_dealloc: fn(usize),
+}
+
+/// Installs the global memory accounting hooks.
+///
+/// # Errors
+///
+/// Hooks can only be installed once, so the given hooks are returned
+/// if some were already installed.
+pub fn set_memory_hooks(hooks: MemoryHooks) -> Result<(), MemoryHooks> {
+    HOOKS.set(hooks)
+}
+
+pub fn on_alloc(size: usize) {
+    if let Some(hooks) = HOOKS.get() {
+        (hooks.on_alloc)(size);
+    }
+}
+
+pub fn on_dealloc(size: usize) {
+    if let Some(hooks) = HOOKS.get() {
+        (hooks.on_dealloc)(size);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{set_memory_hooks, MemoryHooks};
+    use crate::ByteView;
+    use std::cell::Cell;
+
+    thread_local! {
+        // NOTE: Tests run in parallel, so only count allocations of the current thread
+        static MEMORY_USAGE: Cell<isize> = const { Cell::new(0) };
+    }
+
+    #[test]
+    #[allow(clippy::cast_possible_wrap)]
+    fn memory_hooks() {
+        set_memory_hooks(MemoryHooks {
+            on_alloc: |size| MEMORY_USAGE.with(|x| x.set(x.get() + size as isize)),
+            on_dealloc: |size| MEMORY_USAGE.with(|x| x.set(x.get() - size as isize)),
+        })
+        .unwrap();
+
+        let a = ByteView::from("helloworld_thisisaverylongstring");
+        let b = ByteView::from(vec![0; 100]);
+        let c = ByteView::from("abc");
+        assert_eq!(
+            (a.heap_size() + b.heap_size()) as isize,
+            MEMORY_USAGE.with(Cell::get)
+        );
+        assert_eq!(0, c.heap_size());
+
+        let weak = a.downgrade();
+        drop(a);
+        drop(b);
+        assert_eq!(weak.upgrade(), None);
+        assert!(MEMORY_USAGE.with(Cell::get) > 0);
+
+        drop(weak);
+        assert_eq!(0, MEMORY_USAGE.with(Cell::get));
+    }
+}
@@ -0,0 +1,290 @@
+// Copyright (c) 2024-present, fjall-rs
+// This source code is licensed under both the Apache 2.0 and MIT License
+// (found in the LICENSE-* files in the repository)
+
+//! Encodings of slices in human-readable serde formats
+//!
+//! Human-readable formats (like JSON) serialize a [`ByteView`] as a hex string by default.
+//! Use the modules below with `#[serde(with = "...")]` to pick the encoding per field.
+//! Other formats always serialize the raw bytes.
+//!
+//! Deserializing accepts the encoded string, or an array of bytes.
+//!
+//! ```
+//! # use byteview::ByteView;
+//! # use serde::{Deserialize, Serialize};
+//! #[derive(Serialize, Deserialize)]
+//! struct Entry {
+//!     key: ByteView,
+//!
+//!     #[serde(with = "byteview::human_readable::base64")]
+//!     value: ByteView,
+//! }
+//!
+//! let entry = Entry {
+//!     key: ByteView::from("abc"),
+//!     value: ByteView::from("abc"),
+//! };
+//!
+//! let json = serde_json::to_string(&entry)?;
+//! assert_eq!(r#"{"key":"616263","value":"YWJj"}"#, json);
+//! # Ok::<(), serde_json::Error>(())
+//! ```
+
+use crate::ByteView;
+use serde::{Deserializer, Serializer};
+
+/// Encoding of slices in human-readable formats
+#[derive(Copy, Clone, Debug)]
+pub(crate) enum Encoding {
+    Hex,
+    Base64,
+}
+
+const HEX_ALPHABET: &[u8; 16] = b"0123456789abcdef";
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+const fn hex_value(c: u8) -> Option<u8> {
+    match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'a'..=b'f' => Some(c - b'a' + 10),
+        b'A'..=b'F' => Some(c - b'A' + 10),
+        _ => None,
+    }
+}
+
+const fn base64_value(c: u8) -> Option<u8> {
+    match c {
+        b'A'..=b'Z' => Some(c - b'A'),
+        b'a'..=b'z' => Some(c - b'a' + 26),
+        b'0'..=b'9' => Some(c - b'0' + 52),
+        b'+' => Some(62),
+        b'/' => Some(63),
+        _ => None,
+    }
+}
+
+fn alphabet_char(alphabet: &[u8], idx: u8) -> char {
+    alphabet
+        .get(usize::from(idx))
+        .copied()
+        .map_or('=', char::from)
+}
+
+impl Encoding {
+    pub(crate) fn encode(self, bytes: &[u8]) -> String {
+        match self {
+            Self::Hex => {
+                let mut out = String::with_capacity(bytes.len() * 2);
+
+                for byte in bytes {
+                    out.push(alphabet_char(HEX_ALPHABET, byte >> 4));
+                    out.push(alphabet_char(HEX_ALPHABET, byte & 0x0F));
+                }
+
+                out
+            }
+            Self::Base64 => {
+                let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+
+                for chunk in bytes.chunks(3) {
+                    let mut buf = [0; 3];
+                    for (dst, src) in buf.iter_mut().zip(chunk) {
+                        *dst = *src;
+                    }
+                    let [b0, b1, b2] = buf;
+
+                    out.push(alphabet_char(BASE64_ALPHABET, b0 >> 2));
+                    out.push(alphabet_char(
+                        BASE64_ALPHABET,
+                        ((b0 & 0x03) << 4) | (b1 >> 4),
+                    ));
+
+                    if chunk.len() > 1 {
+                        out.push(alphabet_char(
+                            BASE64_ALPHABET,
+                            ((b1 & 0x0F) << 2) | (b2 >> 6),
+                        ));
+                    } else {
+                        out.push('=');
+                    }
+
+                    if chunk.len() > 2 {
+                        out.push(alphabet_char(BASE64_ALPHABET, b2 & 0x3F));
+                    } else {
+                        out.push('=');
+                    }
+                }
+
+                out
+            }
+        }
+    }
+
+    pub(crate) fn decode(self, input: &str) -> Result<Vec<u8>, &'static str> {
+        let input = input.as_bytes();
+
+        match self {
+            Self::Hex => {
+                if input.len() % 2 != 0 {
+                    return Err("hex string has odd length");
+                }
+
+                input
+                    .chunks_exact(2)
+                    .map(|pair| match pair {
+                        [hi, lo] => hex_value(*hi)
+                            .zip(hex_value(*lo))
+                            .map(|(hi, lo)| (hi << 4) | lo),
+                        _ => None,
+                    })
+                    .collect::<Option<Vec<_>>>()
+                    .ok_or("invalid hex string")
+            }
+            Self::Base64 => {
+                if input.len() % 4 != 0 {
+                    return Err("base64 string has invalid length");
+                }
+
+                let mut bytes = Vec::with_capacity(input.len() / 4 * 3);
+
+                for (idx, chunk) in input.chunks_exact(4).enumerate() {
+                    let is_last = (idx + 1) * 4 == input.len();
+                    let padding = chunk.iter().rev().take_while(|&&c| c == b'=').count();
+
+                    if padding > 2 || (padding > 0 && !is_last) {
+                        return Err("invalid base64 string");
+                    }
+
+                    let mut values = [0u8; 4];
+                    for (dst, &c) in values.iter_mut().zip(chunk).take(4 - padding) {
+                        *dst = base64_value(c).ok_or("invalid base64 string")?;
+                    }
+                    let [v0, v1, v2, v3] = values;
+
+                    bytes.push((v0 << 2) | (v1 >> 4));
+
+                    if padding < 2 {
+                        bytes.push((v1 << 4) | (v2 >> 2));
+                    }
+
+                    if padding < 1 {
+                        bytes.push((v2 << 6) | v3);
+                    }
+                }
+
+                Ok(bytes)
+            }
+        }
+    }
+
+    pub(crate) fn serialize<S: Serializer>(
+        self,
+        view: &ByteView,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&self.encode(view))
+        } else {
+            serializer.serialize_bytes(view)
+        }
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<ByteView, D::Error> {
+        use crate::byteview::{has_source, ByteViewVisitor};
+
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(ByteViewVisitor(Some(self)))
+        } else if has_source() {
+            // NOTE: Ask for borrowed bytes, so they can share the source's heap allocation
+            deserializer.deserialize_bytes(ByteViewVisitor(None))
+        } else {
+            deserializer.deserialize_byte_buf(ByteViewVisitor(None))
+        }
+    }
+}
+
+/// Encodes slices as hex strings in human-readable formats (the default)
+pub mod hex {
+    use super::Encoding;
+    use crate::ByteView;
+    use serde::{Deserializer, Serializer};
+
+    /// Serializes the slice as a hex string, if the format is human-readable.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the serializer failed.
+    pub fn serialize<S: Serializer>(view: &ByteView, serializer: S) -> Result<S::Ok, S::Error> {
+        Encoding::Hex.serialize(view, serializer)
+    }
+
+    /// Deserializes a slice from a hex string, if the format is human-readable.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input is not a valid hex string or byte array.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<ByteView, D::Error> {
+        Encoding::Hex.deserialize(deserializer)
+    }
+}
+
+/// Encodes slices as base64 strings (standard alphabet, padded) in human-readable formats
+pub mod base64 {
+    use super::Encoding;
+    use crate::ByteView;
+    use serde::{Deserializer, Serializer};
+
+    /// Serializes the slice as a base64 string, if the format is human-readable.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the serializer failed.
+    pub fn serialize<S: Serializer>(view: &ByteView, serializer: S) -> Result<S::Ok, S::Error> {
+        Encoding::Base64.serialize(view, serializer)
+    }
+
+    /// Deserializes a slice from a base64 string, if the format is human-readable.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input is not a valid base64 string or byte array.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<ByteView, D::Error> {
+        Encoding::Base64.deserialize(deserializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Encoding;
+
+    #[test]
+    fn human_readable_codecs() {
+        for len in 0..20 {
+            let bytes = (0..len).map(|i: u8| i.wrapping_mul(37)).collect::<Vec<_>>();
+
+            for encoding in [Encoding::Hex, Encoding::Base64] {
+                let s = encoding.encode(&bytes);
+                assert_eq!(Ok(&bytes), encoding.decode(&s).as_ref());
+            }
+        }
+
+        assert_eq!("68656c6c6f", Encoding::Hex.encode(b"hello"));
+        assert_eq!("aGVsbG8=", Encoding::Base64.encode(b"hello"));
+        assert_eq!("aGVsbG8h", Encoding::Base64.encode(b"hello!"));
+        assert_eq!("aGk=", Encoding::Base64.encode(b"hi"));
+
+        assert_eq!(Ok(b"\xAB".to_vec()), Encoding::Hex.decode("aB"));
+        assert!(Encoding::Hex.decode("abc").is_err());
+        assert!(Encoding::Hex.decode("zz").is_err());
+        assert!(Encoding::Base64.decode("aGk").is_err());
+        assert!(Encoding::Base64.decode("a===").is_err());
+        assert!(Encoding::Base64.decode("aG=kaGk=").is_err());
+        assert!(Encoding::Base64.decode("aG!=").is_err());
+    }
+}
@@ -0,0 +1,329 @@
+// Copyright (c) 2024-present, fjall-rs
+// This source code is licensed under both the Apache 2.0 and MIT License
+// (found in the LICENSE-* files in the repository)
+
+use crate::{byteview::INLINE_SIZE, ByteView, StrView};
+use std::{
+    collections::HashSet,
+    sync::{Mutex, PoisonError},
+};
+
+/// Minimum amount of entries before [`StrInterner`] starts evicting
+const MIN_EVICTION_THRESHOLD: usize = 64;
+
+/// Deduplicates equal byte strings
+///
+/// Interning a byte string that was seen before returns a slice
+/// of the existing heap allocation, instead of allocating again.
+///
+/// # Examples
+///
+/// ```
+/// # use byteview::Interner;
+/// let mut interner = Interner::new();
+///
+/// let a = interner.intern(b"helloworld_thisisaverylongstring");
+/// let b = interner.intern(b"helloworld_thisisaverylongstring");
+/// assert_eq!(a.as_ptr(), b.as_ptr());
+/// assert_eq!(1, interner.len());
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Interner {
+    set: HashSet<ByteView>,
+}
+
+impl Interner {
+    /// Creates a new, empty interner.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the interned slice equal to `bytes`,
+    /// allocating a new one if it was not interned before.
+    pub fn intern(&mut self, bytes: &[u8]) -> ByteView {
+        if let Some(view) = self.set.get(bytes) {
+            return view.clone();
+        }
+
+        let view = ByteView::new(bytes);
+        self.set.insert(view.clone());
+        view
+    }
+
+    /// Returns the interned slice equal to `view`,
+    /// or interns `view` itself if it was not interned before.
+    ///
+    /// Unlike [`Interner::intern`], this never allocates a new slice.
+    pub fn intern_view(&mut self, view: ByteView) -> ByteView {
+        if let Some(existing) = self.set.get(&*view) {
+            return existing.clone();
+        }
+
+        self.set.insert(view.clone());
+        view
+    }
+
+    /// Returns the interned slice equal to `bytes`, if it exists.
+    #[must_use]
+    pub fn get(&self, bytes: &[u8]) -> Option<ByteView> {
+        self.set.get(bytes).cloned()
+    }
+
+    /// Returns `true` if a slice equal to `bytes` is interned.
+    #[must_use]
+    pub fn contains(&self, bytes: &[u8]) -> bool {
+        self.set.contains(bytes)
+    }
+
+    /// Removes the interned slice equal to `bytes`, returning it.
+    pub fn remove(&mut self, bytes: &[u8]) -> Option<ByteView> {
+        self.set.take(bytes)
+    }
+
+    /// Returns the amount of interned slices.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.set.len()
+    }
+
+    /// Returns `true` if no slices are interned.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.set.is_empty()
+    }
+
+    /// Removes all interned slices.
+    pub fn clear(&mut self) {
+        self.set.clear();
+    }
+}
+
+/// Thread-safe, deduplicating string pool
+///
+/// The interner holds strong references to its entries.
+/// Entries that are no longer referenced outside of the interner are evicted by a sweep,
+/// which runs when [`StrInterner::intern`] inserts a new string while the
+/// amount of entries has reached a threshold, or explicitly using [`StrInterner::evict`].
+/// Until then, unused strings stay allocated.
+///
+/// After every sweep, the threshold is set to twice the amount of remaining entries
+/// (at least 64), so sweeping is amortized over the inserts.
+///
+/// Strings that are short enough to be inlined are not pooled,
+/// because they do not use a heap allocation anyway.
+///
+/// # Examples
+///
+/// ```
+/// # use byteview::StrInterner;
+/// let interner = StrInterner::new();
+///
+/// let a = interner.intern("helloworld_thisisaverylongstring");
+/// let b = interner.intern("helloworld_thisisaverylongstring");
+/// assert_eq!(a.as_ptr(), b.as_ptr());
+///
+/// drop((a, b));
+/// assert_eq!(1, interner.evict());
+/// assert!(interner.is_empty());
+/// ```
+#[derive(Debug, Default)]
+pub struct StrInterner {
+    inner: Mutex<StrInternerInner>,
+}
+
+#[derive(Debug, Default)]
+struct StrInternerInner {
+    set: HashSet<StrView>,
+
+    /// Amount of entries at which the next eviction happens
+    evict_at: usize,
+}
+
+impl StrInternerInner {
+    fn evict(&mut self) -> usize {
+        let before = self.set.len();
+
+        // NOTE: The lock is held, so no new references can be handed out,
+        // and a ref count of 1 means that only we are holding the string
+        self.set.retain(|s| s.as_view().ref_count() > 1);
+
+        self.evict_at = (self.set.len() * 2).max(MIN_EVICTION_THRESHOLD);
+
+        before - self.set.len()
+    }
+}
+
+impl StrInterner {
+    /// Creates a new, empty interner.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, StrInternerInner> {
+        // NOTE: The set stays consistent even if a thread panicked while holding the lock
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Returns the interned string equal to `s`,
+    /// allocating a new one if it is not interned.
+    ///
+    /// Inserting a new string may first sweep unused entries, see [`StrInterner`].
+    pub fn intern(&self, s: &str) -> StrView {
+        if s.len() <= INLINE_SIZE {
+            return StrView::new(s);
+        }
+
+        let mut inner = self.lock();
+
+        if let Some(existing) = inner.set.get(s) {
+            return existing.clone();
+        }
+
+        if inner.set.len() >= inner.evict_at {
+            inner.evict();
+        }
+
+        let s = StrView::new(s);
+        inner.set.insert(s.clone());
+        s
+    }
+
+    /// Returns the interned string equal to `s`, if it exists.
+    #[must_use]
+    pub fn get(&self, s: &str) -> Option<StrView> {
+        self.lock().set.get(s).cloned()
+    }
+
+    /// Evicts all strings that are not referenced outside of the interner,
+    /// returning the amount of evicted strings.
+    pub fn evict(&self) -> usize {
+        self.lock().evict()
+    }
+
+    /// Returns the amount of interned strings.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.lock().set.len()
+    }
+
+    /// Returns `true` if no strings are interned.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.lock().set.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Interner, StrInterner};
+    use crate::ByteView;
+
+    #[test]
+    fn interner_dedup() {
+        let mut interner = Interner::new();
+        assert!(interner.is_empty());
+
+        let a = interner.intern(b"helloworld_thisisaverylongstring");
+        let b = interner.intern(b"helloworld_thisisaverylongstring");
+        let c = interner.intern(b"abc");
+        assert_eq!(a.as_ptr(), b.as_ptr());
+        assert_eq!(3, a.ref_count());
+        assert_eq!(b"abc", &*c);
+        assert_eq!(2, interner.len());
+
+        assert!(interner.contains(b"abc"));
+        assert!(interner.get(b"def").is_none());
+        assert_eq!(
+            a.as_ptr(),
+            interner
+                .get(b"helloworld_thisisaverylongstring")
+                .unwrap()
+                .as_ptr(),
+        );
+
+        assert!(interner
+            .remove(b"helloworld_thisisaverylongstring")
+            .is_some());
+        assert_eq!(2, a.ref_count());
+
+        interner.clear();
+        assert!(interner.is_empty());
+    }
+
+    #[test]
+    fn interner_view() {
+        let mut interner = Interner::new();
+
+        let view = ByteView::from("helloworld_thisisaverylongstring");
+        let ptr = view.as_ptr();
+
+        let a = interner.intern_view(view);
+        let b = interner.intern_view(ByteView::from("helloworld_thisisaverylongstring"));
+        assert_eq!(ptr, a.as_ptr());
+        assert_eq!(ptr, b.as_ptr());
+        assert_eq!(1, interner.len());
+    }
+
+    #[test]
+    fn str_interner_evict() {
+        let interner = StrInterner::new();
+
+        let a = interner.intern("helloworld_thisisaverylongstring");
+        let b = interner.intern("helloworld_thisisaverylongstring");
+        let c = interner.intern("helloworld_thisisanotherlongstring");
+        assert_eq!(a.as_ptr(), b.as_ptr());
+        assert_eq!(2, interner.len());
+
+        let short = interner.intern("abc");
+        assert_eq!("abc", &*short);
+        assert_eq!(2, interner.len());
+
+        assert_eq!(0, interner.evict());
+
+        drop(a);
+        assert_eq!(0, interner.evict());
+
+        drop(b);
+        assert_eq!(1, interner.evict());
+        assert!(interner.get("helloworld_thisisaverylongstring").is_none());
+        assert_eq!(
+            c.as_ptr(),
+            interner
+                .get("helloworld_thisisanotherlongstring")
+                .unwrap()
+                .as_ptr(),
+        );
+    }
+
+    #[test]
+    fn str_interner_evict_while_interning() {
+        let interner = StrInterner::new();
+
+        for i in 0..1_000 {
+            drop(interner.intern(&format!("helloworld_thisisaverylongstring_{i}")));
+        }
+        assert!(interner.len() <= super::MIN_EVICTION_THRESHOLD);
+    }
+
+    #[test]
+    #[cfg(not(feature = "local"))]
+    fn str_interner_threads() {
+        let interner = StrInterner::new();
+
+        let strings = std::thread::scope(|s| {
+            let handles = (0..4)
+                .map(|_| s.spawn(|| interner.intern("helloworld_thisisaverylongstring")))
+                .collect::<Vec<_>>();
+
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap())
+                .collect::<Vec<_>>()
+        });
+
+        assert!(strings.iter().all(|s| s.as_ptr() == strings[0].as_ptr()));
+        assert_eq!(1, interner.len());
+    }
+}
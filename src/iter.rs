@@ -0,0 +1,188 @@
+// Copyright (c) 2024-present, fjall-rs
+// This source code is licensed under both the Apache 2.0 and MIT License
+// (found in the LICENSE-* files in the repository)
+
+use crate::ByteView;
+use std::iter::FusedIterator;
+
+/// Iterator over the bytes of a [`ByteView`], see [`ByteView::iter`]
+#[derive(Clone, Debug)]
+pub struct Iter<'a> {
+    inner: std::slice::Iter<'a, u8>,
+}
+
+impl<'a> Iter<'a> {
+    /// Returns the remaining bytes.
+    #[must_use]
+    pub fn as_slice(&self) -> &'a [u8] {
+        self.inner.as_slice()
+    }
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = &'a u8;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for Iter<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+    }
+}
+
+impl ExactSizeIterator for Iter<'_> {}
+
+impl FusedIterator for Iter<'_> {}
+
+/// Owning iterator over the bytes of a [`ByteView`]
+///
+/// The iterator keeps the heap allocation alive until it is dropped.
+#[derive(Clone, Debug)]
+pub struct IntoIter {
+    view: ByteView,
+    start: usize,
+    end: usize,
+}
+
+impl IntoIter {
+    /// Returns the remaining bytes.
+    #[must_use]
+    pub fn as_slice(&self) -> &[u8] {
+        self.view.get(self.start..self.end).unwrap_or_default()
+    }
+}
+
+impl Iterator for IntoIter {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.start >= self.end {
+            return None;
+        }
+
+        let byte = self.view.get(self.start).copied();
+        self.start += 1;
+        byte
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.start;
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for IntoIter {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.start >= self.end {
+            return None;
+        }
+
+        self.end -= 1;
+        self.view.get(self.end).copied()
+    }
+}
+
+impl ExactSizeIterator for IntoIter {}
+
+impl FusedIterator for IntoIter {}
+
+impl IntoIterator for ByteView {
+    type Item = u8;
+    type IntoIter = IntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            start: 0,
+            end: self.len(),
+            view: self,
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a ByteView {
+    type Item = &'a u8;
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl ByteView {
+    /// Returns an iterator over the bytes of the slice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use byteview::ByteView;
+    /// let slice = ByteView::from("hello");
+    /// assert_eq!(2, slice.iter().filter(|&&b| b == b'l').count());
+    ///
+    /// let mut sum = 0;
+    /// for byte in &slice {
+    ///     sum += u32::from(*byte);
+    /// }
+    /// assert_eq!(532, sum);
+    /// ```
+    #[must_use]
+    pub fn iter(&self) -> Iter<'_> {
+        Iter {
+            inner: (**self).iter(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ByteView;
+
+    #[test]
+    fn iter() {
+        let slice = ByteView::from("helloworld_thisisaverylongstring");
+
+        assert!(slice.iter().eq(b"helloworld_thisisaverylongstring"));
+        assert!((&slice).into_iter().rev().eq(slice.as_ref().iter().rev()));
+        assert_eq!(32, slice.iter().len());
+
+        let mut iter = slice.iter();
+        iter.nth(10);
+        assert_eq!(b"thisisaverylongstring", iter.as_slice());
+    }
+
+    #[test]
+    fn into_iter() {
+        for s in ["", "abc", "helloworld_thisisaverylongstring"] {
+            let slice = ByteView::from(s);
+
+            assert_eq!(s.as_bytes(), slice.clone().into_iter().collect::<Vec<_>>());
+            assert!(slice.clone().into_iter().rev().eq(s.bytes().rev()));
+            assert_eq!(s.len(), slice.clone().into_iter().len());
+        }
+
+        // NOTE: The iterator keeps the allocation alive
+        let mut iter = {
+            let slice = ByteView::from("helloworld_thisisaverylongstring");
+            let iter = slice.clone().into_iter();
+            assert_eq!(2, slice.ref_count());
+            iter
+        };
+
+        assert_eq!(Some(b'h'), iter.next());
+        assert_eq!(Some(b'g'), iter.next_back());
+        assert_eq!(b"elloworld_thisisaverylongstrin", iter.as_slice());
+        assert_eq!(30, iter.len());
+
+        let mut iter = ByteView::from("ab").into_iter();
+        assert_eq!(Some(b'b'), iter.next_back());
+        assert_eq!(Some(b'a'), iter.next_back());
+        assert_eq!(None, iter.next());
+        assert_eq!(None, iter.next_back());
+    }
+}
@@ -0,0 +1,105 @@
+// Copyright (c) 2024-present, fjall-rs
+// This source code is licensed under both the Apache 2.0 and MIT License
+// (found in the LICENSE-* files in the repository)
+
+use crate::{ByteView, StrView};
+use std::{ops::Deref, sync::OnceLock};
+
+macro_rules! impl_lazy {
+    ($name:ident, $ty:ident, $example_ctor:literal, $example_value:literal) => {
+        #[doc = concat!("A lazily initialized [`", stringify!($ty), "`], suitable for `static`s")]
+        ///
+        /// The value is built from the given closure on first access.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        #[doc = concat!("# use byteview::{", stringify!($ty), ", ", stringify!($name), "};")]
+        #[doc = concat!("static GREETING: ", stringify!($name), " = ", stringify!($name), "::new(|| ", $example_ctor, ");")]
+        ///
+        #[doc = concat!("assert_eq!(", $example_value, ", &**GREETING);")]
+        /// ```
+        pub struct $name<F = fn() -> $ty> {
+            cell: OnceLock<$ty>,
+            init: F,
+        }
+
+        impl<F: Fn() -> $ty> $name<F> {
+            /// Creates a new lazily initialized value.
+            pub const fn new(init: F) -> Self {
+                Self {
+                    cell: OnceLock::new(),
+                    init,
+                }
+            }
+
+            /// Returns the value, initializing it if needed.
+            pub fn get(&self) -> &$ty {
+                self.cell.get_or_init(&self.init)
+            }
+        }
+
+        impl<F: Fn() -> $ty> Deref for $name<F> {
+            type Target = $ty;
+
+            fn deref(&self) -> &Self::Target {
+                self.get()
+            }
+        }
+
+        impl<F> std::fmt::Debug for $name<F> {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                match self.cell.get() {
+                    Some(value) => write!(f, "{value:?}"),
+                    None => f.write_str("<uninit>"),
+                }
+            }
+        }
+    };
+}
+
+impl_lazy!(
+    LazyByteView,
+    ByteView,
+    "ByteView::from(\"helloworld_thisisaverylongstring\")",
+    "b\"helloworld_thisisaverylongstring\""
+);
+
+impl_lazy!(
+    LazyStrView,
+    StrView,
+    "StrView::from(\"helloworld_thisisaverylongstring\")",
+    "\"helloworld_thisisaverylongstring\""
+);
+
+#[cfg(test)]
+mod tests {
+    use super::{LazyByteView, LazyStrView};
+    use crate::{ByteView, StrView};
+
+    static BYTES: LazyByteView = LazyByteView::new(|| ByteView::from("a".repeat(100)));
+
+    static STR: LazyStrView =
+        LazyStrView::new(|| StrView::from("helloworld_thisisaverylongstring"));
+
+    #[test]
+    fn lazy_byteview() {
+        assert_eq!(100, BYTES.len());
+        assert!(std::ptr::eq(BYTES.get(), BYTES.get()));
+        assert_eq!(1, BYTES.ref_count());
+    }
+
+    #[test]
+    fn lazy_strview() {
+        assert_eq!("helloworld_thisisaverylongstring", &**STR);
+    }
+
+    #[test]
+    fn lazy_debug() {
+        let lazy = LazyByteView::new(|| ByteView::from("abc"));
+        assert_eq!("<uninit>", format!("{lazy:?}"));
+
+        let _ = lazy.get();
+        assert_eq!("b\"abc\"", format!("{lazy:?}"));
+    }
+}
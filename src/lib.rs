@@ -8,7 +8,7 @@
 //!
 //! ```
 //! # use byteview::ByteView;
-//! let slice = ByteView::from("helloworld_thisisaverylongstring");
+//! let slice = ByteView::from("helloworld_thisisanevenlongerstring");
 //!
 //! // No heap allocation - increases the ref count like an Arc<[u8]>
 //! let full_copy = slice.clone();
@@ -16,7 +16,7 @@
 //!
 //! // No heap allocation - increases the ref count like an Arc<[u8]>, but we only get a subslice
 //! let copy = slice.slice(11..);
-//! assert_eq!(b"thisisaverylongstring", &*copy);
+//! assert_eq!(b"thisisanevenlongerstring", &*copy);
 //!
 //! // No heap allocation - if the slice is small enough, it will be inlined into the struct...
 //! let copycopy = copy.slice(0..4);
@@ -47,7 +47,86 @@
     clippy::needless_lifetimes
 )]
 
+#[cfg(feature = "allocator-api2")]
+mod alloc;
+
+#[cfg(feature = "arrow")]
+pub mod arrow;
+
 mod byteview;
+mod byteview_mut;
+mod chunks;
+mod error;
+mod escape;
+
+#[cfg(feature = "ffi")]
+pub mod ffi;
+
+pub mod framing;
+pub mod front_coding;
+mod hooks;
+
+#[cfg(feature = "serde")]
+pub mod human_readable;
+
+mod interner;
+mod iter;
+
+#[cfg(not(feature = "local"))]
+mod lazy;
+
+mod local;
+mod macros;
+mod matches;
+mod reader;
+mod refcount;
+mod repack;
+mod segment;
+
+#[cfg(feature = "simd")]
+mod simd;
+mod sorted_block;
+mod source;
+mod split;
 mod strview;
+mod uninit;
+mod varint;
+mod view_array;
+mod weak;
+mod writer;
+
+#[cfg(all(unix, feature = "shm"))]
+mod shm;
+
+#[cfg(feature = "test-util")]
+pub mod testing;
+
+#[cfg(all(target_os = "linux", feature = "io-uring"))]
+mod uring;
+
+pub use {
+    byteview::{ByteView, Mutator, RawParts},
+    byteview_mut::ByteViewMut,
+    chunks::{Chunks, Windows},
+    error::Error,
+    escape::DisplayEscaped,
+    hooks::{set_memory_hooks, MemoryHooks},
+    interner::{Interner, StrInterner},
+    iter::{IntoIter, Iter},
+    local::LocalByteView,
+    matches::{MatchIndices, MatchOffsets, Matches},
+    reader::ByteReader,
+    repack::repack,
+    segment::{ArenaWriter, Segment},
+    sorted_block::SortedBlock,
+    source::ByteSource,
+    split::{RSplit, Split, SplitN, SplitTerminator},
+    strview::StrView,
+    uninit::UninitByteView,
+    view_array::ViewArray,
+    weak::WeakByteView,
+    writer::ByteViewWriter,
+};
 
-pub use {byteview::ByteView, strview::StrView};
+#[cfg(not(feature = "local"))]
+pub use lazy::{LazyByteView, LazyStrView};
@@ -0,0 +1,311 @@
+// Copyright (c) 2024-present, fjall-rs
+// This source code is licensed under both the Apache 2.0 and MIT License
+// (found in the LICENSE-* files in the repository)
+
+use crate::ByteView;
+use std::{cell::Cell, mem::ManuallyDrop, ops::Deref, ptr::NonNull};
+
+/// Holds a single (atomic) reference to a heap allocation,
+/// which is shared by all local slices using a non-atomic ref count
+struct LocalHandle {
+    ref_count: Cell<u64>,
+    view: ByteView,
+}
+
+/// A single-threaded, immutable byte slice
+///
+/// Like [`ByteView`], but cloning and dropping use a non-atomic ref count (like [`std::rc::Rc`]),
+/// so it cannot be sent to other threads.
+///
+/// Converting a [`ByteView`] into a local slice allocates a small handle that holds
+/// the only atomic reference for all local slices derived from it.
+/// A uniquely referenced local slice can be converted back without any atomic operation.
+///
+/// # Examples
+///
+/// ```
+/// # use byteview::{ByteView, LocalByteView};
+/// let slice = LocalByteView::from(ByteView::from("helloworld_thisisaverylongstring"));
+///
+/// let copy = slice.slice(5..);
+/// assert_eq!(b"world_thisisaverylongstring", &*copy);
+/// assert_eq!(2, slice.ref_count());
+///
+/// drop(slice);
+/// let shared = ByteView::try_from(copy).unwrap();
+/// assert_eq!(b"world_thisisaverylongstring", &*shared);
+/// ```
+pub struct LocalByteView {
+    /// The slice itself, whose reference is held by the handle
+    view: ManuallyDrop<ByteView>,
+
+    /// `None` if the slice is inlined or references static data
+    handle: Option<NonNull<LocalHandle>>,
+}
+
+impl LocalByteView {
+    /// Creates a new slice from an existing byte slice.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length does not fit in a u32 (4 GiB).
+    #[must_use]
+    pub fn new(slice: &[u8]) -> Self {
+        Self::from(ByteView::new(slice))
+    }
+
+    fn handle(&self) -> Option<&LocalHandle> {
+        // SAFETY: The handle lives as long as any local slice references it
+        self.handle.map(|handle| unsafe { handle.as_ref() })
+    }
+
+    /// Clones the given range of the existing slice without heap allocation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the slice is out of bounds.
+    #[must_use]
+    pub fn slice(&self, range: impl std::ops::RangeBounds<usize>) -> Self {
+        let view = self.view.slice_uncounted(range);
+
+        let handle = if view.heap_region().is_some() {
+            self.increment_ref_count();
+            self.handle
+        } else {
+            None
+        };
+
+        Self { view, handle }
+    }
+
+    fn increment_ref_count(&self) {
+        if let Some(handle) = self.handle() {
+            let rc = handle
+                .ref_count
+                .get()
+                .checked_add(1)
+                .unwrap_or_else(|| std::process::abort());
+
+            handle.ref_count.set(rc);
+        }
+    }
+
+    /// Returns the amount of local slices that share the same handle.
+    #[must_use]
+    pub fn ref_count(&self) -> u64 {
+        self.handle().map_or(1, |handle| handle.ref_count.get())
+    }
+
+    /// Clones the slice into a [`ByteView`] that can be sent to other threads.
+    ///
+    /// Unlike the [`TryFrom`] conversion, this always succeeds, but needs an atomic operation.
+    #[must_use]
+    pub fn to_shared(&self) -> ByteView {
+        (*self.view).clone()
+    }
+}
+
+impl Clone for LocalByteView {
+    fn clone(&self) -> Self {
+        self.increment_ref_count();
+
+        Self {
+            // SAFETY: We incremented the local ref count, and never drop the copied slice
+            view: ManuallyDrop::new(unsafe { std::ptr::read(&*self.view) }),
+            handle: self.handle,
+        }
+    }
+}
+
+impl Drop for LocalByteView {
+    fn drop(&mut self) {
+        let Some(handle) = self.handle else {
+            return;
+        };
+
+        // SAFETY: The handle lives as long as any local slice references it
+        let rc = unsafe { handle.as_ref() }.ref_count.get() - 1;
+
+        if rc == 0 {
+            // SAFETY: We were the last local slice, so we can drop the handle
+            // (which drops the atomic reference)
+            drop(unsafe { Box::from_raw(handle.as_ptr()) });
+        } else {
+            // SAFETY: See above
+            unsafe { handle.as_ref() }.ref_count.set(rc);
+        }
+    }
+}
+
+impl Default for LocalByteView {
+    fn default() -> Self {
+        Self::from(ByteView::default())
+    }
+}
+
+impl Deref for LocalByteView {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        &self.view
+    }
+}
+
+impl Eq for LocalByteView {}
+
+impl PartialEq for LocalByteView {
+    fn eq(&self, other: &Self) -> bool {
+        *self.view == *other.view
+    }
+}
+
+impl Ord for LocalByteView {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.view.cmp(&other.view)
+    }
+}
+
+impl PartialOrd for LocalByteView {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl std::hash::Hash for LocalByteView {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.deref().hash(state);
+    }
+}
+
+impl std::fmt::Debug for LocalByteView {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", &**self)
+    }
+}
+
+impl std::borrow::Borrow<[u8]> for LocalByteView {
+    fn borrow(&self) -> &[u8] {
+        self
+    }
+}
+
+impl AsRef<[u8]> for LocalByteView {
+    fn as_ref(&self) -> &[u8] {
+        self
+    }
+}
+
+impl From<ByteView> for LocalByteView {
+    fn from(value: ByteView) -> Self {
+        if value.heap_region().is_none() {
+            return Self {
+                view: ManuallyDrop::new(value),
+                handle: None,
+            };
+        }
+
+        // SAFETY: The handle takes over the reference, and we never drop the copied slice
+        let view = ManuallyDrop::new(unsafe { std::ptr::read(&value) });
+
+        let handle = Box::new(LocalHandle {
+            ref_count: Cell::new(1),
+            view: value,
+        });
+
+        Self {
+            view,
+            handle: Some(NonNull::from(Box::leak(handle))),
+        }
+    }
+}
+
+impl From<&[u8]> for LocalByteView {
+    fn from(value: &[u8]) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<&str> for LocalByteView {
+    fn from(value: &str) -> Self {
+        Self::new(value.as_bytes())
+    }
+}
+
+impl TryFrom<LocalByteView> for ByteView {
+    type Error = LocalByteView;
+
+    /// Converts a local slice into a [`ByteView`], without any atomic operation.
+    ///
+    /// Fails if other local slices share the same handle,
+    /// see [`LocalByteView::to_shared`] for an alternative.
+    fn try_from(value: LocalByteView) -> Result<Self, Self::Error> {
+        if value.ref_count() > 1 {
+            return Err(value);
+        }
+
+        let value = ManuallyDrop::new(value);
+
+        // SAFETY: We were the only local slice, so we can take over
+        // the handle's reference, and then free the handle without dropping its slice
+        unsafe {
+            if let Some(handle) = value.handle {
+                let handle = Box::from_raw(handle.as_ptr());
+                std::mem::forget(handle.view);
+            }
+
+            Ok(std::ptr::read(&*value.view))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LocalByteView;
+    use crate::ByteView;
+
+    #[test]
+    fn local_clone() {
+        let shared = ByteView::from("helloworld_thisisaverylongstring");
+        let slice = LocalByteView::from(shared.clone());
+        assert_eq!(2, shared.ref_count());
+
+        let copies = (0..10).map(|_| slice.clone()).collect::<Vec<_>>();
+        assert_eq!(11, slice.ref_count());
+        assert_eq!(2, shared.ref_count());
+        assert_eq!(slice, copies[9]);
+
+        let tail = slice.slice(5..);
+        let inline = slice.slice(..5);
+        assert_eq!(b"world_thisisaverylongstring", &*tail);
+        assert_eq!(b"hello", &*inline);
+        assert_eq!(1, inline.ref_count());
+        assert_eq!(12, slice.ref_count());
+
+        drop(copies);
+        drop(slice);
+        assert_eq!(1, tail.ref_count());
+        assert_eq!(2, shared.ref_count());
+
+        drop(tail);
+        assert_eq!(1, shared.ref_count());
+    }
+
+    #[test]
+    fn local_to_shared() {
+        let slice = LocalByteView::new(b"helloworld_thisisaverylongstring");
+        let copy = slice.clone();
+
+        let slice = ByteView::try_from(slice).unwrap_err();
+
+        let shared = copy.to_shared();
+        assert_eq!(2, shared.ref_count());
+
+        drop(copy);
+        let unique = ByteView::try_from(slice).unwrap();
+        assert_eq!(b"helloworld_thisisaverylongstring", &*unique);
+        assert_eq!(2, unique.ref_count());
+
+        let inline = ByteView::try_from(LocalByteView::from("abc")).unwrap();
+        assert_eq!(b"abc", &*inline);
+    }
+}
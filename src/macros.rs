@@ -0,0 +1,94 @@
+// Copyright (c) 2024-present, fjall-rs
+// This source code is licensed under both the Apache 2.0 and MIT License
+// (found in the LICENSE-* files in the repository)
+
+/// Creates a [`ByteView`](crate::ByteView) from a byte string literal.
+///
+/// The slice is built at compile time: short literals are inlined,
+/// and long literals reference the static data (see [`ByteView::from_static`](crate::ByteView::from_static)),
+/// so no heap allocation happens either way.
+///
+/// # Examples
+///
+/// ```
+/// use byteview::{byteview, ByteView};
+///
+/// let short = byteview!(b"abc");
+/// assert!(short.is_inline());
+///
+/// const LONG: ByteView = byteview!(b"helloworld_thisisaverylongstring");
+/// assert_eq!(b"helloworld_thisisaverylongstring", &*LONG);
+/// assert_eq!(0, LONG.heap_size());
+///
+/// assert!(byteview!().is_empty());
+/// ```
+#[macro_export]
+macro_rules! byteview {
+    () => {
+        $crate::byteview!(b"")
+    };
+    ($lit:literal) => {{
+        const VIEW: $crate::ByteView = $crate::ByteView::from_static($lit);
+        VIEW
+    }};
+}
+
+/// Creates a [`StrView`](crate::StrView) from a string literal.
+///
+/// Like [`byteview!`], the string is built at compile time, without any heap allocation.
+///
+/// # Examples
+///
+/// ```
+/// use byteview::strview;
+///
+/// let short = strview!("abc");
+/// assert_eq!("abc", &*short);
+///
+/// let long = strview!("helloworld_thisisaverylongstring");
+/// assert_eq!("helloworld_thisisaverylongstring", &*long);
+///
+/// assert!(strview!().is_empty());
+/// ```
+#[macro_export]
+macro_rules! strview {
+    () => {
+        $crate::strview!("")
+    };
+    ($lit:literal) => {{
+        const VIEW: $crate::StrView = $crate::StrView::from_static($lit);
+        VIEW
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ByteView, StrView};
+
+    #[test]
+    fn byteview_macro() {
+        let short = byteview!(b"abc");
+        assert_eq!(ByteView::from("abc"), short);
+        assert!(short.is_inline());
+
+        let long = byteview!(b"helloworld_thisisaverylongstring");
+        assert_eq!(b"helloworld_thisisaverylongstring", &*long);
+        assert!(!long.is_inline());
+        assert_eq!(0, long.heap_size());
+        assert_eq!(long, long.clone());
+
+        assert_eq!(ByteView::from(""), byteview!());
+    }
+
+    #[test]
+    fn strview_macro() {
+        let short = strview!("abc");
+        assert_eq!(StrView::from("abc"), short);
+
+        let long = strview!("helloworld_thisisaverylongstring");
+        assert_eq!("helloworld_thisisaverylongstring", &*long);
+        assert_eq!(0, long.as_view().heap_size());
+
+        assert_eq!(StrView::from(""), strview!());
+    }
+}
@@ -0,0 +1,205 @@
+// Copyright (c) 2024-present, fjall-rs
+// This source code is licensed under both the Apache 2.0 and MIT License
+// (found in the LICENSE-* files in the repository)
+
+use crate::ByteView;
+use memchr::memmem::FindIter;
+
+/// Iterator over the offsets of all non-overlapping occurrences
+/// of a byte pattern in a [`ByteView`], see [`ByteView::match_offsets`]
+#[derive(Clone, Debug)]
+pub struct MatchOffsets<'a, 'n> {
+    inner: FindIter<'a, 'n>,
+}
+
+impl Iterator for MatchOffsets<'_, '_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+/// Iterator over all non-overlapping occurrences of a byte pattern in a [`ByteView`],
+/// and their offsets, see [`ByteView::match_indices`]
+#[derive(Clone, Debug)]
+pub struct MatchIndices<'a, 'n> {
+    view: &'a ByteView,
+    inner: MatchOffsets<'a, 'n>,
+    len: usize,
+}
+
+impl Iterator for MatchIndices<'_, '_> {
+    type Item = (usize, ByteView);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let offset = self.inner.next()?;
+        Some((offset, self.view.slice(offset..(offset + self.len))))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+/// Iterator over all non-overlapping occurrences of a byte pattern in a [`ByteView`],
+/// see [`ByteView::matches`]
+#[derive(Clone, Debug)]
+pub struct Matches<'a, 'n> {
+    inner: MatchIndices<'a, 'n>,
+}
+
+impl Iterator for Matches<'_, '_> {
+    type Item = ByteView;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(_, view)| view)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl ByteView {
+    /// Returns an iterator over the offsets of all non-overlapping occurrences of `needle`,
+    /// from the start to the end of the slice.
+    ///
+    /// An empty needle matches at every offset, including the end of the slice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use byteview::ByteView;
+    /// let slice = ByteView::from("a,b,,c");
+    /// assert_eq!(vec![1, 3, 4], slice.match_offsets(",").collect::<Vec<_>>());
+    ///
+    /// // NOTE: Occurrences do not overlap
+    /// let slice = ByteView::from("aaaaa");
+    /// assert_eq!(vec![0, 2], slice.match_offsets("aa").collect::<Vec<_>>());
+    /// ```
+    #[must_use]
+    pub fn match_offsets<'a, 'n, T: AsRef<[u8]> + ?Sized>(
+        &'a self,
+        needle: &'n T,
+    ) -> MatchOffsets<'a, 'n> {
+        MatchOffsets {
+            inner: memchr::memmem::find_iter(self, needle.as_ref()),
+        }
+    }
+
+    /// Returns an iterator over all non-overlapping occurrences of `needle`,
+    /// yielding their offsets and the matched bytes as slices, like `str::match_indices`.
+    ///
+    /// Like [`ByteView::slice`], the slices reference the same heap allocation
+    /// (or are inlined, if short enough), without copying.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use byteview::ByteView;
+    /// let slice = ByteView::from("helloworld_thisisaverylongstring_helloworld_thisisaverylongstring");
+    /// let needle = "world_thisisaverylongstring";
+    ///
+    /// let matches = slice.match_indices(needle).collect::<Vec<_>>();
+    /// assert_eq!(2, matches.len());
+    /// assert_eq!((38, ByteView::from(needle)), matches[1]);
+    /// assert!(slice.contains_view(&matches[1].1));
+    /// ```
+    #[must_use]
+    pub fn match_indices<'a, 'n, T: AsRef<[u8]> + ?Sized>(
+        &'a self,
+        needle: &'n T,
+    ) -> MatchIndices<'a, 'n> {
+        let needle = needle.as_ref();
+
+        MatchIndices {
+            view: self,
+            inner: self.match_offsets(needle),
+            len: needle.len(),
+        }
+    }
+
+    /// Returns an iterator over all non-overlapping occurrences of `needle`,
+    /// yielding the matched bytes as slices, like `str::matches`.
+    ///
+    /// See [`ByteView::match_indices`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use byteview::ByteView;
+    /// let slice = ByteView::from("a1b22c333");
+    /// let digits = slice.matches("2").collect::<Vec<_>>();
+    /// assert_eq!(vec![ByteView::from("2"), ByteView::from("2")], digits);
+    /// ```
+    #[must_use]
+    pub fn matches<'a, 'n, T: AsRef<[u8]> + ?Sized>(&'a self, needle: &'n T) -> Matches<'a, 'n> {
+        Matches {
+            inner: self.match_indices(needle),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ByteView;
+
+    #[test]
+    fn match_offsets() {
+        let slice = ByteView::from("helloworld_thisisaverylongstring_hello_hello");
+
+        assert_eq!(
+            vec![0, 33, 39],
+            slice.match_offsets("hello").collect::<Vec<_>>()
+        );
+        assert_eq!(vec![13, 15], slice.match_offsets(b"is").collect::<Vec<_>>());
+        assert_eq!(0, slice.match_offsets("hellO").count());
+        assert_eq!(
+            vec![0, 1, 2, 3],
+            ByteView::from("abc").match_offsets("").collect::<Vec<_>>()
+        );
+        assert_eq!(0, ByteView::from("").match_offsets("a").count());
+    }
+
+    #[test]
+    fn match_indices() {
+        let slice =
+            ByteView::from("helloworld_thisisaverylongstring_helloworld_thisisaverylongstring");
+
+        let matches = slice
+            .match_indices("helloworld_thisisaverylong")
+            .collect::<Vec<_>>();
+        assert_eq!(2, matches.len());
+        assert_eq!(3, slice.ref_count());
+
+        for (offset, view) in &matches {
+            assert_eq!(b"helloworld_thisisaverylong", &**view);
+            assert_eq!(Some(*offset), view.offset_in_parent());
+            assert!(slice.contains_view(view));
+        }
+    }
+
+    #[test]
+    fn matches() {
+        let slice =
+            ByteView::from("helloworld_thisisaverylongstring_helloworld_thisisaverylongstring");
+
+        for s in ["is", "hello", "", "x"] {
+            let expected = std::str::from_utf8(&slice)
+                .unwrap()
+                .matches(s)
+                .map(ByteView::from)
+                .collect::<Vec<_>>();
+            assert_eq!(expected, slice.matches(s).collect::<Vec<_>>(), "{s:?}");
+        }
+
+        let short = slice.matches("is").collect::<Vec<_>>();
+        assert_eq!(4, short.len());
+        assert!(short.iter().all(ByteView::is_inline));
+    }
+}
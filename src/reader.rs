@@ -0,0 +1,288 @@
+// Copyright (c) 2024-present, fjall-rs
+// This source code is licensed under both the Apache 2.0 and MIT License
+// (found in the LICENSE-* files in the repository)
+
+use crate::{varint, ByteView};
+use std::io::{Error, ErrorKind};
+
+macro_rules! impl_get {
+    ($($name:ident, $ty:ty, $conv:ident, $endian:literal;)*) => {
+        $(
+            #[doc = concat!("Reads a ", $endian, "`", stringify!($ty), "`, advancing the cursor.")]
+            ///
+            /// # Errors
+            ///
+            /// Returns an error of kind [`ErrorKind::UnexpectedEof`] if not enough bytes remain,
+            /// in which case the cursor is not advanced.
+            pub fn $name(&mut self) -> std::io::Result<$ty> {
+                self.take_array().map(<$ty>::$conv)
+            }
+        )*
+    };
+}
+
+/// A cursor for decoding values from a [`ByteView`]
+///
+/// Slices read from the cursor reference the underlying heap allocation, without copying.
+///
+/// # Examples
+///
+/// ```
+/// # use byteview::{ByteReader, ByteView};
+/// let mut bytes = vec![7, 32, 0, 0, 0];
+/// bytes.extend_from_slice(b"helloworld_thisisaverylongstring");
+///
+/// let mut reader = ByteReader::new(ByteView::from(bytes));
+/// assert_eq!(7, reader.get_u8()?);
+///
+/// let len = reader.get_u32_le()? as usize;
+/// let key = reader.get_slice(len)?;
+/// assert_eq!(b"helloworld_thisisaverylongstring", &*key);
+///
+/// assert_eq!(0, reader.remaining());
+/// assert!(reader.get_u8().is_err());
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ByteReader {
+    view: ByteView,
+    pos: usize,
+}
+
+impl ByteReader {
+    /// Creates a cursor positioned at the start of the given slice.
+    #[must_use]
+    pub const fn new(view: ByteView) -> Self {
+        Self { view, pos: 0 }
+    }
+
+    /// Returns the current position, in bytes from the start of the slice.
+    #[must_use]
+    pub const fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// Returns the amount of bytes that are left to read.
+    #[must_use]
+    pub fn remaining(&self) -> usize {
+        self.view.len() - self.pos
+    }
+
+    /// Returns `true` if no bytes are left to read.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.remaining() == 0
+    }
+
+    /// Returns the bytes that are left to read, without advancing the cursor.
+    #[must_use]
+    pub fn chunk(&self) -> &[u8] {
+        self.view.get(self.pos..).unwrap_or_default()
+    }
+
+    /// Advances the cursor by `n` bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind [`ErrorKind::UnexpectedEof`] if less than `n` bytes remain,
+    /// in which case the cursor is not advanced.
+    pub fn skip(&mut self, n: usize) -> std::io::Result<()> {
+        self.take(n).map(|_| ())
+    }
+
+    /// Reads the next `n` bytes as a slice that references the same heap allocation.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind [`ErrorKind::UnexpectedEof`] if less than `n` bytes remain,
+    /// in which case the cursor is not advanced.
+    pub fn get_slice(&mut self, n: usize) -> std::io::Result<ByteView> {
+        let start = self.pos;
+        self.take(n)?;
+        Ok(self.view.slice(start..self.pos))
+    }
+
+    /// Reads a `u32` encoded as a variable-length integer (LEB128), advancing the cursor.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind [`ErrorKind::UnexpectedEof`] if the integer is truncated,
+    /// or [`ErrorKind::InvalidData`] if it does not fit in a `u32`.
+    /// In both cases, the cursor is not advanced.
+    pub fn get_var_u32(&mut self) -> std::io::Result<u32> {
+        let (value, len) = varint::decode_u32(self.chunk())?;
+        self.pos += len;
+        Ok(value)
+    }
+
+    /// Reads a `u64` encoded as a variable-length integer (LEB128), advancing the cursor.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind [`ErrorKind::UnexpectedEof`] if the integer is truncated,
+    /// or [`ErrorKind::InvalidData`] if it does not fit in a `u64`.
+    /// In both cases, the cursor is not advanced.
+    pub fn get_var_u64(&mut self) -> std::io::Result<u64> {
+        let (value, len) = varint::decode_u64(self.chunk())?;
+        self.pos += len;
+        Ok(value)
+    }
+
+    /// Returns the bytes that are left to read, consuming the cursor.
+    #[must_use]
+    pub fn into_remaining(self) -> ByteView {
+        self.view.slice(self.pos..)
+    }
+
+    fn take(&mut self, n: usize) -> std::io::Result<&[u8]> {
+        let start = self.pos;
+
+        let Some(bytes) = start
+            .checked_add(n)
+            .and_then(|end| self.view.get(start..end))
+        else {
+            return Err(Error::new(
+                ErrorKind::UnexpectedEof,
+                "not enough bytes remaining",
+            ));
+        };
+
+        self.pos += n;
+
+        Ok(bytes)
+    }
+
+    fn take_array<const N: usize>(&mut self) -> std::io::Result<[u8; N]> {
+        let bytes = self.take(N)?;
+
+        // NOTE: take() returns exactly N bytes
+        Ok(bytes.try_into().unwrap_or([0; N]))
+    }
+
+    impl_get! {
+        get_u8, u8, from_le_bytes, "";
+        get_i8, i8, from_le_bytes, "";
+        get_u16_le, u16, from_le_bytes, "little-endian ";
+        get_u16_be, u16, from_be_bytes, "big-endian ";
+        get_i16_le, i16, from_le_bytes, "little-endian ";
+        get_i16_be, i16, from_be_bytes, "big-endian ";
+        get_u32_le, u32, from_le_bytes, "little-endian ";
+        get_u32_be, u32, from_be_bytes, "big-endian ";
+        get_i32_le, i32, from_le_bytes, "little-endian ";
+        get_i32_be, i32, from_be_bytes, "big-endian ";
+        get_u64_le, u64, from_le_bytes, "little-endian ";
+        get_u64_be, u64, from_be_bytes, "big-endian ";
+        get_i64_le, i64, from_le_bytes, "little-endian ";
+        get_i64_be, i64, from_be_bytes, "big-endian ";
+        get_u128_le, u128, from_le_bytes, "little-endian ";
+        get_u128_be, u128, from_be_bytes, "big-endian ";
+        get_f32_le, f32, from_le_bytes, "little-endian ";
+        get_f32_be, f32, from_be_bytes, "big-endian ";
+        get_f64_le, f64, from_le_bytes, "little-endian ";
+        get_f64_be, f64, from_be_bytes, "big-endian ";
+    }
+}
+
+impl std::io::Read for ByteReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = buf.len().min(self.remaining());
+        let bytes = self.take(n)?;
+
+        if let Some(dst) = buf.get_mut(..n) {
+            dst.copy_from_slice(bytes);
+        }
+
+        Ok(n)
+    }
+}
+
+impl From<ByteView> for ByteReader {
+    fn from(value: ByteView) -> Self {
+        Self::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ByteReader;
+    use crate::ByteView;
+    use std::io::{ErrorKind, Read};
+
+    #[test]
+    fn reader_get() -> std::io::Result<()> {
+        let mut bytes = vec![0xFF];
+        bytes.extend_from_slice(&0x1234u16.to_be_bytes());
+        bytes.extend_from_slice(&(-5i32).to_le_bytes());
+        bytes.extend_from_slice(&u64::MAX.to_be_bytes());
+        bytes.extend_from_slice(&1.5f64.to_le_bytes());
+        bytes.extend_from_slice(b"helloworld_thisisaverylongstring");
+
+        let view = ByteView::from(bytes);
+        let mut reader = ByteReader::new(view.clone());
+
+        assert_eq!(0xFF, reader.get_u8()?);
+        assert_eq!(0x1234, reader.get_u16_be()?);
+        assert_eq!(-5, reader.get_i32_le()?);
+        assert_eq!(u64::MAX, reader.get_u64_be()?);
+        assert!((reader.get_f64_le()? - 1.5).abs() < f64::EPSILON);
+        assert_eq!(23, reader.position());
+        assert_eq!(32, reader.remaining());
+
+        let key = reader.get_slice(25)?;
+        assert_eq!(b"helloworld_thisisaverylon", &*key);
+        assert!(view.contains_view(&key));
+
+        let short = reader.get_slice(3)?;
+        assert!(short.is_inline());
+        assert_eq!(b"gst", &*short);
+
+        assert_eq!(b"ring", reader.chunk());
+        assert_eq!(b"ring", &*reader.into_remaining());
+
+        Ok(())
+    }
+
+    #[test]
+    fn reader_eof() -> std::io::Result<()> {
+        let mut reader = ByteReader::from(ByteView::from("abc"));
+
+        assert_eq!(
+            ErrorKind::UnexpectedEof,
+            reader.get_u32_le().unwrap_err().kind(),
+        );
+        assert_eq!(
+            ErrorKind::UnexpectedEof,
+            reader.get_slice(usize::MAX).unwrap_err().kind(),
+        );
+        assert_eq!(0, reader.position());
+
+        reader.skip(1)?;
+
+        let mut buf = [0; 4];
+        assert_eq!(2, reader.read(&mut buf)?);
+        assert_eq!(b"bc\0\0", &buf);
+        assert!(reader.is_empty());
+        assert_eq!(0, reader.read(&mut buf)?);
+
+        let mut reader = ByteReader::new(ByteView::from(&[0x80, 0x80][..]));
+        assert_eq!(
+            ErrorKind::UnexpectedEof,
+            reader.get_var_u64().unwrap_err().kind(),
+        );
+
+        let mut reader = ByteReader::new(ByteView::from(&[0xFF; 9][..]));
+        assert_eq!(
+            ErrorKind::UnexpectedEof,
+            reader.get_var_u64().unwrap_err().kind(),
+        );
+
+        let mut reader = ByteReader::new(ByteView::from(&[0xFF; 11][..]));
+        assert_eq!(
+            ErrorKind::InvalidData,
+            reader.get_var_u64().unwrap_err().kind(),
+        );
+        assert_eq!(0, reader.position());
+
+        Ok(())
+    }
+}
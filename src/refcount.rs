@@ -0,0 +1,85 @@
+// Copyright (c) 2024-present, fjall-rs
+// This source code is licensed under both the Apache 2.0 and MIT License
+// (found in the LICENSE-* files in the repository)
+
+#[cfg(all(not(feature = "local"), not(loom)))]
+pub use std::sync::atomic::AtomicU64 as RefCount;
+
+#[cfg(all(not(feature = "local"), loom))]
+pub use loom::sync::atomic::AtomicU64 as RefCount;
+
+#[cfg(not(loom))]
+pub use std::{hint::spin_loop, sync::atomic::fence};
+
+#[cfg(loom)]
+pub use loom::{hint::spin_loop, sync::atomic::fence};
+
+#[cfg(feature = "local")]
+pub use local::RefCount;
+
+#[cfg(feature = "local")]
+mod local {
+    use std::{cell::Cell, sync::atomic::Ordering};
+
+    /// Non-atomic drop-in replacement for [`std::sync::atomic::AtomicU64`]
+    ///
+    /// Used if the `local` feature is enabled, which makes all slices `!Send` and `!Sync`,
+    /// so the counter is never accessed concurrently.
+    /// The memory orderings are ignored.
+    #[derive(Debug)]
+    pub struct RefCount(Cell<u64>);
+
+    #[allow(clippy::needless_pass_by_value, clippy::missing_const_for_fn)]
+    impl RefCount {
+        pub const fn new(value: u64) -> Self {
+            Self(Cell::new(value))
+        }
+
+        pub fn load(&self, _: Ordering) -> u64 {
+            self.0.get()
+        }
+
+        pub fn store(&self, value: u64, _: Ordering) {
+            self.0.set(value);
+        }
+
+        pub fn fetch_add(&self, n: u64, _: Ordering) -> u64 {
+            let value = self.0.get();
+            self.0.set(value.wrapping_add(n));
+            value
+        }
+
+        pub fn fetch_sub(&self, n: u64, _: Ordering) -> u64 {
+            let value = self.0.get();
+            self.0.set(value.wrapping_sub(n));
+            value
+        }
+
+        pub fn compare_exchange(
+            &self,
+            current: u64,
+            new: u64,
+            _: Ordering,
+            _: Ordering,
+        ) -> Result<u64, u64> {
+            let value = self.0.get();
+
+            if value == current {
+                self.0.set(new);
+                Ok(value)
+            } else {
+                Err(value)
+            }
+        }
+
+        pub fn compare_exchange_weak(
+            &self,
+            current: u64,
+            new: u64,
+            success: Ordering,
+            failure: Ordering,
+        ) -> Result<u64, u64> {
+            self.compare_exchange(current, new, success, failure)
+        }
+    }
+}
@@ -0,0 +1,166 @@
+// Copyright (c) 2024-present, fjall-rs
+// This source code is licensed under both the Apache 2.0 and MIT License
+// (found in the LICENSE-* files in the repository)
+
+use crate::{byteview::Mutator, ByteView};
+
+/// Maximum size of a single repacked heap allocation
+const MAX_ALLOCATION_SIZE: usize = u32::MAX as usize;
+
+/// Copies the given views into as few fresh heap allocations as possible,
+/// and rewrites the handles in place.
+///
+/// After heavy slicing, many small views may each pin a distinct (possibly large)
+/// parent allocation. Repacking them releases those parents (if no other views
+/// reference them) and leaves all views sharing one (or a few) tightly sized allocations.
+///
+/// Inlined views and views of static data are not touched, as they do not
+/// reference any heap allocation.
+///
+/// # Examples
+///
+/// ```
+/// # use byteview::ByteView;
+/// let a = ByteView::from("helloworld_thisisaverylongstring");
+/// let b = ByteView::from("helloworld_thisisanotherverylongstring");
+///
+/// let mut views = [a.slice(1..), b.slice(1..)];
+/// assert_eq!(2, a.ref_count());
+///
+/// byteview::repack(&mut views);
+/// assert_eq!(1, a.ref_count());
+/// assert_eq!(1, b.ref_count());
+///
+/// assert_eq!(b"elloworld_thisisaverylongstring", &*views[0]);
+/// assert_eq!(2, views[0].ref_count());
+/// ```
+pub fn repack(views: &mut [ByteView]) {
+    let mut start = 0;
+
+    while start < views.len() {
+        let mut end = start;
+        let mut total_len = 0;
+
+        // NOTE: Gather as many views as fit into a single allocation
+        for view in views.iter().skip(start) {
+            let len = if view.heap_region().is_some() {
+                view.len()
+            } else {
+                0
+            };
+
+            if end > start && total_len + len > MAX_ALLOCATION_SIZE {
+                break;
+            }
+
+            total_len += len;
+            end += 1;
+        }
+
+        // SAFETY: start < end <= views.len()
+        #[allow(clippy::indexing_slicing)]
+        repack_chunk(&mut views[start..end], total_len);
+
+        start = end;
+    }
+}
+
+fn repack_chunk(views: &mut [ByteView], total_len: usize) {
+    if total_len == 0 {
+        return;
+    }
+
+    // NOTE: We can skip zeroing because we copy exactly `total_len` bytes into it
+    let mut buffer = ByteView::with_size_unchecked(total_len);
+
+    {
+        let mut mutator = Mutator(&mut buffer);
+        let mut offset = 0;
+
+        for view in views.iter().filter(|view| view.heap_region().is_some()) {
+            let len = view.len();
+
+            // SAFETY: The buffer was sized to fit all heap-allocated views
+            #[allow(clippy::indexing_slicing)]
+            mutator[offset..(offset + len)].copy_from_slice(view);
+
+            offset += len;
+        }
+
+        debug_assert_eq!(offset, total_len);
+    }
+
+    let mut offset = 0;
+
+    for view in views.iter_mut().filter(|view| view.heap_region().is_some()) {
+        let len = view.len();
+        *view = buffer.slice(offset..(offset + len));
+        offset += len;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::repack;
+    use crate::ByteView;
+
+    #[test]
+    fn repack_empty() {
+        let mut views: [ByteView; 0] = [];
+        repack(&mut views);
+    }
+
+    #[test]
+    fn repack_inline_untouched() {
+        let mut views = [ByteView::from("abc"), ByteView::from("def")];
+        repack(&mut views);
+
+        assert_eq!(b"abc", &*views[0]);
+        assert_eq!(b"def", &*views[1]);
+        assert_eq!(1, views[0].ref_count());
+    }
+
+    #[test]
+    fn repack_releases_parents() {
+        let parents = (0..10)
+            .map(|idx| ByteView::from(format!("{idx}").repeat(100)))
+            .collect::<Vec<_>>();
+
+        let mut views = parents
+            .iter()
+            .map(|parent| parent.slice(10..50))
+            .chain(std::iter::once(ByteView::from("short")))
+            .collect::<Vec<_>>();
+
+        for parent in &parents {
+            assert_eq!(2, parent.ref_count());
+        }
+
+        repack(&mut views);
+
+        for parent in &parents {
+            assert_eq!(1, parent.ref_count());
+        }
+
+        for (idx, view) in views.iter().take(10).enumerate() {
+            assert_eq!(format!("{idx}").repeat(40).as_bytes(), &**view);
+            assert_eq!(10, view.ref_count());
+        }
+
+        assert_eq!(b"short", &*views[10]);
+    }
+
+    #[test]
+    fn repack_outlives_parents() {
+        let parent = ByteView::from("helloworld_thisisaverylongstring".repeat(2));
+        let mut views = [parent.slice(1..30), parent.slice(2..40)];
+        drop(parent);
+
+        repack(&mut views);
+
+        let [a, b] = views;
+        drop(a);
+        assert_eq!(b"lloworld_thisisaverylongstringhellowor", &*b);
+        assert_eq!(1, b.ref_count());
+    }
+}
@@ -0,0 +1,191 @@
+// Copyright (c) 2024-present, fjall-rs
+// This source code is licensed under both the Apache 2.0 and MIT License
+// (found in the LICENSE-* files in the repository)
+
+use crate::ByteView;
+use std::ops::Range;
+
+/// Appends byte records into a growing buffer
+///
+/// Once sealed, the buffer is frozen into a [`Segment`],
+/// which hands out zero-copy slices of each record.
+///
+/// # Examples
+///
+/// ```
+/// # use byteview::ArenaWriter;
+/// let mut writer = ArenaWriter::new();
+/// writer.push(b"helloworld_thisisaverylongstring");
+/// writer.push(b"abc");
+///
+/// let segment = writer.seal();
+/// assert_eq!(2, segment.len());
+/// assert_eq!(b"abc", &*segment.get(1).unwrap());
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ArenaWriter {
+    buffer: Vec<u8>,
+    records: Vec<Range<usize>>,
+}
+
+impl ArenaWriter {
+    /// Creates a new, empty writer.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            buffer: Vec::new(),
+            records: Vec::new(),
+        }
+    }
+
+    /// Creates a new, empty writer whose buffer can hold at least `capacity` bytes without reallocating.
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            buffer: Vec::with_capacity(capacity),
+            records: Vec::new(),
+        }
+    }
+
+    /// Appends a record, returning its index.
+    pub fn push(&mut self, record: &[u8]) -> usize {
+        let start = self.buffer.len();
+        self.buffer.extend_from_slice(record);
+        self.records.push(start..self.buffer.len());
+        self.records.len() - 1
+    }
+
+    /// Returns the amount of records.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Returns `true` if no records were written.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Returns the amount of bytes written.
+    #[must_use]
+    pub fn byte_len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Freezes the buffer into a [`Segment`].
+    ///
+    /// The segment takes over the buffer's heap allocation, so no bytes are copied.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the buffer's length does not fit in a u32 (4 GiB).
+    #[must_use]
+    pub fn seal(self) -> Segment {
+        Segment {
+            buffer: ByteView::from_vec(self.buffer),
+            records: self.records,
+        }
+    }
+}
+
+/// A sealed, immutable buffer of records
+///
+/// Created using [`ArenaWriter::seal`].
+#[derive(Clone, Debug)]
+pub struct Segment {
+    buffer: ByteView,
+    records: Vec<Range<usize>>,
+}
+
+impl Segment {
+    /// Returns the amount of records.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Returns `true` if the segment has no records.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Returns the whole buffer, containing all records back-to-back.
+    #[must_use]
+    pub const fn as_view(&self) -> &ByteView {
+        &self.buffer
+    }
+
+    /// Returns the record at the given index.
+    ///
+    /// The returned slice references the segment's buffer, without copying.
+    #[must_use]
+    pub fn get(&self, idx: usize) -> Option<ByteView> {
+        self.records
+            .get(idx)
+            .map(|range| self.buffer.slice(range.clone()))
+    }
+
+    /// Returns an iterator over all records.
+    #[must_use]
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = ByteView> + '_ {
+        self.records
+            .iter()
+            .map(|range| self.buffer.slice(range.clone()))
+    }
+
+    /// Converts the segment into a list of its records.
+    #[must_use]
+    pub fn into_views(self) -> Vec<ByteView> {
+        self.iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ArenaWriter;
+    use crate::ByteView;
+
+    #[test]
+    fn segment_records() {
+        let mut writer = ArenaWriter::with_capacity(100);
+        assert!(writer.is_empty());
+
+        assert_eq!(0, writer.push(b"helloworld_thisisaverylongstring"));
+        assert_eq!(1, writer.push(b""));
+        assert_eq!(2, writer.push(b"abc"));
+        assert_eq!(3, writer.push(b"helloworld_thisisanotherlongstring"));
+        assert_eq!(4, writer.len());
+        assert_eq!(69, writer.byte_len());
+
+        let segment = writer.seal();
+        assert_eq!(4, segment.len());
+        assert_eq!(69, segment.as_view().len());
+        assert!(segment.get(4).is_none());
+
+        let first = segment.get(0).unwrap();
+        assert_eq!(b"helloworld_thisisaverylongstring", &*first);
+        assert_eq!(segment.as_view().as_ptr(), first.as_ptr());
+        assert_eq!(b"", &*segment.get(1).unwrap());
+
+        let views = segment.into_views();
+        assert_eq!(
+            vec![
+                ByteView::from("helloworld_thisisaverylongstring"),
+                ByteView::from(""),
+                ByteView::from("abc"),
+                ByteView::from("helloworld_thisisanotherlongstring"),
+            ],
+            views,
+        );
+        assert_eq!(3, views[0].ref_count());
+    }
+
+    #[test]
+    fn segment_empty() {
+        let segment = ArenaWriter::new().seal();
+        assert!(segment.is_empty());
+        assert_eq!(0, segment.iter().count());
+    }
+}
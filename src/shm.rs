@@ -0,0 +1,349 @@
+// Copyright (c) 2024-present, fjall-rs
+// This source code is licensed under both the Apache 2.0 and MIT License
+// (found in the LICENSE-* files in the repository)
+
+use crate::{byteview::INLINE_SIZE, ByteSource, ByteView};
+use std::{
+    ffi::CString,
+    fs::File,
+    io,
+    os::fd::{AsRawFd, FromRawFd},
+    ptr::NonNull,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+/// Header at the start of a shared memory segment
+#[repr(C)]
+struct SegmentHeader {
+    /// Amount of processes that currently have the segment mapped
+    attachments: AtomicU64,
+
+    /// Length of the data region following the header
+    len: u64,
+}
+
+/// Process-local mapping of a shared memory segment
+///
+/// All views of the segment inside a process share one mapping,
+/// so the view ref count is process-local, while the segment header
+/// tracks how many processes are attached.
+struct SharedSegment {
+    mapping: NonNull<u8>,
+    mapping_len: usize,
+    len: usize,
+    name: CString,
+}
+
+// SAFETY: The mapping is only read from, and the attachment count is atomic
+unsafe impl Send for SharedSegment {}
+
+impl ByteSource for SharedSegment {
+    fn as_bytes(&self) -> &[u8] {
+        // SAFETY: The data region follows the header, and stays mapped until we are dropped
+        unsafe {
+            std::slice::from_raw_parts(
+                self.mapping
+                    .as_ptr()
+                    .add(std::mem::size_of::<SegmentHeader>()),
+                self.len,
+            )
+        }
+    }
+}
+
+impl Drop for SharedSegment {
+    /// Detaches from the shared memory segment, unlinking it if this was the last attached process.
+    fn drop(&mut self) {
+        // SAFETY: The mapping starts with the segment header, and no views reference it anymore
+        unsafe {
+            let segment = self.mapping.cast::<SegmentHeader>().as_ref();
+
+            if segment.attachments.fetch_sub(1, Ordering::AcqRel) == 1 {
+                libc::shm_unlink(self.name.as_ptr());
+            }
+
+            libc::munmap(self.mapping.as_ptr().cast(), self.mapping_len);
+        }
+    }
+}
+
+fn to_c_name(name: &str) -> io::Result<CString> {
+    CString::new(name).map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "segment name contains a nul byte",
+        )
+    })
+}
+
+fn map(file: &File, mapping_len: usize) -> io::Result<NonNull<u8>> {
+    // SAFETY: We map a fresh region, so no existing memory is affected
+    let ptr = unsafe {
+        libc::mmap(
+            std::ptr::null_mut(),
+            mapping_len,
+            libc::PROT_READ | libc::PROT_WRITE,
+            libc::MAP_SHARED,
+            file.as_raw_fd(),
+            0,
+        )
+    };
+
+    if ptr == libc::MAP_FAILED {
+        return Err(io::Error::last_os_error());
+    }
+
+    // NOTE: We did not request a fixed address, so the mapping is never null
+    NonNull::new(ptr.cast::<u8>()).ok_or_else(|| io::Error::other("mmap returned null"))
+}
+
+impl ByteView {
+    /// Creates a named shared memory segment, and copies the given slice into it.
+    ///
+    /// Other processes can map the same segment without copying using [`ByteView::open_shared`].
+    ///
+    /// The segment is unlinked once the last view in any process is dropped, so the
+    /// creating process needs to keep its view alive until the other side has opened the segment.
+    ///
+    /// Names follow the `shm_open` conventions, e.g. `/my-segment`.
+    ///
+    /// Views backed by shared memory cannot be mutated using [`ByteView::get_mut`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the segment could not be created (e.g. because it already exists),
+    /// or if the slice is short enough to be inlined, as inlined slices are never shared.
+    pub fn new_shared(name: &str, slice: &[u8]) -> io::Result<Self> {
+        let Ok(len) = u32::try_from(slice.len()) else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "byte slice too long",
+            ));
+        };
+
+        if slice.len() <= INLINE_SIZE {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "byte slice is short enough to be inlined",
+            ));
+        }
+
+        let c_name = to_c_name(name)?;
+
+        // SAFETY: The name is a valid C string
+        let fd = unsafe {
+            libc::shm_open(
+                c_name.as_ptr(),
+                libc::O_CREAT | libc::O_EXCL | libc::O_RDWR,
+                0o600,
+            )
+        };
+
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        // SAFETY: We just opened the file descriptor, so we own it
+        let file = unsafe { File::from_raw_fd(fd) };
+
+        let mapping_len = std::mem::size_of::<SegmentHeader>() + slice.len();
+
+        let mapping = match file
+            .set_len(mapping_len as u64)
+            .and_then(|()| map(&file, mapping_len))
+        {
+            Ok(mapping) => mapping,
+            Err(e) => {
+                // SAFETY: The name is a valid C string
+                unsafe {
+                    libc::shm_unlink(c_name.as_ptr());
+                }
+                return Err(e);
+            }
+        };
+
+        // SAFETY: The mapping is large enough to hold the header and the slice,
+        // and page-aligned, so it is aligned to the header
+        unsafe {
+            mapping
+                .cast::<SegmentHeader>()
+                .as_ptr()
+                .write(SegmentHeader {
+                    attachments: AtomicU64::new(1),
+                    len: u64::from(len),
+                });
+
+            std::ptr::copy_nonoverlapping(
+                slice.as_ptr(),
+                mapping.as_ptr().add(std::mem::size_of::<SegmentHeader>()),
+                slice.len(),
+            );
+        }
+
+        Ok(Self::from_source(SharedSegment {
+            mapping,
+            mapping_len,
+            len: slice.len(),
+            name: c_name,
+        }))
+    }
+
+    /// Opens a named shared memory segment created by [`ByteView::new_shared`],
+    /// possibly in another process.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the segment does not exist (anymore), or is malformed.
+    pub fn open_shared(name: &str) -> io::Result<Self> {
+        let c_name = to_c_name(name)?;
+
+        // SAFETY: The name is a valid C string
+        let fd = unsafe { libc::shm_open(c_name.as_ptr(), libc::O_RDWR, 0) };
+
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        // SAFETY: We just opened the file descriptor, so we own it
+        let file = unsafe { File::from_raw_fd(fd) };
+
+        let Ok(mapping_len) = usize::try_from(file.metadata()?.len()) else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "segment too large",
+            ));
+        };
+
+        if mapping_len < std::mem::size_of::<SegmentHeader>() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "segment too short",
+            ));
+        }
+
+        let mapping = map(&file, mapping_len)?;
+
+        let unmap = || {
+            // SAFETY: We just mapped the region, and did not hand out any pointers into it
+            unsafe {
+                libc::munmap(mapping.as_ptr().cast(), mapping_len);
+            }
+        };
+
+        // SAFETY: The mapping is large enough to hold the header, and page-aligned
+        let segment = unsafe { mapping.cast::<SegmentHeader>().as_ref() };
+
+        let len = usize::try_from(segment.len).ok().filter(|len| {
+            *len > INLINE_SIZE
+                && u32::try_from(*len).is_ok()
+                && std::mem::size_of::<SegmentHeader>() + len <= mapping_len
+        });
+
+        let Some(len) = len else {
+            unmap();
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "segment has invalid length",
+            ));
+        };
+
+        // NOTE: If no process is attached anymore, the segment is being torn down
+        if segment
+            .attachments
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |n| {
+                (n > 0).then(|| n + 1)
+            })
+            .is_err()
+        {
+            unmap();
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                "segment is not attached to any process",
+            ));
+        }
+
+        Ok(Self::from_source(SharedSegment {
+            mapping,
+            mapping_len,
+            len,
+            name: c_name,
+        }))
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(miri))]
+mod tests {
+    use crate::ByteView;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn segment_name() -> String {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+        format!(
+            "/byteview-test-{}-{}",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed),
+        )
+    }
+
+    #[test]
+    fn shm_roundtrip() -> std::io::Result<()> {
+        let name = segment_name();
+
+        let a = ByteView::new_shared(&name, b"helloworld_thisisaverylongstring")?;
+
+        // NOTE: Simulates another process
+        let mut b = ByteView::open_shared(&name)?;
+        assert_eq!(a, b);
+        assert_eq!(1, a.ref_count());
+        assert_eq!(1, b.ref_count());
+        assert!(b.get_mut().is_none());
+
+        let copy = b.slice(5..);
+        assert_eq!(b"world_thisisaverylongstring", &*copy);
+        assert_eq!(2, b.ref_count());
+
+        drop(a);
+        drop(b);
+        assert_eq!(b"world_thisisaverylongstring", &*copy);
+
+        drop(copy);
+        assert_eq!(
+            std::io::ErrorKind::NotFound,
+            ByteView::open_shared(&name).unwrap_err().kind(),
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn shm_already_exists() -> std::io::Result<()> {
+        let name = segment_name();
+
+        let _a = ByteView::new_shared(&name, b"helloworld_thisisaverylongstring")?;
+
+        assert_eq!(
+            std::io::ErrorKind::AlreadyExists,
+            ByteView::new_shared(&name, b"helloworld_thisisaverylongstring")
+                .unwrap_err()
+                .kind(),
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn shm_inline() {
+        let name = segment_name();
+
+        assert_eq!(
+            std::io::ErrorKind::InvalidInput,
+            ByteView::new_shared(&name, b"abc").unwrap_err().kind(),
+        );
+        assert_eq!(
+            std::io::ErrorKind::NotFound,
+            ByteView::open_shared(&name).unwrap_err().kind(),
+        );
+    }
+}
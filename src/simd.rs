@@ -0,0 +1,195 @@
+// Copyright (c) 2024-present, fjall-rs
+// This source code is licensed under both the Apache 2.0 and MIT License
+// (found in the LICENSE-* files in the repository)
+
+//! Vectorized comparison of long byte slices
+//!
+//! Uses AVX2 (if detected at runtime) or SSE2 on `x86_64`, and NEON on `aarch64`.
+//! Other targets fall back to the standard library's comparison.
+
+use std::cmp::Ordering;
+
+/// Slices shorter than this are compared by the standard library
+const MIN_LEN: usize = 32;
+
+/// Returns `true` if both slices contain the same bytes.
+pub fn eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && mismatch(a, b).is_none()
+}
+
+/// Compares both slices lexicographically.
+pub fn cmp(a: &[u8], b: &[u8]) -> Ordering {
+    mismatch(a, b).map_or_else(|| a.len().cmp(&b.len()), |idx| a.get(idx).cmp(&b.get(idx)))
+}
+
+/// Returns the index of the first byte that differs between both slices,
+/// or `None` if the shorter slice is a prefix of the longer one.
+fn mismatch(a: &[u8], b: &[u8]) -> Option<usize> {
+    let len = a.len().min(b.len());
+
+    if len < MIN_LEN {
+        return mismatch_scalar(a, b, 0);
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    {
+        if std::arch::is_x86_feature_detected!("avx2") {
+            // SAFETY: We just checked that AVX2 is available
+            return unsafe { x86_64::mismatch_avx2(a, b) };
+        }
+
+        // SAFETY: SSE2 is always available on x86_64
+        unsafe { x86_64::mismatch_sse2(a, b, 0) }
+    }
+
+    #[cfg(target_arch = "aarch64")]
+    {
+        // SAFETY: NEON is always available on aarch64
+        unsafe { aarch64::mismatch_neon(a, b) }
+    }
+
+    #[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+    mismatch_scalar(a, b, 0)
+}
+
+/// Returns the index of the first differing byte, starting at `start`.
+fn mismatch_scalar(a: &[u8], b: &[u8], start: usize) -> Option<usize> {
+    let a = a.get(start..).unwrap_or_default();
+    let b = b.get(start..).unwrap_or_default();
+
+    a.iter()
+        .zip(b)
+        .position(|(x, y)| x != y)
+        .map(|idx| start + idx)
+}
+
+#[cfg(target_arch = "x86_64")]
+mod x86_64 {
+    use super::mismatch_scalar;
+    use std::arch::x86_64::{
+        __m128i, __m256i, _mm256_cmpeq_epi8, _mm256_loadu_si256, _mm256_movemask_epi8,
+        _mm_cmpeq_epi8, _mm_loadu_si128, _mm_movemask_epi8,
+    };
+
+    /// # Safety
+    ///
+    /// The CPU needs to support AVX2.
+    // NOTE: Unaligned loads do not need aligned pointers
+    #[allow(clippy::cast_ptr_alignment)]
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn mismatch_avx2(a: &[u8], b: &[u8]) -> Option<usize> {
+        let len = a.len().min(b.len());
+        let mut idx = 0;
+
+        while idx + 32 <= len {
+            // NOTE: Both slices have at least idx + 32 bytes
+            let x = _mm256_loadu_si256(a.as_ptr().add(idx).cast::<__m256i>());
+            let y = _mm256_loadu_si256(b.as_ptr().add(idx).cast::<__m256i>());
+
+            // NOTE: Every bit of the mask is set if the bytes at its position are equal
+            #[allow(clippy::cast_sign_loss)]
+            let mask = _mm256_movemask_epi8(_mm256_cmpeq_epi8(x, y)) as u32;
+
+            if mask != u32::MAX {
+                return Some(idx + (!mask).trailing_zeros() as usize);
+            }
+
+            idx += 32;
+        }
+
+        mismatch_sse2(a, b, idx)
+    }
+
+    /// # Safety
+    ///
+    /// The CPU needs to support SSE2 (which every `x86_64` CPU does).
+    #[allow(clippy::cast_ptr_alignment)]
+    #[target_feature(enable = "sse2")]
+    pub unsafe fn mismatch_sse2(a: &[u8], b: &[u8], start: usize) -> Option<usize> {
+        let len = a.len().min(b.len());
+        let mut idx = start;
+
+        while idx + 16 <= len {
+            // NOTE: Both slices have at least idx + 16 bytes
+            let x = _mm_loadu_si128(a.as_ptr().add(idx).cast::<__m128i>());
+            let y = _mm_loadu_si128(b.as_ptr().add(idx).cast::<__m128i>());
+
+            // NOTE: The lowest 16 bits of the mask are set if the bytes at their position are equal
+            #[allow(clippy::cast_sign_loss)]
+            let mask = _mm_movemask_epi8(_mm_cmpeq_epi8(x, y)) as u32;
+
+            if mask != 0xFFFF {
+                return Some(idx + (!mask).trailing_zeros() as usize);
+            }
+
+            idx += 16;
+        }
+
+        mismatch_scalar(a, b, idx)
+    }
+}
+
+#[cfg(target_arch = "aarch64")]
+mod aarch64 {
+    use super::mismatch_scalar;
+    use std::arch::aarch64::{vceqq_u8, vld1q_u8, vminvq_u8};
+
+    /// # Safety
+    ///
+    /// The CPU needs to support NEON (which every `aarch64` CPU does).
+    #[target_feature(enable = "neon")]
+    pub unsafe fn mismatch_neon(a: &[u8], b: &[u8]) -> Option<usize> {
+        let len = a.len().min(b.len());
+        let mut idx = 0;
+
+        while idx + 16 <= len {
+            // NOTE: Both slices have at least idx + 16 bytes
+            let x = vld1q_u8(a.as_ptr().add(idx));
+            let y = vld1q_u8(b.as_ptr().add(idx));
+
+            // NOTE: Every lane is 0xFF if the bytes at its position are equal
+            if vminvq_u8(vceqq_u8(x, y)) != u8::MAX {
+                return mismatch_scalar(a, b, idx);
+            }
+
+            idx += 16;
+        }
+
+        mismatch_scalar(a, b, idx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{cmp, eq};
+
+    #[test]
+    fn simd_eq_cmp() {
+        let base = (0..=250u8).cycle().take(600).collect::<Vec<_>>();
+
+        for len in [0, 1, 15, 16, 17, 31, 32, 33, 63, 64, 100, 257, 500] {
+            let a = base.get(..len).unwrap();
+            assert!(eq(a, a));
+            assert_eq!(a.cmp(a), cmp(a, a));
+
+            // NOTE: Change every position, so both the vector loops and the tails are covered
+            for idx in 0..len {
+                for delta in [1, 0xFF] {
+                    let mut b = a.to_vec();
+                    let byte = b.get_mut(idx).unwrap();
+                    *byte = byte.wrapping_add(delta);
+
+                    assert!(!eq(a, &b));
+                    assert_eq!(a.cmp(&b), cmp(a, &b));
+                    assert_eq!(b.as_slice().cmp(a), cmp(&b, a));
+                }
+            }
+
+            // NOTE: Prefixes compare by length
+            let b = base.get(..(len + 7)).unwrap();
+            assert!(!eq(a, b));
+            assert_eq!(a.cmp(b), cmp(a, b));
+            assert_eq!(b.cmp(a), cmp(b, a));
+        }
+    }
+}
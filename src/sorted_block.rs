@@ -0,0 +1,404 @@
+// Copyright (c) 2024-present, fjall-rs
+// This source code is licensed under both the Apache 2.0 and MIT License
+// (found in the LICENSE-* files in the repository)
+
+use crate::ByteView;
+use std::{
+    cmp::Ordering,
+    io::{Error, ErrorKind},
+    ops::{Bound, RangeBounds},
+};
+
+/// Size of the prefix stored in every index entry
+const PREFIX_SIZE: usize = 4;
+
+/// Size of a single index entry (prefix, offset and length)
+const INDEX_ENTRY_SIZE: usize = PREFIX_SIZE + 2 * std::mem::size_of::<u32>();
+
+/// Size of the key count trailer
+const COUNT_SIZE: usize = std::mem::size_of::<u32>();
+
+fn read_u32(buf: &[u8], pos: usize) -> Option<u32> {
+    let bytes = buf.get(pos..(pos + 4))?;
+    bytes.try_into().ok().map(u32::from_le_bytes)
+}
+
+/// Returns the first bytes of the key (zero-padded), as a big-endian integer,
+/// so comparing two prefixes gives the same order as comparing the keys,
+/// unless the prefixes are equal.
+fn prefix_of(key: &[u8]) -> u32 {
+    let mut prefix = [0; PREFIX_SIZE];
+
+    for (dst, src) in prefix.iter_mut().zip(key) {
+        *dst = *src;
+    }
+
+    u32::from_be_bytes(prefix)
+}
+
+/// An immutable, sorted set of keys, serialized into a single buffer
+///
+/// The buffer contains all keys back-to-back, followed by a fixed-size index entry per key
+/// (containing the key's first 4 bytes, its offset and length), followed by the amount of keys.
+///
+/// Lookups binary search the index, comparing the stored prefixes first,
+/// so most comparisons do not need to touch the keys themselves.
+///
+/// # Examples
+///
+/// ```
+/// # use byteview::{ByteView, SortedBlock};
+/// let block = SortedBlock::new(["apple", "banana", "cherry", "date"]);
+///
+/// assert_eq!(Some(ByteView::from("cherry")), block.get(b"cherry"));
+/// assert!(block.get(b"coconut").is_none());
+///
+/// let keys = block.range("b".."d").collect::<Vec<_>>();
+/// assert_eq!(vec![ByteView::from("banana"), ByteView::from("cherry")], keys);
+///
+/// // The block can be stored, and loaded again without copying
+/// let block = SortedBlock::from_bytes(block.as_view().clone())?;
+/// assert_eq!(4, block.len());
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Clone, Debug)]
+pub struct SortedBlock {
+    buffer: ByteView,
+    len: usize,
+    index_start: usize,
+}
+
+impl SortedBlock {
+    /// Builds a block from the given keys, which need to be sorted.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the keys are not sorted, or the block does not fit in a u32 (4 GiB).
+    #[must_use]
+    pub fn new<K: AsRef<[u8]>>(keys: impl IntoIterator<Item = K>) -> Self {
+        let mut data = Vec::new();
+        let mut index = Vec::new();
+        let mut last: Option<std::ops::Range<usize>> = None;
+
+        for key in keys {
+            let key = key.as_ref();
+
+            assert!(
+                last.and_then(|range| data.get(range))
+                    .map_or(true, |last: &[u8]| last <= key),
+                "keys must be sorted",
+            );
+
+            let start = data.len();
+            data.extend_from_slice(key);
+
+            let (Ok(offset), Ok(len)) = (u32::try_from(start), u32::try_from(key.len())) else {
+                panic!("sorted block too long");
+            };
+
+            index.extend_from_slice(&prefix_of(key).to_be_bytes());
+            index.extend_from_slice(&offset.to_le_bytes());
+            index.extend_from_slice(&len.to_le_bytes());
+
+            last = Some(start..data.len());
+        }
+
+        let len = index.len() / INDEX_ENTRY_SIZE;
+        let index_start = data.len();
+
+        let Ok(count) = u32::try_from(len) else {
+            panic!("sorted block too long");
+        };
+
+        data.extend_from_slice(&index);
+        data.extend_from_slice(&count.to_le_bytes());
+
+        Self {
+            buffer: ByteView::from_vec(data),
+            len,
+            index_start,
+        }
+    }
+
+    /// Loads a block from its serialized form (see [`SortedBlock::as_view`]), without copying.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind [`ErrorKind::InvalidData`] if the block is malformed.
+    /// The keys are not checked to be sorted.
+    pub fn from_bytes(buffer: ByteView) -> std::io::Result<Self> {
+        let invalid = || Error::new(ErrorKind::InvalidData, "malformed sorted block");
+
+        let count_pos = buffer.len().checked_sub(COUNT_SIZE).ok_or_else(invalid)?;
+        let len = read_u32(&buffer, count_pos).ok_or_else(invalid)? as usize;
+
+        let index_start = len
+            .checked_mul(INDEX_ENTRY_SIZE)
+            .and_then(|index_len| count_pos.checked_sub(index_len))
+            .ok_or_else(invalid)?;
+
+        let block = Self {
+            buffer,
+            len,
+            index_start,
+        };
+
+        for idx in 0..len {
+            let (prefix, start, end) = block.entry(idx).ok_or_else(invalid)?;
+
+            let key = block
+                .buffer
+                .get(start..end)
+                .filter(|_| end <= index_start)
+                .ok_or_else(invalid)?;
+
+            if prefix != prefix_of(key) {
+                return Err(invalid());
+            }
+        }
+
+        Ok(block)
+    }
+
+    /// Returns the serialized block.
+    #[must_use]
+    pub const fn as_view(&self) -> &ByteView {
+        &self.buffer
+    }
+
+    /// Returns the amount of keys.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the block has no keys.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the prefix and location of the key at the given index.
+    fn entry(&self, idx: usize) -> Option<(u32, usize, usize)> {
+        if idx >= self.len {
+            return None;
+        }
+
+        let pos = self.index_start + idx * INDEX_ENTRY_SIZE;
+
+        let prefix = self
+            .buffer
+            .get(pos..(pos + PREFIX_SIZE))
+            .and_then(|bytes| bytes.try_into().ok())
+            .map(u32::from_be_bytes)?;
+
+        let start = read_u32(&self.buffer, pos + PREFIX_SIZE)? as usize;
+        let len = read_u32(&self.buffer, pos + PREFIX_SIZE + 4)? as usize;
+
+        Some((prefix, start, start.checked_add(len)?))
+    }
+
+    /// Returns the key at the given index.
+    ///
+    /// The returned slice references the block's buffer, without copying.
+    #[must_use]
+    pub fn key(&self, idx: usize) -> Option<ByteView> {
+        let (_, start, end) = self.entry(idx)?;
+        Some(self.buffer.slice(start..end))
+    }
+
+    /// Compares the key at the given index to `needle`, whose prefix is `needle_prefix`.
+    fn cmp_at(&self, idx: usize, needle: &[u8], needle_prefix: u32) -> Ordering {
+        let Some((prefix, start, end)) = self.entry(idx) else {
+            return Ordering::Greater;
+        };
+
+        // NOTE: Only compare the keys themselves if the prefixes are equal
+        prefix
+            .cmp(&needle_prefix)
+            .then_with(|| self.buffer.get(start..end).unwrap_or_default().cmp(needle))
+    }
+
+    /// Returns the index of the first key that is not less than `needle`
+    /// (or, if `inclusive` is `false`, the first key that is greater than `needle`).
+    fn partition_point(&self, needle: &[u8], inclusive: bool) -> usize {
+        let needle_prefix = prefix_of(needle);
+
+        let (mut lo, mut hi) = (0, self.len);
+
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+
+            let before = match self.cmp_at(mid, needle, needle_prefix) {
+                Ordering::Less => true,
+                Ordering::Equal => !inclusive,
+                Ordering::Greater => false,
+            };
+
+            if before {
+                lo = mid + 1;
+            } else {
+                hi = mid;
+            }
+        }
+
+        lo
+    }
+
+    /// Binary searches the block for the given key.
+    ///
+    /// Like [`slice::binary_search`], returns `Ok` with the index of the key if it was found,
+    /// otherwise `Err` with the index where it could be inserted.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` with the insertion index if the key does not exist.
+    pub fn binary_search(&self, key: &[u8]) -> Result<usize, usize> {
+        let idx = self.partition_point(key, true);
+
+        match self.entry(idx) {
+            Some((_, start, end)) if self.buffer.get(start..end) == Some(key) => Ok(idx),
+            _ => Err(idx),
+        }
+    }
+
+    /// Returns the stored key equal to `key`, if it exists.
+    ///
+    /// The returned slice references the block's buffer, without copying.
+    #[must_use]
+    pub fn get(&self, key: &[u8]) -> Option<ByteView> {
+        self.binary_search(key).ok().and_then(|idx| self.key(idx))
+    }
+
+    /// Returns an iterator over all keys in the given range.
+    #[must_use]
+    pub fn range<K: AsRef<[u8]>, R: RangeBounds<K>>(
+        &self,
+        range: R,
+    ) -> impl DoubleEndedIterator<Item = ByteView> + ExactSizeIterator + '_ {
+        let start = match range.start_bound() {
+            Bound::Included(key) => self.partition_point(key.as_ref(), true),
+            Bound::Excluded(key) => self.partition_point(key.as_ref(), false),
+            Bound::Unbounded => 0,
+        };
+
+        let end = match range.end_bound() {
+            Bound::Included(key) => self.partition_point(key.as_ref(), false),
+            Bound::Excluded(key) => self.partition_point(key.as_ref(), true),
+            Bound::Unbounded => self.len,
+        };
+
+        (start..end.max(start)).map(|idx| self.key(idx).unwrap_or_default())
+    }
+
+    /// Returns an iterator over all keys.
+    #[must_use]
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = ByteView> + ExactSizeIterator + '_ {
+        (0..self.len).map(|idx| self.key(idx).unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SortedBlock;
+    use crate::ByteView;
+    use std::io::ErrorKind;
+
+    #[test]
+    fn sorted_block_lookup() -> std::io::Result<()> {
+        let mut keys = vec![
+            String::new(),
+            "a".to_owned(),
+            "ab".to_owned(),
+            "ab\0".to_owned(),
+            "ab\0\0\0".to_owned(),
+            "abc".to_owned(),
+        ];
+        keys.extend((0..100).map(|i| format!("helloworld_thisisaverylongstring_{i:03}")));
+        keys.push("z".repeat(100));
+
+        let block = SortedBlock::new(&keys);
+        assert_eq!(keys.len(), block.len());
+
+        let block = SortedBlock::from_bytes(block.as_view().clone())?;
+
+        for (idx, key) in keys.iter().enumerate() {
+            assert_eq!(Ok(idx), block.binary_search(key.as_bytes()));
+            assert_eq!(Some(ByteView::from(&**key)), block.get(key.as_bytes()));
+        }
+
+        assert_eq!(Ok(0), block.binary_search(b""));
+        assert_eq!(Err(6), block.binary_search(b"abd"));
+        assert_eq!(Err(4), block.binary_search(b"ab\0\0"));
+        assert_eq!(
+            Err(keys.len()),
+            block.binary_search("z".repeat(101).as_bytes()),
+        );
+
+        let long = block.get(b"helloworld_thisisaverylongstring_042").unwrap();
+        assert!(block.as_view().as_ptr_range().contains(&long.as_ptr()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn sorted_block_range() {
+        let keys = (0..100).map(|i| format!("key_{i:03}")).collect::<Vec<_>>();
+        let block = SortedBlock::new(&keys);
+
+        let range = block.range("key_010".."key_020").collect::<Vec<_>>();
+        assert_eq!(10, range.len());
+        assert_eq!(b"key_010", &*range[0]);
+        assert_eq!(b"key_019", &*range[9]);
+
+        assert_eq!(11, block.range("key_010"..="key_020").count());
+        assert_eq!(
+            Some(ByteView::from("key_099")),
+            block.range("key_09"..).next_back(),
+        );
+        assert_eq!(5, block.range(.."key_005").count());
+        assert_eq!(0, block.range("key_020".."key_010").count());
+        assert_eq!(100, block.iter().count());
+    }
+
+    #[test]
+    fn sorted_block_malformed() {
+        let block = SortedBlock::new(["abc", "def", "helloworld_thisisaverylongstring"]);
+        let bytes = block.as_view();
+
+        // NOTE: Keys (38 bytes), then 3 index entries (12 bytes each), then the count
+        let corruptions: [(usize, u8); 4] = [(74, 4), (74, 255), (66, 255), (38, b'x')];
+
+        for (pos, byte) in corruptions {
+            let mut corrupted = bytes.to_vec();
+            corrupted[pos] = byte;
+
+            assert_eq!(
+                ErrorKind::InvalidData,
+                SortedBlock::from_bytes(ByteView::from(corrupted))
+                    .unwrap_err()
+                    .kind(),
+            );
+        }
+
+        for len in 0..4 {
+            assert_eq!(
+                ErrorKind::InvalidData,
+                SortedBlock::from_bytes(bytes.slice(..len))
+                    .unwrap_err()
+                    .kind(),
+            );
+        }
+
+        let empty = SortedBlock::new(Vec::<&[u8]>::new());
+        assert!(SortedBlock::from_bytes(empty.as_view().clone())
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    #[should_panic = "keys must be sorted"]
+    fn sorted_block_unsorted() {
+        let _ = SortedBlock::new(["b", "a"]);
+    }
+}
@@ -0,0 +1,431 @@
+// Copyright (c) 2024-present, fjall-rs
+// This source code is licensed under both the Apache 2.0 and MIT License
+// (found in the LICENSE-* files in the repository)
+
+use crate::{
+    byteview::{HeapAllocationHeader, HeapKind, INLINE_SIZE},
+    refcount::RefCount,
+    ByteView,
+};
+use std::{mem::ManuallyDrop, ptr::NonNull};
+
+/// Custom backing storage for [`ByteView`]s
+///
+/// Allows views to reference memory that is not allocated by the global allocator
+/// (e.g. pinned buffers, arena pages, or foreign allocations).
+///
+/// The source is moved into a shared handle, and dropped once the last view
+/// referencing it is dropped, so its [`Drop`] implementation can run custom release logic.
+///
+/// Views backed by a source cannot be mutated using [`ByteView::get_mut`].
+pub trait ByteSource: Send + 'static {
+    /// Returns the bytes of the backing storage.
+    ///
+    /// This is called once, after the source has been moved to its final location.
+    /// The returned bytes need to stay valid and unchanged until the source is dropped.
+    fn as_bytes(&self) -> &[u8];
+}
+
+/// Type-erased part of a [`SourceHandle`]
+#[repr(C)]
+pub struct SourceHeader {
+    pub(crate) header: HeapAllocationHeader,
+
+    /// Start of the source's bytes
+    pub(crate) data: *const u8,
+
+    /// Size of the handle, plus any spare capacity the source keeps allocated,
+    /// not including the source's bytes
+    pub(crate) overhead: usize,
+
+    /// Drops the source, once no strong references are left
+    drop_source: Option<unsafe fn(NonNull<u8>)>,
+
+    /// Frees the handle, once no weak references are left
+    release: unsafe fn(NonNull<u8>),
+}
+
+impl SourceHeader {
+    /// Creates a header holding a single reference.
+    ///
+    /// The length and start of the data region need to be filled in
+    /// once the handle has been moved to its final location.
+    pub(crate) fn new(
+        kind: HeapKind,
+        overhead: usize,
+        drop_source: Option<unsafe fn(NonNull<u8>)>,
+        release: unsafe fn(NonNull<u8>),
+    ) -> Self {
+        Self {
+            header: HeapAllocationHeader {
+                ref_count: RefCount::new(1),
+                weak_count: RefCount::new(1),
+                len: 0,
+                kind,
+                #[cfg(feature = "checksum")]
+                checksum: None,
+                #[cfg(feature = "hash-cache")]
+                hash: std::sync::atomic::AtomicU64::new(0),
+            },
+            data: std::ptr::null(),
+            overhead,
+            drop_source,
+            release,
+        }
+    }
+}
+
+#[repr(C)]
+struct SourceHandle<S> {
+    base: SourceHeader,
+    source: S,
+}
+
+/// Adapts any owner of bytes into a [`ByteSource`]
+struct Owner<T>(T);
+
+impl<T: AsRef<[u8]> + Send + 'static> ByteSource for Owner<T> {
+    fn as_bytes(&self) -> &[u8] {
+        self.0.as_ref()
+    }
+}
+
+unsafe fn drop_handle_source<S>(heap: NonNull<u8>) {
+    let handle = heap.cast::<SourceHandle<S>>().as_ptr();
+    std::ptr::drop_in_place(std::ptr::addr_of_mut!((*handle).source));
+}
+
+unsafe fn release_handle<S>(heap: NonNull<u8>) {
+    // NOTE: The source was already dropped, so only free the handle
+    drop(Box::from_raw(
+        heap.cast::<ManuallyDrop<SourceHandle<S>>>().as_ptr(),
+    ));
+}
+
+/// Drops the source, but keeps the handle alive for any weak references.
+///
+/// # Safety
+///
+/// `heap` needs to point to a [`SourceHeader`] that has no strong references left.
+pub unsafe fn drop_source(heap: NonNull<u8>) {
+    let drop_source = heap.cast::<SourceHeader>().as_ref().drop_source;
+
+    if let Some(drop_source) = drop_source {
+        drop_source(heap);
+    }
+}
+
+/// Frees the handle.
+///
+/// # Safety
+///
+/// `heap` needs to point to a [`SourceHeader`] that has no references left,
+/// and whose source was already dropped using [`drop_source`].
+pub unsafe fn release(heap: NonNull<u8>) {
+    let release = heap.cast::<SourceHeader>().as_ref().release;
+
+    release(heap);
+}
+
+impl ByteView {
+    /// Creates a slice that references the bytes of the given source, without copying.
+    ///
+    /// If the bytes are short enough to be inlined, they are copied instead,
+    /// and the source is dropped immediately.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use byteview::{ByteSource, ByteView};
+    /// struct Page(Box<[u8]>);
+    ///
+    /// impl ByteSource for Page {
+    ///     fn as_bytes(&self) -> &[u8] {
+    ///         &self.0
+    ///     }
+    /// }
+    ///
+    /// let slice = ByteView::from_source(Page(vec![1; 4_096].into()));
+    /// assert_eq!(4_096, slice.len());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length does not fit in a u32 (4 GiB).
+    #[must_use]
+    pub fn from_source<S: ByteSource>(source: S) -> Self {
+        Self::adopt(source, HeapKind::Source, 0, |source| {
+            let bytes = source.as_bytes();
+            (bytes.as_ptr(), bytes.len())
+        })
+    }
+
+    /// Creates a slice that takes ownership of the vector's allocation, without copying.
+    ///
+    /// The vector's spare capacity stays allocated until the handle is dropped,
+    /// so it is counted in [`ByteView::heap_size`].
+    ///
+    /// The slice can still be mutated using [`ByteView::get_mut`].
+    pub(crate) fn from_vec(vec: Vec<u8>) -> Self {
+        let spare_capacity = vec.capacity() - vec.len();
+
+        Self::adopt(vec, HeapKind::Vec, spare_capacity, |vec| {
+            (vec.as_mut_ptr().cast_const(), vec.len())
+        })
+    }
+
+    /// Takes the adopted vector back out of its handle, if this slice is its only reference.
+    ///
+    /// The vector is cut down to the bytes of the slice, without reallocating.
+    pub(crate) fn try_into_adopted_vec(self) -> Result<Vec<u8>, Self> {
+        let Some(heap_region) = self.heap_region() else {
+            return Err(self);
+        };
+
+        if heap_region.kind != HeapKind::Vec || !heap_region.is_unique() {
+            return Err(self);
+        }
+
+        let (Some(heap), Some(offset)) = (self.heap_ptr(), self.offset_in_parent()) else {
+            return Err(self);
+        };
+
+        let len = self.len();
+        let view = ManuallyDrop::new(self);
+
+        // SAFETY: The handle was created by `from_vec`, and we hold its only reference,
+        // so we can move the vector out, and free the handle without dropping it
+        let mut vec = unsafe {
+            let handle = heap.cast::<SourceHandle<Vec<u8>>>().as_ptr();
+            let vec = std::ptr::read(std::ptr::addr_of!((*handle).source));
+
+            crate::hooks::on_dealloc(view.heap_size());
+            release(heap);
+
+            vec
+        };
+
+        // NOTE: The offset is relative to the start of the vector
+        vec.truncate(offset + len);
+        vec.drain(..offset);
+
+        Ok(vec)
+    }
+
+    /// Moves the given owner into a shared handle, and creates a slice of the bytes
+    /// returned by `locate` (which needs to stay valid until the owner is dropped).
+    fn adopt<S: Send + 'static>(
+        owner: S,
+        kind: HeapKind,
+        spare_capacity: usize,
+        locate: impl FnOnce(&mut S) -> (*const u8, usize),
+    ) -> Self {
+        // NOTE: The handle needs to be leaked before locating the bytes,
+        // because the owner may store its bytes inline (e.g. an array),
+        // and pointers derived before leaking would be invalidated by moving the box
+        let handle = Box::into_raw(Box::new(SourceHandle {
+            base: SourceHeader::new(
+                kind,
+                std::mem::size_of::<SourceHandle<S>>() + spare_capacity,
+                Some(drop_handle_source::<S>),
+                release_handle::<S>,
+            ),
+            source: owner,
+        }));
+
+        // SAFETY: The handle was just allocated, and is not referenced anywhere else
+        let (data, len) = locate(unsafe { &mut (*handle).source });
+
+        if len <= INLINE_SIZE {
+            // SAFETY: The owner is still alive, so the bytes are valid,
+            // and the handle was created by `Box::into_raw` above
+            return unsafe {
+                let view = Self::new(std::slice::from_raw_parts(data, len));
+                drop(Box::from_raw(handle));
+                view
+            };
+        }
+
+        let Ok(len) = u32::try_from(len) else {
+            // SAFETY: The handle was created by `Box::into_raw` above
+            drop(unsafe { Box::from_raw(handle) });
+            panic!("byte slice too long");
+        };
+
+        // SAFETY: `Box::into_raw` never returns null
+        let handle = unsafe { NonNull::new_unchecked(handle) };
+
+        // SAFETY: We own the handle, and it does not move anymore
+        unsafe {
+            let base = std::ptr::addr_of_mut!((*handle.as_ptr()).base);
+            (*base).header.len = len;
+            (*base).data = data;
+
+            let view = Self::from_heap_parts(handle.cast::<u8>(), data, len);
+            crate::hooks::on_alloc(view.heap_size());
+            view
+        }
+    }
+
+    /// Creates a slice that borrows the bytes of the given owner, without copying.
+    ///
+    /// The owner is kept alive until the last view referencing it is dropped,
+    /// which allows serving data straight from e.g. memory-mapped files.
+    ///
+    /// If the bytes are short enough to be inlined, they are copied instead,
+    /// and the owner is dropped immediately.
+    ///
+    /// This is a shorthand for [`ByteView::from_source`] for types that
+    /// already expose their bytes using [`AsRef`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use byteview::ByteView;
+    /// let data = vec![1; 4_096];
+    /// let ptr = data.as_ptr();
+    ///
+    /// let slice = ByteView::from_owner(data);
+    /// assert_eq!(4_096, slice.len());
+    /// assert_eq!(ptr, slice.as_ptr());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length does not fit in a u32 (4 GiB).
+    #[must_use]
+    pub fn from_owner<T: AsRef<[u8]> + Send + 'static>(owner: T) -> Self {
+        Self::from_source(Owner(owner))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ByteSource;
+    use crate::ByteView;
+    use std::sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    };
+
+    struct Tracked {
+        data: Vec<u8>,
+        dropped: Arc<AtomicBool>,
+    }
+
+    impl ByteSource for Tracked {
+        fn as_bytes(&self) -> &[u8] {
+            &self.data
+        }
+    }
+
+    impl Drop for Tracked {
+        fn drop(&mut self) {
+            self.dropped.store(true, Ordering::Release);
+        }
+    }
+
+    #[test]
+    fn source_release() {
+        let dropped = Arc::new(AtomicBool::new(false));
+
+        let slice = ByteView::from_source(Tracked {
+            data: b"helloworld_thisisaverylongstring".to_vec(),
+            dropped: dropped.clone(),
+        });
+        assert_eq!(b"helloworld_thisisaverylongstring", &*slice);
+        assert_eq!(1, slice.ref_count());
+
+        let mut copy = slice.slice(1..);
+        assert_eq!(2, slice.ref_count());
+        assert!(copy.get_mut().is_none());
+
+        drop(slice);
+        assert!(!dropped.load(Ordering::Acquire));
+        assert_eq!(b"elloworld_thisisaverylongstring", &*copy);
+
+        drop(copy);
+        assert!(dropped.load(Ordering::Acquire));
+    }
+
+    #[test]
+    fn owner_release() {
+        let owner: Arc<[u8]> = Arc::from(&b"helloworld_thisisaverylongstring"[..]);
+
+        let slice = ByteView::from_owner(owner.clone());
+        assert_eq!(b"helloworld_thisisaverylongstring", &*slice);
+        assert_eq!(owner.as_ptr(), slice.as_ptr());
+        assert_eq!(2, Arc::strong_count(&owner));
+
+        let copy = slice.slice(5..);
+        drop(slice);
+        assert_eq!(2, Arc::strong_count(&owner));
+        assert_eq!(b"world_thisisaverylongstring", &*copy);
+
+        drop(copy);
+        assert_eq!(1, Arc::strong_count(&owner));
+    }
+
+    #[test]
+    fn vec_adopt() {
+        let vec = b"helloworld_thisisaverylongstring".to_vec();
+        let ptr = vec.as_ptr();
+
+        let mut slice = ByteView::from(vec);
+        assert_eq!(b"helloworld_thisisaverylongstring", &*slice);
+        assert_eq!(ptr, slice.as_ptr());
+
+        {
+            let mut mutator = slice.get_mut().unwrap();
+            mutator[0] = b'j';
+        }
+        assert_eq!(b"jelloworld_thisisaverylongstring", &*slice);
+        assert_eq!(ByteView::from("jelloworld_thisisaverylongstring"), slice);
+
+        let copy = slice.clone();
+        assert!(slice.get_mut().is_none());
+        drop(copy);
+
+        let boxed: Box<[u8]> = slice.to_vec().into_boxed_slice();
+        let ptr = boxed.as_ptr();
+        let slice = ByteView::from(boxed);
+        assert_eq!(ptr, slice.as_ptr());
+
+        // NOTE: The boxed slice can be taken back without copying
+        let boxed = Box::<[u8]>::from(slice);
+        assert_eq!(ptr, boxed.as_ptr());
+        assert_eq!(b"jelloworld_thisisaverylongstring", &*boxed);
+
+        let string = String::from("helloworld_thisisaverylongstring");
+        let ptr = string.as_ptr();
+        assert_eq!(ptr, ByteView::from(string).as_ptr());
+    }
+
+    #[test]
+    fn owner_in_struct() {
+        let slice = ByteView::from_owner([7u8; 64]);
+        assert_eq!([7u8; 64], *slice);
+        assert!(!slice.is_inline());
+
+        let copy = slice.slice(32..);
+        drop(slice);
+        assert_eq!([7u8; 32], *copy);
+
+        let slice = ByteView::from_owner([7u8; 4]);
+        assert_eq!([7u8; 4], *slice);
+        assert!(slice.is_inline());
+    }
+
+    #[test]
+    fn source_inline() {
+        let dropped = Arc::new(AtomicBool::new(false));
+
+        let slice = ByteView::from_source(Tracked {
+            data: b"abc".to_vec(),
+            dropped: dropped.clone(),
+        });
+        assert_eq!(b"abc", &*slice);
+        assert!(slice.is_inline());
+        assert!(dropped.load(Ordering::Acquire));
+    }
+}
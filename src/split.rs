@@ -0,0 +1,358 @@
+// Copyright (c) 2024-present, fjall-rs
+// This source code is licensed under both the Apache 2.0 and MIT License
+// (found in the LICENSE-* files in the repository)
+
+use crate::ByteView;
+use std::iter::FusedIterator;
+
+/// Iterator over the parts of a [`ByteView`] that are separated by a byte,
+/// see [`ByteView::split`]
+#[derive(Clone, Debug)]
+pub struct Split<'a> {
+    view: &'a ByteView,
+    delim: u8,
+    start: usize,
+    end: usize,
+    finished: bool,
+}
+
+impl Split<'_> {
+    fn rest(&self) -> &[u8] {
+        self.view.get(self.start..self.end).unwrap_or_default()
+    }
+
+    fn finish(&mut self) -> Option<ByteView> {
+        if self.finished {
+            return None;
+        }
+
+        self.finished = true;
+        Some(self.view.slice(self.start..self.end))
+    }
+}
+
+impl Iterator for Split<'_> {
+    type Item = ByteView;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        if let Some(idx) = memchr::memchr(self.delim, self.rest()) {
+            let start = self.start;
+            self.start += idx + 1;
+            Some(self.view.slice(start..(start + idx)))
+        } else {
+            self.finish()
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.finished {
+            (0, Some(0))
+        } else {
+            (1, Some(self.end - self.start + 1))
+        }
+    }
+}
+
+impl DoubleEndedIterator for Split<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        if let Some(idx) = memchr::memrchr(self.delim, self.rest()) {
+            let end = self.end;
+            self.end = self.start + idx;
+            Some(self.view.slice((self.end + 1)..end))
+        } else {
+            self.finish()
+        }
+    }
+}
+
+impl FusedIterator for Split<'_> {}
+
+/// Iterator over the parts of a [`ByteView`] that are separated by a byte,
+/// starting from the end, see [`ByteView::rsplit`]
+#[derive(Clone, Debug)]
+pub struct RSplit<'a> {
+    inner: Split<'a>,
+}
+
+impl Iterator for RSplit<'_> {
+    type Item = ByteView;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for RSplit<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
+
+impl FusedIterator for RSplit<'_> {}
+
+/// Iterator over at most `n` parts of a [`ByteView`] that are separated by a byte,
+/// see [`ByteView::splitn`]
+#[derive(Clone, Debug)]
+pub struct SplitN<'a> {
+    inner: Split<'a>,
+    count: usize,
+}
+
+impl Iterator for SplitN<'_> {
+    type Item = ByteView;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.count {
+            0 => None,
+            1 => {
+                self.count = 0;
+                self.inner.finish()
+            }
+            _ => {
+                self.count -= 1;
+                self.inner.next()
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.inner.size_hint();
+        let count = self.count;
+
+        (
+            lower.min(count),
+            Some(upper.map_or(count, |upper| upper.min(count))),
+        )
+    }
+}
+
+impl FusedIterator for SplitN<'_> {}
+
+/// Iterator over the parts of a [`ByteView`] that are terminated by a byte,
+/// see [`ByteView::split_terminator`]
+#[derive(Clone, Debug)]
+pub struct SplitTerminator<'a> {
+    inner: Split<'a>,
+}
+
+impl Iterator for SplitTerminator<'_> {
+    type Item = ByteView;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let part = self.inner.next()?;
+
+        // NOTE: The part after the last terminator is skipped if it is empty
+        if self.inner.finished && part.is_empty() {
+            None
+        } else {
+            Some(part)
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (_, upper) = self.inner.size_hint();
+        (0, upper)
+    }
+}
+
+impl FusedIterator for SplitTerminator<'_> {}
+
+impl ByteView {
+    /// Returns an iterator over the parts of the slice that are separated by `delim`.
+    ///
+    /// Like [`ByteView::slice`], the parts reference the same heap allocation
+    /// (or are inlined, if short enough), without copying.
+    ///
+    /// Like `<[u8]>::split`, a slice with `n` delimiters is split into `n + 1` parts,
+    /// so an empty slice yields a single empty part.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use byteview::ByteView;
+    /// let row = ByteView::from("1000,helloworld_thisisaverylongstring,,user");
+    /// let fields = row.split(b',').collect::<Vec<_>>();
+    ///
+    /// assert_eq!(4, fields.len());
+    /// assert_eq!(b"helloworld_thisisaverylongstring", &*fields[1]);
+    /// assert!(fields[2].is_empty());
+    /// assert!(row.contains_view(&fields[1]));
+    /// ```
+    #[must_use]
+    pub fn split(&self, delim: u8) -> Split<'_> {
+        Split {
+            view: self,
+            delim,
+            start: 0,
+            end: self.len(),
+            finished: false,
+        }
+    }
+
+    /// Returns an iterator over the parts of the slice that are separated by `delim`,
+    /// starting from the end of the slice.
+    ///
+    /// The parts are yielded in the reverse order of [`ByteView::split`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use byteview::ByteView;
+    /// let path = ByteView::from("/usr/local/bin");
+    /// let mut parts = path.rsplit(b'/');
+    ///
+    /// assert_eq!(Some(ByteView::from("bin")), parts.next());
+    /// assert_eq!(Some(ByteView::from("local")), parts.next());
+    /// ```
+    #[must_use]
+    pub fn rsplit(&self, delim: u8) -> RSplit<'_> {
+        RSplit {
+            inner: self.split(delim),
+        }
+    }
+
+    /// Returns an iterator over at most `n` parts of the slice that are separated by `delim`.
+    ///
+    /// The last part contains the remainder of the slice, including any delimiters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use byteview::ByteView;
+    /// let header = ByteView::from("Content-Type: text/plain: utf-8");
+    /// let parts = header.splitn(2, b':').collect::<Vec<_>>();
+    ///
+    /// assert_eq!(b"Content-Type", &*parts[0]);
+    /// assert_eq!(b" text/plain: utf-8", &*parts[1]);
+    /// ```
+    #[must_use]
+    pub fn splitn(&self, n: usize, delim: u8) -> SplitN<'_> {
+        SplitN {
+            inner: self.split(delim),
+            count: n,
+        }
+    }
+
+    /// Returns an iterator over the parts of the slice that are terminated by `delim`.
+    ///
+    /// Equivalent to [`ByteView::split`], except that the trailing part is skipped if it is empty,
+    /// like `str::split_terminator`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use byteview::ByteView;
+    /// let lines = ByteView::from("a\nb\n");
+    /// let lines = lines.split_terminator(b'\n').collect::<Vec<_>>();
+    ///
+    /// assert_eq!(vec![ByteView::from("a"), ByteView::from("b")], lines);
+    /// ```
+    #[must_use]
+    pub fn split_terminator(&self, delim: u8) -> SplitTerminator<'_> {
+        SplitTerminator {
+            inner: self.split(delim),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ByteView;
+
+    fn collect(parts: impl Iterator<Item = ByteView>) -> Vec<String> {
+        parts
+            .map(|part| String::from_utf8(part.to_vec()).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn split() {
+        let split = |s: &str| collect(ByteView::from(s).split(b','));
+
+        assert_eq!([""], &*split(""));
+        assert_eq!(["", ""], &*split(","));
+        assert_eq!(["a", "b", "", "c", ""], &*split("a,b,,c,"));
+        assert_eq!(["abc"], &*split("abc"));
+
+        let row =
+            ByteView::from("helloworld_thisisaverylongstring,helloworld_thisisaverylongstring");
+        let parts = row.split(b',').collect::<Vec<_>>();
+        assert_eq!(2, parts.len());
+        assert_eq!(3, row.ref_count());
+        assert_eq!(Some(33), parts[1].offset_in_parent());
+
+        let mut iter = row.split(b',');
+        assert_eq!(2, iter.by_ref().count());
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn split_double_ended() {
+        let slice = ByteView::from("a,b,,c,d");
+        let mut iter = slice.split(b',');
+
+        assert_eq!(Some(ByteView::from("a")), iter.next());
+        assert_eq!(Some(ByteView::from("d")), iter.next_back());
+        assert_eq!(Some(ByteView::from("b")), iter.next());
+        assert_eq!(Some(ByteView::from("c")), iter.next_back());
+        assert_eq!(Some(ByteView::from("")), iter.next_back());
+        assert_eq!(None, iter.next());
+        assert_eq!(None, iter.next_back());
+    }
+
+    #[test]
+    fn rsplit() {
+        let rsplit = |s: &str| collect(ByteView::from(s).rsplit(b','));
+
+        assert_eq!([""], &*rsplit(""));
+        assert_eq!(["", ""], &*rsplit(","));
+        assert_eq!(["", "c", "", "b", "a"], &*rsplit("a,b,,c,"));
+
+        for s in ["", ",", "a,b,,c,", ",,x,"] {
+            let forward = ByteView::from(s).split(b',').collect::<Vec<_>>();
+            let backward = ByteView::from(s).rsplit(b',').rev().collect::<Vec<_>>();
+            assert_eq!(forward, backward);
+        }
+    }
+
+    #[test]
+    fn splitn() {
+        let splitn = |s: &str, n| collect(ByteView::from(s).splitn(n, b','));
+
+        assert!(splitn("a,b,c", 0).is_empty());
+        assert_eq!(["a,b,c"], &*splitn("a,b,c", 1));
+        assert_eq!(["a", "b,c"], &*splitn("a,b,c", 2));
+        assert_eq!(["a", "b", "c"], &*splitn("a,b,c", 3));
+        assert_eq!(["a", "b", "c"], &*splitn("a,b,c", 10));
+        assert_eq!([""], &*splitn("", 2));
+        assert_eq!(["", ""], &*splitn(",", 2));
+
+        let slice = ByteView::from("a,b,c");
+        assert_eq!((1, Some(2)), slice.splitn(2, b',').size_hint());
+    }
+
+    #[test]
+    fn split_terminator() {
+        let split_terminator = |s: &str| collect(ByteView::from(s).split_terminator(b','));
+
+        for s in ["", ",", "a", "a,", "a,b", "a,b,", ",a,,b,,", "a,,"] {
+            let expected = s
+                .split_terminator(',')
+                .map(ToOwned::to_owned)
+                .collect::<Vec<_>>();
+            assert_eq!(expected, split_terminator(s), "{s:?}");
+        }
+    }
+}
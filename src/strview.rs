@@ -8,7 +8,7 @@ use std::{ops::Deref, sync::Arc};
 /// An immutable, UTF-8–encoded string slice
 ///
 /// Will be inlined (no pointer dereference or heap allocation)
-/// if it is 20 characters or shorter (on a 64-bit system).
+/// if it is 23 characters or shorter (on a 64-bit system).
 ///
 /// A single heap allocation will be shared between multiple strings.
 /// Even substrings of that heap allocation can be cloned without additional heap allocation.
@@ -18,8 +18,10 @@ use std::{ops::Deref, sync::Arc};
 #[derive(Default, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct StrView(ByteView);
 
+#[cfg(not(feature = "local"))]
 #[allow(clippy::non_send_fields_in_send_ty)]
 unsafe impl Send for StrView {}
+#[cfg(not(feature = "local"))]
 #[allow(clippy::non_send_fields_in_send_ty)]
 unsafe impl Sync for StrView {}
 
@@ -38,10 +40,19 @@ impl Deref for StrView {
     }
 }
 
+impl<I: std::slice::SliceIndex<str>> std::ops::Index<I> for StrView {
+    type Output = I::Output;
+
+    /// Indexes into the string, with the same semantics (and panics) as indexing a `str`.
+    fn index(&self, index: I) -> &Self::Output {
+        std::ops::Index::index(&**self, index)
+    }
+}
+
 impl StrView {
     /// Creates a new string from an existing byte string.
     ///
-    /// Will heap-allocate the string if it has at least length 13.
+    /// Will heap-allocate the string if it is longer than 23 bytes (on a 64-bit system).
     ///
     /// # Panics
     ///
@@ -51,6 +62,33 @@ impl StrView {
         Self(ByteView::new(s.as_bytes()))
     }
 
+    /// An empty string.
+    pub const EMPTY: Self = Self(ByteView::EMPTY);
+
+    /// Creates a new inlined string in a `const` context.
+    ///
+    /// See [`ByteView::new_inline`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the string is too long to be inlined (23 bytes on a 64-bit system).
+    #[must_use]
+    pub const fn new_inline(s: &str) -> Self {
+        Self(ByteView::new_inline(s.as_bytes()))
+    }
+
+    /// Creates a new string that references static data.
+    ///
+    /// See [`ByteView::from_static`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length does not fit in a u32 (4 GiB).
+    #[must_use]
+    pub const fn from_static(s: &'static str) -> Self {
+        Self(ByteView::from_static(s.as_bytes()))
+    }
+
     #[doc(hidden)]
     #[must_use]
     #[allow(clippy::missing_const_for_fn)]
@@ -87,6 +125,66 @@ impl StrView {
     pub fn starts_with(&self, needle: &str) -> bool {
         self.0.starts_with(needle.as_bytes())
     }
+
+    /// Returns `true` if `needle` is a suffix of the string or equal to the string.
+    #[must_use]
+    pub fn ends_with(&self, needle: &str) -> bool {
+        self.0.ends_with(needle.as_bytes())
+    }
+
+    /// Returns the length (in bytes) of the longest prefix both strings share.
+    ///
+    /// The length always lies on a char boundary, so it can be used to slice either string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use byteview::StrView;
+    /// let a = StrView::new("café");
+    /// let b = StrView::new("cafè");
+    ///
+    /// // NOTE: Both chars start with the same byte
+    /// assert_eq!(3, a.longest_common_prefix(&b));
+    /// ```
+    #[must_use]
+    pub fn longest_common_prefix(&self, other: &Self) -> usize {
+        let mut len = self.0.longest_common_prefix(&other.0);
+
+        // NOTE: Both strings share the bytes up to `len`,
+        // so a char boundary in one string is a char boundary in the other
+        while !self.is_char_boundary(len) {
+            len -= 1;
+        }
+
+        len
+    }
+
+    /// Returns a cheap, collision-prone hash of the string's length and first 4 bytes.
+    ///
+    /// See [`ByteView::fast_hash`].
+    #[must_use]
+    pub fn fast_hash(&self) -> u64 {
+        self.0.fast_hash()
+    }
+
+    /// Consumes the string, returning a reference to it that lives for the rest of the program.
+    ///
+    /// See [`ByteView::leak`].
+    #[must_use]
+    pub fn leak(self) -> &'static str {
+        // SAFETY: A StrView is always valid UTF-8
+        unsafe { std::str::from_utf8_unchecked(self.0.leak()) }
+    }
+
+    pub(crate) const fn as_view(&self) -> &ByteView {
+        &self.0
+    }
+}
+
+impl std::hash::Hash for StrView {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.deref().hash(state);
+    }
 }
 
 impl std::borrow::Borrow<str> for StrView {
@@ -109,7 +207,22 @@ impl From<&str> for StrView {
 
 impl From<String> for StrView {
     fn from(value: String) -> Self {
-        Self::new(&value)
+        Self(ByteView::from(value))
+    }
+}
+
+impl From<&String> for StrView {
+    fn from(value: &String) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<std::borrow::Cow<'_, str>> for StrView {
+    fn from(value: std::borrow::Cow<'_, str>) -> Self {
+        match value {
+            std::borrow::Cow::Borrowed(s) => Self::new(s),
+            std::borrow::Cow::Owned(s) => Self::from(s),
+        }
     }
 }
 
@@ -119,6 +232,12 @@ impl From<Arc<str>> for StrView {
     }
 }
 
+impl From<std::rc::Rc<str>> for StrView {
+    fn from(value: std::rc::Rc<str>) -> Self {
+        Self::new(&value)
+    }
+}
+
 impl TryFrom<ByteView> for StrView {
     type Error = std::str::Utf8Error;
 
@@ -140,14 +259,13 @@ mod serde {
     use serde::de::{self, Visitor};
     use serde::{Deserialize, Deserializer, Serialize, Serializer};
     use std::fmt;
-    use std::ops::Deref;
 
     impl Serialize for StrView {
         fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
         where
             S: Serializer,
         {
-            serializer.serialize_str(self.deref())
+            serializer.serialize_str(self)
         }
     }
 
@@ -171,9 +289,205 @@ mod serde {
                 {
                     Ok(StrView::new(v))
                 }
+
+                fn visit_string<E>(self, v: String) -> Result<StrView, E>
+                where
+                    E: de::Error,
+                {
+                    if u32::try_from(v.len()).is_err() {
+                        return Err(E::invalid_length(v.len(), &self));
+                    }
+
+                    // NOTE: Takes ownership of the buffer, without copying
+                    Ok(StrView::from(v))
+                }
+
+                fn visit_bytes<E>(self, v: &[u8]) -> Result<StrView, E>
+                where
+                    E: de::Error,
+                {
+                    std::str::from_utf8(v)
+                        .map(StrView::new)
+                        .map_err(|_| E::invalid_value(de::Unexpected::Bytes(v), &self))
+                }
+
+                fn visit_borrowed_str<E>(self, v: &'de str) -> Result<StrView, E>
+                where
+                    E: de::Error,
+                {
+                    // SAFETY: The slice contains the same bytes as the string
+                    crate::byteview::borrow_from_source(v.as_bytes()).map_or_else(
+                        || self.visit_str(v),
+                        |view| Ok(unsafe { StrView::from_raw(view) }),
+                    )
+                }
             }
 
-            deserializer.deserialize_bytes(StrViewVisitor)
+            deserializer.deserialize_str(StrViewVisitor)
+        }
+    }
+}
+
+#[cfg(feature = "heed")]
+mod heed {
+    use super::StrView;
+    use heed_traits::{BoxedError, BytesDecode, BytesEncode};
+    use std::borrow::Cow;
+
+    /// Encodes any string (so a [`StrView`] can be passed, too), without copying
+    impl<'a> BytesEncode<'a> for StrView {
+        type EItem = str;
+
+        fn bytes_encode(item: &'a str) -> Result<Cow<'a, [u8]>, BoxedError> {
+            Ok(Cow::Borrowed(item.as_bytes()))
+        }
+    }
+
+    /// Decodes into a [`StrView`], see [`crate::ByteView`]'s implementation
+    impl BytesDecode<'_> for StrView {
+        type DItem = Self;
+
+        fn bytes_decode(bytes: &[u8]) -> Result<Self, BoxedError> {
+            Ok(Self::new(std::str::from_utf8(bytes)?))
+        }
+    }
+}
+
+#[cfg(feature = "redb")]
+mod redb {
+    use super::StrView;
+    use redb::{Key, TypeName, Value};
+
+    /// Stored like a `&str`, see [`crate::ByteView`]'s implementation
+    impl Value for StrView {
+        type SelfType<'a> = Self;
+        type AsBytes<'a> = &'a str;
+
+        fn fixed_width() -> Option<usize> {
+            None
+        }
+
+        fn from_bytes<'a>(data: &'a [u8]) -> Self
+        where
+            Self: 'a,
+        {
+            Self::new(<&str>::from_bytes(data))
+        }
+
+        fn as_bytes<'a, 'b: 'a>(value: &'a Self) -> &'a str
+        where
+            Self: 'b,
+        {
+            value
+        }
+
+        fn type_name() -> TypeName {
+            <&str>::type_name()
+        }
+    }
+
+    impl Key for StrView {
+        fn compare(data1: &[u8], data2: &[u8]) -> std::cmp::Ordering {
+            <&str>::compare(data1, data2)
+        }
+    }
+}
+
+#[cfg(feature = "rkyv")]
+mod rkyv {
+    use super::StrView;
+    use rkyv::{
+        string::{ArchivedString, StringResolver},
+        Archive, Deserialize, Fallible, Serialize, SerializeUnsized,
+    };
+
+    /// Archived like a `String`
+    impl Archive for StrView {
+        type Archived = ArchivedString;
+        type Resolver = StringResolver;
+
+        unsafe fn resolve(&self, pos: usize, resolver: Self::Resolver, out: *mut Self::Archived) {
+            ArchivedString::resolve_from_str(self, pos, resolver, out);
+        }
+    }
+
+    impl<S: Fallible + ?Sized> Serialize<S> for StrView
+    where
+        str: SerializeUnsized<S>,
+    {
+        fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+            ArchivedString::serialize_from_str(self, serializer)
+        }
+    }
+
+    impl<D: Fallible + ?Sized> Deserialize<StrView, D> for ArchivedString {
+        fn deserialize(&self, _: &mut D) -> Result<StrView, D::Error> {
+            Ok(StrView::new(self))
+        }
+    }
+}
+
+#[cfg(feature = "equivalent")]
+mod equivalent {
+    use super::StrView;
+    use equivalent::Equivalent;
+    use std::hash::BuildHasher;
+
+    // NOTE: `str` is already covered by the blanket implementation, through `Borrow<str>`
+    impl Equivalent<StrView> for &str {
+        fn equivalent(&self, key: &StrView) -> bool {
+            *self == &**key
+        }
+    }
+
+    impl StrView {
+        /// Returns the hash that a map using the given hasher computes for a string
+        /// with the given contents, without constructing a string.
+        ///
+        /// See [`crate::ByteView::hash_borrowed`].
+        #[must_use]
+        pub fn hash_borrowed<S: BuildHasher>(hasher: &S, s: &str) -> u64 {
+            // NOTE: The string hashes exactly like its contents
+            hasher.hash_one(s)
+        }
+    }
+}
+
+#[cfg(feature = "zeroize")]
+mod zeroize {
+    use super::StrView;
+    use zeroize::{Zeroize, ZeroizeOnDrop};
+
+    /// Resets the string to an empty string, see [`crate::ByteView`]'s implementation.
+    impl Zeroize for StrView {
+        fn zeroize(&mut self) {
+            self.0.zeroize();
+        }
+    }
+
+    impl ZeroizeOnDrop for StrView {}
+}
+
+#[cfg(feature = "arbitrary")]
+mod arbitrary {
+    use super::StrView;
+    use crate::byteview::arbitrary::with_layout;
+    use arbitrary::{Arbitrary, Result, Unstructured};
+
+    impl<'a> Arbitrary<'a> for StrView {
+        fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+            let before = <&str>::arbitrary(u)?;
+            let s = <&str>::arbitrary(u)?;
+            let after = <&str>::arbitrary(u)?;
+
+            let view = with_layout(u, before.as_bytes(), s.as_bytes(), after.as_bytes())?;
+
+            // SAFETY: The slice contains the same bytes as the string
+            Ok(unsafe { Self::from_raw(view) })
+        }
+
+        fn size_hint(depth: usize) -> (usize, Option<usize>) {
+            arbitrary::size_hint::and(<&str>::size_hint(depth), (0, None))
         }
     }
 }
@@ -189,6 +503,84 @@ mod tests {
         assert!(a < b);
     }
 
+    #[test]
+    fn longest_common_prefix_char_boundary() {
+        let a = StrView::from("helloworld_thisisaverylongstring_é");
+        let b = StrView::from("helloworld_thisisaverylongstring_è");
+        assert_eq!(33, a.longest_common_prefix(&b));
+        assert_eq!(a.len(), a.longest_common_prefix(&a));
+
+        let a = StrView::from("日本");
+        let b = StrView::from("日月");
+        assert_eq!(3, a.longest_common_prefix(&b));
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore = "leaks intentionally")]
+    fn leak_str() {
+        let leaked: &'static str = StrView::from("helloworld_thisisaverylongstring").leak();
+        assert_eq!("helloworld_thisisaverylongstring", leaked);
+    }
+
+    #[test]
+    #[cfg(feature = "equivalent")]
+    fn equivalent_str() {
+        use std::hash::BuildHasher;
+
+        let mut map = hashbrown::HashMap::new();
+        map.insert(StrView::from("helloworld_thisisaverylongstring"), 1);
+
+        let needle = "helloworld_thisisaverylongstring";
+        assert_eq!(Some(&1), map.get(&needle));
+        assert_eq!(Some(&1), map.get(needle));
+        assert_eq!(
+            map.hasher().hash_one(needle),
+            StrView::hash_borrowed(map.hasher(), needle),
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_json_str() -> serde_json::Result<()> {
+        let s = StrView::from("helloworld_thisisaverylongstring");
+
+        let json = serde_json::to_string(&s)?;
+        assert_eq!("\"helloworld_thisisaverylongstring\"", json);
+        assert_eq!(s, serde_json::from_str::<StrView>(&json)?);
+
+        // NOTE: Escaped strings are unescaped into an owned buffer
+        assert_eq!(
+            StrView::from("hello \"world\" ✓"),
+            serde_json::from_str::<StrView>(r#""hello \"world\" \u2713""#)?,
+        );
+
+        assert!(serde_json::from_str::<StrView>("[97,98,99]").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_shared_str() -> Result<(), serde::de::value::Error> {
+        use crate::ByteView;
+        use serde::de::{
+            value::{BorrowedStrDeserializer, Error},
+            Deserialize,
+        };
+
+        let input = ByteView::from("helloworld_thisisaverylongstring");
+
+        let shared = input.deserialize_shared(|bytes| {
+            let s = std::str::from_utf8(bytes).unwrap();
+            StrView::deserialize(BorrowedStrDeserializer::<Error>::new(&s[2..]))
+        })?;
+
+        assert_eq!("lloworld_thisisaverylongstring", &*shared);
+        assert_eq!(2, input.ref_count());
+
+        Ok(())
+    }
+
     #[test]
     fn nostr() {
         let slice = StrView::from("");
@@ -353,6 +745,58 @@ mod tests {
         assert!(!a.starts_with("def"));
     }
 
+    #[test]
+    fn str_from_conversions() {
+        use std::{borrow::Cow, rc::Rc};
+
+        let s = "helloworld_thisisaverylongstring";
+        let expected = StrView::from(s);
+
+        assert_eq!(expected, StrView::from(&s.to_owned()));
+        assert_eq!(expected, StrView::from(Rc::<str>::from(s)));
+        assert_eq!(expected, StrView::from(Cow::Borrowed(s)));
+
+        let string = s.to_owned();
+        let ptr = string.as_ptr();
+        let view = StrView::from(Cow::<str>::Owned(string));
+        assert_eq!(expected, view);
+        assert_eq!(ptr, view.as_ptr());
+    }
+
+    #[test]
+    fn str_new_inline() {
+        const NAMES: [StrView; 2] = [StrView::EMPTY, StrView::new_inline("café")];
+
+        assert_eq!(StrView::from(""), NAMES[0]);
+        assert_eq!(StrView::from("café"), NAMES[1]);
+    }
+
+    #[test]
+    fn str_index() {
+        let a = StrView::from("helloworld_thisisaverylongstring_café");
+        assert_eq!("llow", &a[2..6]);
+        assert_eq!("café", &a[33..]);
+        assert_eq!("he", &a[..=1]);
+    }
+
+    #[test]
+    #[should_panic = "is not a char boundary"]
+    fn str_index_char_boundary() {
+        let a = StrView::from("café");
+        let _ = &a[..4];
+    }
+
+    #[test]
+    fn str_ends_with() {
+        let a = StrView::from("abcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdef");
+        assert!(a.ends_with("def"));
+        assert!(!a.ends_with("abc"));
+
+        let b = StrView::from("abc");
+        assert!(b.ends_with("bc"));
+        assert!(!b.ends_with("b"));
+    }
+
     #[test]
     fn tiny_str_cmp() {
         let a = StrView::from("abc");
@@ -0,0 +1,164 @@
+// Copyright (c) 2024-present, fjall-rs
+// This source code is licensed under both the Apache 2.0 and MIT License
+// (found in the LICENSE-* files in the repository)
+
+//! Generators for views of every interesting length and representation,
+//! so downstream crates can test their code against all shapes of [`ByteView`]
+//!
+//! ```
+//! # use byteview::testing::samples;
+//! for sample in samples() {
+//!     let expected = sample.view.to_vec();
+//!     assert_eq!(expected, &*sample.view);
+//! }
+//! ```
+
+use crate::ByteView;
+
+/// Maximum length of a slice that is inlined
+pub const INLINE_SIZE: usize = crate::byteview::INLINE_SIZE;
+
+/// Page size used to generate page-sized lengths
+pub const PAGE_SIZE: usize = 4_096;
+
+/// Representation of a generated view
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Shape {
+    /// Inlined into the struct, without heap allocation
+    Inline,
+
+    /// Heap-allocated, and the only reference to its allocation
+    Unique,
+
+    /// Heap-allocated, and the allocation is shared with another view
+    Shared,
+
+    /// Heap-allocated as part of a larger parent allocation, starting at a non-zero offset
+    Subslice,
+}
+
+/// A generated view
+pub struct Sample {
+    /// Representation of the view
+    pub shape: Shape,
+
+    /// The generated view
+    pub view: ByteView,
+
+    /// Keeps the allocation shared for the [`Shape::Shared`] and [`Shape::Subslice`] shapes
+    #[allow(dead_code)]
+    parent: Option<ByteView>,
+}
+
+impl std::fmt::Debug for Sample {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Sample")
+            .field("shape", &self.shape)
+            .field("len", &self.view.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Returns all lengths that hit the boundaries of the different representations.
+#[must_use]
+pub fn lengths() -> Vec<usize> {
+    vec![
+        0,
+        1,
+        4,
+        5,
+        INLINE_SIZE - 1,
+        INLINE_SIZE,
+        INLINE_SIZE + 1,
+        PAGE_SIZE - 1,
+        PAGE_SIZE,
+        PAGE_SIZE + 1,
+        2 * PAGE_SIZE,
+    ]
+}
+
+/// Returns deterministic, non-repeating-looking content of the given length.
+#[must_use]
+#[allow(clippy::cast_possible_truncation)]
+pub fn content(len: usize) -> Vec<u8> {
+    (0..len).map(|idx| (idx % 251) as u8).collect()
+}
+
+/// Generates views of the given content in every representation possible for its length.
+///
+/// Slices that are short enough to be inlined are always inlined,
+/// so they only produce [`Shape::Inline`].
+#[must_use]
+pub fn samples_of(content: &[u8]) -> Vec<Sample> {
+    if content.len() <= INLINE_SIZE {
+        return vec![Sample {
+            shape: Shape::Inline,
+            view: ByteView::new(content),
+            parent: None,
+        }];
+    }
+
+    let unique = ByteView::new(content);
+
+    let shared = ByteView::new(content);
+    let shared_parent = shared.clone();
+
+    let mut padded = Vec::with_capacity(content.len() + 2);
+    padded.push(0);
+    padded.extend_from_slice(content);
+    padded.push(0);
+
+    let subslice_parent = ByteView::from(padded);
+    let subslice = subslice_parent.slice(1..=content.len());
+
+    vec![
+        Sample {
+            shape: Shape::Unique,
+            view: unique,
+            parent: None,
+        },
+        Sample {
+            shape: Shape::Shared,
+            view: shared,
+            parent: Some(shared_parent),
+        },
+        Sample {
+            shape: Shape::Subslice,
+            view: subslice,
+            parent: Some(subslice_parent),
+        },
+    ]
+}
+
+/// Generates views of every interesting length (see [`lengths`]) in every representation.
+#[must_use]
+pub fn samples() -> Vec<Sample> {
+    lengths()
+        .into_iter()
+        .flat_map(|len| samples_of(&content(len)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{samples, Shape};
+
+    #[test]
+    fn testing_samples() {
+        let samples = samples();
+
+        for shape in [Shape::Inline, Shape::Unique, Shape::Shared, Shape::Subslice] {
+            assert!(samples.iter().any(|sample| sample.shape == shape));
+        }
+
+        for sample in &samples {
+            let expected = super::content(sample.view.len());
+            assert_eq!(expected, &*sample.view);
+
+            match sample.shape {
+                Shape::Inline | Shape::Unique => assert_eq!(1, sample.view.ref_count()),
+                Shape::Shared | Shape::Subslice => assert_eq!(2, sample.view.ref_count()),
+            }
+        }
+    }
+}
@@ -0,0 +1,127 @@
+// Copyright (c) 2024-present, fjall-rs
+// This source code is licensed under both the Apache 2.0 and MIT License
+// (found in the LICENSE-* files in the repository)
+
+use crate::ByteView;
+use std::{
+    mem::MaybeUninit,
+    ops::{Deref, DerefMut},
+};
+
+/// A fixed-length slice whose bytes may not be initialized yet
+///
+/// Created using [`ByteView::with_size_uninit`].
+///
+/// # Examples
+///
+/// ```
+/// # use byteview::ByteView;
+/// let mut slice = ByteView::with_size_uninit(32);
+///
+/// for (idx, byte) in slice.iter_mut().enumerate() {
+///     byte.write(idx as u8);
+/// }
+///
+/// // SAFETY: We initialized every byte
+/// let slice = unsafe { slice.assume_init() };
+/// assert_eq!(31, slice[31]);
+/// ```
+pub struct UninitByteView(ByteView);
+
+impl UninitByteView {
+    /// Converts into an initialized slice.
+    ///
+    /// # Safety
+    ///
+    /// Every byte of the slice needs to be initialized.
+    #[must_use]
+    pub unsafe fn assume_init(self) -> ByteView {
+        let mut view = self.0;
+        view.update_prefix();
+        view
+    }
+}
+
+impl Deref for UninitByteView {
+    type Target = [MaybeUninit<u8>];
+
+    fn deref(&self) -> &Self::Target {
+        let len = self.0.len();
+        let ptr = self.0.get_ptr();
+
+        // SAFETY: The slice has `len` (possibly uninitialized) bytes
+        unsafe { std::slice::from_raw_parts(ptr.cast::<MaybeUninit<u8>>(), len) }
+    }
+}
+
+impl DerefMut for UninitByteView {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        let len = self.0.len();
+        let ptr = self.0.get_mut_ptr();
+
+        // SAFETY: We uniquely own the slice, which has `len` (possibly uninitialized) bytes
+        unsafe { std::slice::from_raw_parts_mut(ptr.cast::<MaybeUninit<u8>>(), len) }
+    }
+}
+
+impl std::fmt::Debug for UninitByteView {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "UninitByteView {{ len: {} }}", self.0.len())
+    }
+}
+
+impl ByteView {
+    /// Creates a new fixed-length slice without initializing its bytes.
+    ///
+    /// This skips zeroing the heap allocation, which is useful if the bytes
+    /// are going to be overwritten anyway (e.g. by reading into them).
+    ///
+    /// Use [`UninitByteView::assume_init`] once all bytes are written.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length does not fit in a u32 (4 GiB).
+    #[must_use]
+    pub fn with_size_uninit(slice_len: usize) -> UninitByteView {
+        UninitByteView(Self::with_size_unchecked(slice_len))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ByteView;
+
+    #[test]
+    fn uninit_write() {
+        let mut slice = ByteView::with_size_uninit(32);
+        assert_eq!(32, slice.len());
+
+        for (byte, value) in slice.iter_mut().zip(b"helloworld_thisisaverylongstring") {
+            byte.write(*value);
+        }
+
+        let slice = unsafe { slice.assume_init() };
+        assert_eq!(b"helloworld_thisisaverylongstring", &*slice);
+        assert_eq!(ByteView::from("helloworld_thisisaverylongstring"), slice);
+        assert_eq!(1, slice.ref_count());
+    }
+
+    #[test]
+    fn uninit_inline() {
+        let mut slice = ByteView::with_size_uninit(5);
+        assert_eq!(5, slice.len());
+
+        for (byte, value) in slice.iter_mut().zip(b"hello") {
+            byte.write(*value);
+        }
+
+        let slice = unsafe { slice.assume_init() };
+        assert!(slice.is_inline());
+        assert_eq!(ByteView::from("hello"), slice);
+    }
+
+    #[test]
+    fn uninit_drop() {
+        drop(ByteView::with_size_uninit(100));
+    }
+}
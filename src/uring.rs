@@ -0,0 +1,299 @@
+// Copyright (c) 2024-present, fjall-rs
+// This source code is licensed under both the Apache 2.0 and MIT License
+// (found in the LICENSE-* files in the repository)
+
+use crate::ByteView;
+use io_uring::{opcode, types, IoUring};
+use std::{
+    fs::File,
+    io,
+    os::{fd::AsRawFd, unix::fs::FileExt},
+};
+
+/// User data tag of our read submissions
+const READ_USER_DATA: u64 = 0x6279_7465_7669_6577;
+
+/// In-flight read of [`ByteView::from_file_uring`]
+///
+/// Keeps the view alive until the kernel is done with it, even if the read fails.
+struct ReadAt<'a> {
+    ring: &'a mut IoUring,
+    file: &'a File,
+
+    /// Heap-allocated slice that is being read into
+    view: Option<ByteView>,
+
+    /// Pointer to the registered data region of `view`
+    ptr: *mut u8,
+
+    offset: u64,
+    filled: usize,
+    in_flight: bool,
+}
+
+impl ReadAt<'_> {
+    fn read(&mut self) -> io::Result<ByteView> {
+        let Some(len) = self.view.as_ref().map(ByteView::len) else {
+            unreachable!("read is only called once");
+        };
+
+        loop {
+            if !self.in_flight {
+                if self.filled == len {
+                    self.ring.submitter().unregister_buffers()?;
+
+                    let Some(mut view) = self.view.take() else {
+                        unreachable!("view is checked above");
+                    };
+                    view.update_prefix();
+
+                    return Ok(view);
+                }
+
+                let remaining = len - self.filled;
+
+                // NOTE: The view length fits into a u32
+                #[allow(clippy::cast_possible_truncation)]
+                let entry = opcode::ReadFixed::new(
+                    types::Fd(self.file.as_raw_fd()),
+                    // SAFETY: We never read past the data region
+                    unsafe { self.ptr.add(self.filled) },
+                    remaining as u32,
+                    0,
+                )
+                .offset(self.offset + self.filled as u64)
+                .build()
+                .user_data(READ_USER_DATA);
+
+                // SAFETY: The buffer stays alive until the read has completed,
+                // see our Drop implementation
+                unsafe {
+                    self.ring.submission().push(&entry).map_err(|_| {
+                        io::Error::new(io::ErrorKind::WouldBlock, "submission queue is full")
+                    })?;
+                }
+
+                self.in_flight = true;
+            }
+
+            self.ring.submit_and_wait(1)?;
+
+            // NOTE: Only consume a single completion, so we never discard completions
+            // that arrive after ours
+            let Some(entry) = self.ring.completion().next() else {
+                continue;
+            };
+
+            if entry.user_data() != READ_USER_DATA {
+                return Err(io::Error::other("unexpected completion on ring"));
+            }
+
+            self.in_flight = false;
+
+            match usize::try_from(entry.result()) {
+                Ok(0) => return Err(io::ErrorKind::UnexpectedEof.into()),
+                Ok(n) => self.filled += n,
+                Err(_) => return Err(io::Error::from_raw_os_error(-entry.result())),
+            }
+        }
+    }
+}
+
+impl Drop for ReadAt<'_> {
+    fn drop(&mut self) {
+        if self.view.is_none() {
+            return;
+        }
+
+        // IMPORTANT: The kernel may still write into the view,
+        // so we cannot free it before the read has completed
+        while self.in_flight {
+            if self.ring.submit_and_wait(1).is_err() {
+                // NOTE: We cannot know when the kernel is done with the buffer, so leak it
+                std::mem::forget(self.view.take());
+                return;
+            }
+
+            if self
+                .ring
+                .completion()
+                .any(|entry| entry.user_data() == READ_USER_DATA)
+            {
+                self.in_flight = false;
+            }
+        }
+
+        let _ = self.ring.submitter().unregister_buffers();
+    }
+}
+
+impl ByteView {
+    /// Creates a slice and populates it with `len` bytes read from
+    /// the given file, starting at `offset`, using `io_uring`.
+    ///
+    /// The slice's heap allocation is registered as a fixed buffer, so the kernel
+    /// reads directly into it.
+    ///
+    /// This blocks until the read has completed, like a regular file read,
+    /// so in async code, it should be run where blocking is acceptable,
+    /// e.g. using `tokio::task::spawn_blocking`.
+    ///
+    /// Slices that are short enough to be inlined are read using a regular `pread`.
+    ///
+    /// The ring must not have any registered buffers, nor other operations in flight
+    /// or completions that were not consumed yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an I/O error occurred, or the file is too short.
+    ///
+    /// If the ring has unconsumed completions, an error of kind [`io::ErrorKind::InvalidInput`] is returned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length does not fit in a u32 (4 GiB).
+    pub fn from_file_uring(
+        ring: &mut IoUring,
+        file: &File,
+        offset: u64,
+        len: usize,
+    ) -> io::Result<Self> {
+        // NOTE: We can use _unchecked to skip zeroing of the heap allocated slice
+        // because we fail if we cannot read exactly `len` bytes
+        let mut view = Self::with_size_unchecked(len);
+
+        if view.is_inline() {
+            file.read_exact_at(view.get_mut_slice(), offset)?;
+            view.update_prefix();
+            return Ok(view);
+        }
+
+        if !ring.completion().is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "ring has unconsumed completions",
+            ));
+        }
+
+        let data = view.get_mut_slice();
+        let ptr = data.as_mut_ptr();
+
+        let iovec = libc::iovec {
+            iov_base: ptr.cast(),
+            iov_len: data.len(),
+        };
+
+        // SAFETY: The heap allocation stays alive (and does not move)
+        // until we unregister it, see `ReadAt`
+        unsafe {
+            ring.submitter().register_buffers(&[iovec])?;
+        }
+
+        ReadAt {
+            ring,
+            file,
+            view: Some(view),
+            ptr,
+            offset,
+            filled: 0,
+            in_flight: false,
+        }
+        .read()
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(miri))]
+mod tests {
+    use crate::ByteView;
+    use io_uring::IoUring;
+    use std::{
+        io::Write,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    fn test_file(content: &[u8]) -> std::io::Result<std::fs::File> {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+        let path = std::env::temp_dir().join(format!(
+            "byteview-uring-{}-{}",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed),
+        ));
+
+        let mut file = std::fs::File::create(&path)?;
+        file.write_all(content)?;
+        drop(file);
+
+        let file = std::fs::File::open(&path)?;
+        std::fs::remove_file(&path)?;
+        Ok(file)
+    }
+
+    #[test]
+    fn uring_read() -> std::io::Result<()> {
+        // NOTE: io_uring may not be permitted in sandboxed environments
+        let Ok(mut ring) = IoUring::new(8) else {
+            return Ok(());
+        };
+
+        let content = b"helloworld_thisisaverylongstring".repeat(100);
+        let file = test_file(&content)?;
+
+        let view = ByteView::from_file_uring(&mut ring, &file, 0, content.len())?;
+        assert_eq!(&*view, content);
+
+        let view = ByteView::from_file_uring(&mut ring, &file, 11, 21)?;
+        assert_eq!(b"thisisaverylongstring", &*view);
+
+        let view = ByteView::from_file_uring(&mut ring, &file, 11, 4)?;
+        assert_eq!(b"this", &*view);
+
+        Ok(())
+    }
+
+    #[test]
+    fn uring_read_unconsumed_completions() -> std::io::Result<()> {
+        let Ok(mut ring) = IoUring::new(8) else {
+            return Ok(());
+        };
+
+        let file = test_file(b"helloworld_thisisaverylongstring")?;
+
+        // SAFETY: A no-op does not reference any buffer
+        unsafe {
+            ring.submission()
+                .push(&io_uring::opcode::Nop::new().build().user_data(42))
+                .expect("submission queue should not be full");
+        }
+        ring.submit_and_wait(1)?;
+
+        let err = ByteView::from_file_uring(&mut ring, &file, 0, 32).unwrap_err();
+        assert_eq!(std::io::ErrorKind::InvalidInput, err.kind());
+
+        // NOTE: The unrelated completion is left to its owner
+        assert_eq!(
+            Some(42),
+            ring.completion().next().map(|entry| entry.user_data())
+        );
+
+        let view = ByteView::from_file_uring(&mut ring, &file, 0, 32)?;
+        assert_eq!(b"helloworld_thisisaverylongstring", &*view);
+
+        Ok(())
+    }
+
+    #[test]
+    fn uring_read_eof() -> std::io::Result<()> {
+        let Ok(mut ring) = IoUring::new(8) else {
+            return Ok(());
+        };
+
+        let file = test_file(b"helloworld_thisisaverylongstring")?;
+
+        let err = ByteView::from_file_uring(&mut ring, &file, 10, 30).unwrap_err();
+        assert_eq!(std::io::ErrorKind::UnexpectedEof, err.kind());
+
+        Ok(())
+    }
+}
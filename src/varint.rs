@@ -0,0 +1,141 @@
+// Copyright (c) 2024-present, fjall-rs
+// This source code is licensed under both the Apache 2.0 and MIT License
+// (found in the LICENSE-* files in the repository)
+
+//! Variable-length integers (LEB128)
+//!
+//! Every byte stores 7 bits of the value, starting with the lowest bits,
+//! and has its highest bit set if more bytes follow.
+
+/// Maximum encoded size of a `u64`
+pub const MAX_LEN: usize = 10;
+
+/// Reasons why a variable-length integer could not be decoded
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum DecodeError {
+    /// The input ended in the middle of the integer
+    Truncated,
+
+    /// The integer does not fit in the requested amount of bits
+    Overflow,
+}
+
+impl From<DecodeError> for std::io::Error {
+    fn from(value: DecodeError) -> Self {
+        match value {
+            DecodeError::Truncated => Self::new(
+                std::io::ErrorKind::UnexpectedEof,
+                "not enough bytes remaining",
+            ),
+            DecodeError::Overflow => Self::new(std::io::ErrorKind::InvalidData, "varint too long"),
+        }
+    }
+}
+
+/// Encodes the value into the given buffer, returning the encoded bytes.
+pub fn encode(mut value: u64, buf: &mut [u8; MAX_LEN]) -> &[u8] {
+    let mut len = 0;
+
+    for byte in buf.iter_mut() {
+        // NOTE: Truncation is intended, we only want the lowest 7 bits
+        #[allow(clippy::cast_possible_truncation)]
+        {
+            *byte = (value as u8) & 0x7F;
+        }
+        value >>= 7;
+        len += 1;
+
+        if value == 0 {
+            break;
+        }
+
+        *byte |= 0x80;
+    }
+
+    buf.get(..len).unwrap_or_default()
+}
+
+/// Decodes an integer of at most `bits` bits from the start of the given bytes,
+/// returning the value and its encoded size.
+pub fn decode(bytes: &[u8], bits: u32) -> Result<(u64, usize), DecodeError> {
+    let max_len = bits.div_ceil(7) as usize;
+    let mut value = 0;
+
+    for (idx, &byte) in bytes.iter().enumerate().take(max_len) {
+        // NOTE: idx < max_len <= 10, so the shift is at most 63
+        #[allow(clippy::cast_possible_truncation)]
+        let shift = (idx * 7) as u32;
+
+        let chunk = u64::from(byte & 0x7F);
+
+        if shift + 7 > bits && chunk >> (bits - shift) != 0 {
+            return Err(DecodeError::Overflow);
+        }
+
+        value |= chunk << shift;
+
+        if byte & 0x80 == 0 {
+            return Ok((value, idx + 1));
+        }
+    }
+
+    if bytes.len() < max_len {
+        Err(DecodeError::Truncated)
+    } else {
+        Err(DecodeError::Overflow)
+    }
+}
+
+/// Decodes a `u32` from the start of the given bytes, returning the value and its encoded size.
+pub fn decode_u32(bytes: &[u8]) -> Result<(u32, usize), DecodeError> {
+    // NOTE: The value has at most 32 bits
+    #[allow(clippy::cast_possible_truncation)]
+    decode(bytes, u32::BITS).map(|(value, len)| (value as u32, len))
+}
+
+/// Decodes a `u64` from the start of the given bytes, returning the value and its encoded size.
+pub fn decode_u64(bytes: &[u8]) -> Result<(u64, usize), DecodeError> {
+    decode(bytes, u64::BITS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_u32, decode_u64, encode, DecodeError, MAX_LEN};
+
+    #[test]
+    fn varint_roundtrip() {
+        let mut buf = [0; MAX_LEN];
+
+        for value in [0, 1, 127, 128, 300, u64::from(u32::MAX), u64::MAX] {
+            let bytes = encode(value, &mut buf).to_vec();
+            assert_eq!(Ok((value, bytes.len())), decode_u64(&bytes));
+
+            match u32::try_from(value) {
+                Ok(value) => assert_eq!(Ok((value, bytes.len())), decode_u32(&bytes)),
+                Err(_) => assert_eq!(Err(DecodeError::Overflow), decode_u32(&bytes)),
+            }
+        }
+
+        assert_eq!(&[0xAC, 0x02], encode(300, &mut buf));
+        assert_eq!(MAX_LEN, encode(u64::MAX, &mut buf).len());
+    }
+
+    #[test]
+    fn varint_malformed() {
+        assert_eq!(Err(DecodeError::Truncated), decode_u64(&[]));
+        assert_eq!(Err(DecodeError::Truncated), decode_u64(&[0x80, 0x80]));
+        assert_eq!(Err(DecodeError::Truncated), decode_u32(&[0xFF; 4]));
+        assert_eq!(Err(DecodeError::Overflow), decode_u32(&[0xFF; 5]));
+        assert_eq!(Err(DecodeError::Overflow), decode_u64(&[0xFF; 11]));
+
+        // NOTE: The last byte may only contribute the remaining bits
+        assert_eq!(
+            Err(DecodeError::Overflow),
+            decode_u32(&[0xFF, 0xFF, 0xFF, 0xFF, 0x1F]),
+        );
+        assert_eq!(
+            Ok((u32::MAX, 5)),
+            decode_u32(&[0xFF, 0xFF, 0xFF, 0xFF, 0x0F]),
+        );
+    }
+}
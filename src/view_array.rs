@@ -0,0 +1,454 @@
+// Copyright (c) 2024-present, fjall-rs
+// This source code is licensed under both the Apache 2.0 and MIT License
+// (found in the LICENSE-* files in the repository)
+
+use crate::ByteView;
+use std::collections::HashMap;
+
+/// Maximum length of values that are stored inside the packed view itself
+const PACKED_INLINE_SIZE: usize = 12;
+
+/// A 16-byte view of a single value (in the same layout as Apache Arrow's `BinaryView`)
+///
+/// Short values are stored inline, longer values store their first 4 bytes,
+/// and the data buffer and offset they are located at.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[repr(C)]
+pub struct PackedView {
+    len: u32,
+    data: [u8; PACKED_INLINE_SIZE],
+}
+
+impl PackedView {
+    fn inline(bytes: &[u8]) -> Self {
+        debug_assert!(bytes.len() <= PACKED_INLINE_SIZE);
+
+        let mut data = [0; PACKED_INLINE_SIZE];
+
+        for (dst, src) in data.iter_mut().zip(bytes) {
+            *dst = *src;
+        }
+
+        // NOTE: We are not larger than the inline size
+        #[allow(clippy::cast_possible_truncation)]
+        Self {
+            len: bytes.len() as u32,
+            data,
+        }
+    }
+
+    fn long(bytes: &[u8], buffer_idx: u32, offset: u32) -> Self {
+        debug_assert!(bytes.len() > PACKED_INLINE_SIZE);
+
+        let mut data = [0; PACKED_INLINE_SIZE];
+
+        for (dst, src) in data.iter_mut().zip(
+            bytes
+                .iter()
+                .take(4)
+                .copied()
+                .chain(buffer_idx.to_le_bytes())
+                .chain(offset.to_le_bytes()),
+        ) {
+            *dst = src;
+        }
+
+        // NOTE: Values are slices, so their length fits into a u32
+        #[allow(clippy::cast_possible_truncation)]
+        Self {
+            len: bytes.len() as u32,
+            data,
+        }
+    }
+
+    const fn len(&self) -> usize {
+        self.len as usize
+    }
+
+    /// Returns the data buffer index and offset of a long value.
+    const fn location(&self) -> (usize, usize) {
+        let [_, _, _, _, b0, b1, b2, b3, o0, o1, o2, o3] = self.data;
+
+        (
+            u32::from_le_bytes([b0, b1, b2, b3]) as usize,
+            u32::from_le_bytes([o0, o1, o2, o3]) as usize,
+        )
+    }
+
+    /// Returns the view in Arrow's `u128` representation.
+    #[cfg(all(feature = "arrow", not(feature = "local")))]
+    pub fn to_u128(self) -> u128 {
+        let mut bytes = [0; 16];
+
+        for (dst, src) in bytes
+            .iter_mut()
+            .zip(self.len.to_le_bytes().into_iter().chain(self.data))
+        {
+            *dst = src;
+        }
+
+        u128::from_le_bytes(bytes)
+    }
+}
+
+/// A dense, columnar collection of slices
+///
+/// Instead of storing a 24-byte [`ByteView`] per value, every value is stored as a 16-byte view
+/// (in the same layout as Apache Arrow's `BinaryView`), which either contains the value itself
+/// (up to 12 bytes), or points into one of the array's shared data buffers.
+///
+/// Slices that reference the same heap allocation share a single data buffer,
+/// as long as they are pushed in ascending order of their location in the allocation
+/// (e.g. the records of a [`crate::Segment`]).
+/// Short values that do not fit into a view are copied into an extra data buffer.
+///
+/// Null values are tracked in a validity bitmap, which is only allocated once
+/// the first null value is pushed.
+///
+/// # Examples
+///
+/// ```
+/// # use byteview::{ByteView, ViewArray};
+/// let buffer = ByteView::from("helloworld_thisisaverylongstring_helloworld_thisisanotherlongstring");
+///
+/// let mut array = ViewArray::new();
+/// array.push(&buffer.slice(..32));
+/// array.push_null();
+/// array.push(&ByteView::from("abc"));
+/// array.push(&buffer.slice(33..));
+///
+/// assert_eq!(4, array.len());
+/// assert_eq!(Some(buffer.slice(33..)), array.get(3));
+/// assert!(array.is_null(1));
+/// ```
+#[derive(Clone, Default)]
+pub struct ViewArray {
+    pub(crate) views: Vec<PackedView>,
+    pub(crate) buffers: Vec<ByteView>,
+
+    /// Index of the data buffer of every referenced heap allocation (keyed by address)
+    regions: HashMap<usize, u32>,
+
+    /// Index of the data buffer that short values are copied into
+    copied: Option<u32>,
+
+    /// One bit per value, which is set if the value is not null
+    validity: Option<Vec<u8>>,
+}
+
+impl ViewArray {
+    /// Creates a new, empty array.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new, empty array that can hold at least `capacity` values without reallocating its views.
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            views: Vec::with_capacity(capacity),
+            ..Default::default()
+        }
+    }
+
+    /// Appends a value.
+    ///
+    /// The value's bytes are not copied, unless the value is inlined.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are more than `u32::MAX` data buffers.
+    pub fn push(&mut self, view: &ByteView) {
+        self.push_validity(true);
+
+        if view.len() <= PACKED_INLINE_SIZE {
+            self.views.push(PackedView::inline(view));
+            return;
+        }
+
+        let (buffer_idx, offset) = match view.heap_ptr() {
+            Some(heap) => self.locate(heap.as_ptr() as usize, view),
+            None => self.copy(view),
+        };
+
+        self.views.push(PackedView::long(view, buffer_idx, offset));
+    }
+
+    /// Appends a null value.
+    pub fn push_null(&mut self) {
+        self.push_validity(false);
+        self.views.push(PackedView::default());
+    }
+
+    fn push_validity(&mut self, valid: bool) {
+        let idx = self.views.len();
+
+        if valid && self.validity.is_none() {
+            return;
+        }
+
+        // NOTE: All values pushed before the first null value are valid
+        let bitmap = self
+            .validity
+            .get_or_insert_with(|| vec![0xFF; idx.div_ceil(8)]);
+
+        if bitmap.len() <= idx / 8 {
+            bitmap.push(0);
+        }
+
+        if let Some(byte) = bitmap.get_mut(idx / 8) {
+            let mask = 1 << (idx % 8);
+
+            if valid {
+                *byte |= mask;
+            } else {
+                *byte &= !mask;
+            }
+        }
+    }
+
+    fn push_buffer(&mut self, buffer: ByteView) -> u32 {
+        let Ok(idx) = u32::try_from(self.buffers.len()) else {
+            panic!("too many data buffers");
+        };
+
+        self.buffers.push(buffer);
+        idx
+    }
+
+    /// Returns the data buffer and offset of a heap-backed value,
+    /// extending the data buffer of its heap allocation if needed.
+    fn locate(&mut self, key: usize, view: &ByteView) -> (u32, u32) {
+        let existing = self.regions.get(&key).and_then(|&idx| {
+            let buffer = self.buffers.get_mut(idx as usize)?;
+
+            if view.get_ptr() < buffer.get_ptr() {
+                return None;
+            }
+
+            if view.get_ptr().wrapping_add(view.len()) > buffer.get_ptr().wrapping_add(buffer.len())
+            {
+                // SAFETY: Both slices reference the same heap allocation,
+                // and the value ends after the data buffer
+                *buffer = unsafe { buffer.extend_to(view) };
+            }
+
+            // SAFETY: Both slices reference the same heap allocation
+            // NOTE: Heap allocations are no larger than u32::MAX
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let offset = unsafe { view.get_ptr().offset_from(buffer.get_ptr()) } as u32;
+
+            Some((idx, offset))
+        });
+
+        existing.unwrap_or_else(|| {
+            let idx = self.push_buffer(view.clone());
+            self.regions.insert(key, idx);
+            (idx, 0)
+        })
+    }
+
+    /// Copies an inlined value into the copy buffer.
+    fn copy(&mut self, view: &ByteView) -> (u32, u32) {
+        let existing = self.copied.and_then(|idx| {
+            let buffer = self.buffers.get_mut(idx as usize)?;
+
+            let Ok(offset) = u32::try_from(buffer.len()) else {
+                unreachable!("slice length should fit into a u32");
+            };
+
+            if buffer.len() + view.len() > u32::MAX as usize {
+                return None;
+            }
+
+            buffer.extend_from_slice(view);
+            Some((idx, offset))
+        });
+
+        existing.unwrap_or_else(|| {
+            let idx = self.push_buffer(view.clone());
+            self.copied = Some(idx);
+            (idx, 0)
+        })
+    }
+
+    /// Returns the amount of values.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.views.len()
+    }
+
+    /// Returns `true` if the array has no values.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.views.is_empty()
+    }
+
+    /// Returns `true` if the value at the given index is null.
+    #[must_use]
+    pub fn is_null(&self, idx: usize) -> bool {
+        self.validity.as_ref().is_some_and(|bitmap| {
+            bitmap
+                .get(idx / 8)
+                .is_some_and(|byte| byte & (1 << (idx % 8)) == 0)
+        })
+    }
+
+    /// Returns the value at the given index.
+    ///
+    /// Returns `None` if the index is out of bounds, or the value is null.
+    /// The returned slice references the array's data buffer, without copying.
+    #[must_use]
+    pub fn get(&self, idx: usize) -> Option<ByteView> {
+        let view = self.views.get(idx)?;
+
+        if self.is_null(idx) {
+            return None;
+        }
+
+        let len = view.len();
+
+        if len <= PACKED_INLINE_SIZE {
+            return view.data.get(..len).map(ByteView::new);
+        }
+
+        let (buffer_idx, offset) = view.location();
+
+        self.buffers
+            .get(buffer_idx)
+            .map(|buffer| buffer.slice(offset..(offset + len)))
+    }
+
+    /// Returns an iterator over all values, yielding `None` for null values.
+    #[must_use]
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = Option<ByteView>> + '_ {
+        (0..self.len()).map(|idx| self.get(idx))
+    }
+
+    /// Converts the array into a list of its values.
+    #[must_use]
+    pub fn into_views(self) -> Vec<Option<ByteView>> {
+        self.iter().collect()
+    }
+}
+
+impl std::fmt::Debug for ViewArray {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl FromIterator<ByteView> for ViewArray {
+    fn from_iter<T: IntoIterator<Item = ByteView>>(iter: T) -> Self {
+        let mut array = Self::new();
+
+        for view in iter {
+            array.push(&view);
+        }
+
+        array
+    }
+}
+
+impl FromIterator<Option<ByteView>> for ViewArray {
+    fn from_iter<T: IntoIterator<Item = Option<ByteView>>>(iter: T) -> Self {
+        let mut array = Self::new();
+
+        for view in iter {
+            match view {
+                Some(view) => array.push(&view),
+                None => array.push_null(),
+            }
+        }
+
+        array
+    }
+}
+
+impl From<Vec<ByteView>> for ViewArray {
+    fn from(value: Vec<ByteView>) -> Self {
+        value.into_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ViewArray;
+    use crate::ByteView;
+
+    #[test]
+    fn view_array_shared_buffers() {
+        let buffer =
+            ByteView::from("helloworld_thisisaverylongstring_helloworld_thisisanotherlongstring");
+        let other = ByteView::from("helloworld_thisisaverylongstring");
+
+        let slices = vec![
+            buffer.slice(..10),
+            buffer.slice(..32),
+            ByteView::from("helloworld_thisis"),
+            other.clone(),
+            buffer.slice(33..),
+            ByteView::from_static(b"helloworld_thisisastaticstring"),
+            ByteView::from("helloworld_thisisa"),
+            ByteView::from(""),
+        ];
+
+        let array = ViewArray::from(slices.clone());
+        assert_eq!(slices.len(), array.len());
+        assert_eq!(4, array.buffers.len());
+        assert_eq!(
+            b"helloworld_thisisaverylongstring_helloworld_thisisanotherlongstring",
+            &*array.buffers[0],
+        );
+        assert_eq!(b"helloworld_thisishelloworld_thisisa", &*array.buffers[1]);
+        assert_eq!(other.as_ptr(), array.buffers[2].as_ptr());
+
+        let value = array.get(4).unwrap();
+        assert_eq!(buffer.slice(33..), value);
+        assert_eq!(buffer[33..].as_ptr(), value.as_ptr());
+        assert!(array.get(8).is_none());
+
+        assert_eq!(
+            slices,
+            array
+                .into_views()
+                .into_iter()
+                .map(Option::unwrap)
+                .collect::<Vec<_>>(),
+        );
+    }
+
+    #[test]
+    fn view_array_nulls() {
+        let mut array = (0..20)
+            .map(|i| (i % 3 != 0).then(|| ByteView::from(format!("value_{i}").as_str())))
+            .collect::<ViewArray>();
+        array.push(&ByteView::from("helloworld_thisisaverylongstring"));
+
+        assert_eq!(21, array.len());
+        assert!(array.is_null(0));
+        assert!(array.is_null(18));
+        assert!(!array.is_null(19));
+        assert!(!array.is_null(20));
+        assert_eq!(Some(ByteView::from("value_19")), array.get(19));
+        assert_eq!(7, array.iter().filter(Option::is_none).count());
+
+        let array = ViewArray::from(vec![ByteView::from("abc")]);
+        assert!(!array.is_null(0));
+        assert!(array.validity.is_none());
+    }
+
+    #[test]
+    fn view_array_descending() {
+        let buffer =
+            ByteView::from("helloworld_thisisaverylongstring_helloworld_thisisanotherlongstring");
+
+        let array = [buffer.slice(33..), buffer.slice(..32), buffer.slice(33..60)]
+            .into_iter()
+            .collect::<ViewArray>();
+
+        assert_eq!(2, array.buffers.len());
+        assert_eq!(Some(buffer.slice(33..60)), array.get(2));
+    }
+}
@@ -0,0 +1,207 @@
+// Copyright (c) 2024-present, fjall-rs
+// This source code is licensed under both the Apache 2.0 and MIT License
+// (found in the LICENSE-* files in the repository)
+
+use crate::ByteView;
+use std::{mem::ManuallyDrop, sync::atomic::Ordering};
+
+/// A weak reference to a [`ByteView`]
+///
+/// Does not keep the slice's bytes alive, so it needs to be upgraded
+/// using [`WeakByteView::upgrade`] to access them.
+///
+/// Buffers adopted from a `Vec<u8>` or a [`crate::ByteSource`] are dropped as soon as
+/// the last strong reference is dropped.
+/// Heap allocations made by [`ByteView`] itself contain both the ref counts and the bytes,
+/// so (like with [`std::sync::Weak`]) their memory is freed once the last weak reference is dropped, too.
+///
+/// Inlined slices and slices referencing static data can always be upgraded.
+///
+/// # Examples
+///
+/// ```
+/// # use byteview::ByteView;
+/// let slice = ByteView::from("helloworld_thisisaverylongstring");
+/// let weak = slice.downgrade();
+///
+/// assert_eq!(Some(slice.clone()), weak.upgrade());
+///
+/// drop(slice);
+/// assert!(weak.upgrade().is_none());
+/// ```
+pub struct WeakByteView {
+    view: ManuallyDrop<ByteView>,
+}
+
+#[cfg(not(feature = "local"))]
+#[allow(clippy::non_send_fields_in_send_ty)]
+unsafe impl Send for WeakByteView {}
+#[cfg(not(feature = "local"))]
+#[allow(clippy::non_send_fields_in_send_ty)]
+unsafe impl Sync for WeakByteView {}
+
+impl WeakByteView {
+    /// Tries to get a strong reference to the slice.
+    ///
+    /// Returns `None` if all strong references were dropped.
+    #[must_use]
+    pub fn upgrade(&self) -> Option<ByteView> {
+        if let Some(heap_region) = self.view.heap_region() {
+            if !heap_region.try_increment_ref_count() {
+                return None;
+            }
+        }
+
+        // SAFETY: We incremented the ref count
+        // (or the slice is inlined or static, so copying it is fine anyway)
+        Some(unsafe { std::ptr::read(&*self.view) })
+    }
+
+    /// Returns the amount of strong references to the slice.
+    #[must_use]
+    pub fn strong_count(&self) -> u64 {
+        self.view.heap_region().map_or(1, |heap_region| {
+            heap_region.ref_count.load(Ordering::Acquire)
+        })
+    }
+}
+
+impl Clone for WeakByteView {
+    fn clone(&self) -> Self {
+        if let Some(heap_region) = self.view.heap_region() {
+            heap_region.increment_weak_count();
+        }
+
+        Self {
+            // SAFETY: We incremented the weak count, and never drop the copied slice
+            view: ManuallyDrop::new(unsafe { std::ptr::read(&*self.view) }),
+        }
+    }
+}
+
+impl Drop for WeakByteView {
+    fn drop(&mut self) {
+        // SAFETY: We are releasing our own weak reference
+        unsafe {
+            self.view.release_weak();
+        }
+    }
+}
+
+impl std::fmt::Debug for WeakByteView {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "(Weak)")
+    }
+}
+
+impl ByteView {
+    /// Creates a weak reference to the slice.
+    #[must_use]
+    pub fn downgrade(&self) -> WeakByteView {
+        if let Some(heap_region) = self.heap_region() {
+            heap_region.increment_weak_count();
+        }
+
+        WeakByteView {
+            // SAFETY: We incremented the weak count, and never drop the copied slice
+            view: ManuallyDrop::new(unsafe { std::ptr::read(self) }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ByteView;
+    use std::sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    };
+
+    #[test]
+    fn weak_upgrade() {
+        let slice = ByteView::from("helloworld_thisisaverylongstring").slice(5..);
+        let weak = slice.downgrade();
+        let weak2 = weak.clone();
+        assert_eq!(1, weak.strong_count());
+
+        let upgraded = weak.upgrade().unwrap();
+        assert_eq!(b"world_thisisaverylongstring", &*upgraded);
+        assert_eq!(slice.as_ptr(), upgraded.as_ptr());
+        assert_eq!(2, slice.ref_count());
+
+        drop(slice);
+        drop(weak);
+        assert_eq!(1, weak2.strong_count());
+
+        drop(upgraded);
+        assert_eq!(0, weak2.strong_count());
+        assert!(weak2.upgrade().is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "zeroize")]
+    fn weak_zeroize() {
+        use zeroize::Zeroize;
+
+        let mut slice = ByteView::from("helloworld_thisisaverylongstring");
+        let weak = slice.downgrade();
+        let copy = slice.clone();
+
+        slice.zeroize();
+        assert!(slice.is_empty());
+        assert_eq!(b"helloworld_thisisaverylongstring", &*copy);
+
+        drop(copy);
+        assert!(weak.upgrade().is_none());
+
+        // SAFETY: The weak reference keeps the heap allocation alive
+        let data = unsafe { std::slice::from_raw_parts(weak.view.get_ptr(), 32) };
+        assert_eq!([0; 32], data);
+    }
+
+    #[test]
+    fn weak_inline() {
+        let slice = ByteView::from("abc");
+        let weak = slice.downgrade();
+        drop(slice);
+        assert_eq!(Some(ByteView::from("abc")), weak.upgrade());
+    }
+
+    #[test]
+    fn weak_prevents_mutation() {
+        let mut slice = ByteView::from("helloworld_thisisaverylongstring");
+        assert!(slice.get_mut().is_some());
+
+        let weak = slice.downgrade();
+        assert!(slice.get_mut().is_none());
+
+        drop(weak);
+        assert!(slice.get_mut().is_some());
+    }
+
+    #[test]
+    fn weak_drops_source() {
+        struct Source(Arc<AtomicBool>, Vec<u8>);
+
+        impl Drop for Source {
+            fn drop(&mut self) {
+                self.0.store(true, Ordering::Release);
+            }
+        }
+
+        impl crate::ByteSource for Source {
+            fn as_bytes(&self) -> &[u8] {
+                &self.1
+            }
+        }
+
+        let dropped = Arc::new(AtomicBool::new(false));
+
+        let slice = ByteView::from_source(Source(dropped.clone(), vec![0; 100]));
+        let weak = slice.downgrade();
+
+        drop(slice);
+        assert!(dropped.load(Ordering::Acquire));
+        assert!(weak.upgrade().is_none());
+    }
+}
@@ -0,0 +1,253 @@
+// Copyright (c) 2024-present, fjall-rs
+// This source code is licensed under both the Apache 2.0 and MIT License
+// (found in the LICENSE-* files in the repository)
+
+use crate::{byteview_mut::impl_put_appending, varint, ByteView};
+use std::io::{Error, ErrorKind};
+
+/// Writes bytes directly into a [`ByteView`]'s heap allocation
+///
+/// Unlike [`crate::ByteViewMut`], which adopts a `Vec<u8>` when frozen,
+/// the bytes are written into an allocation owned by [`ByteView`] itself,
+/// so finishing the writer is free, and the ref count lives next to the bytes.
+///
+/// # Examples
+///
+/// ```
+/// # use byteview::ByteViewWriter;
+/// use std::io::Write;
+///
+/// let mut writer = ByteViewWriter::with_capacity(64);
+/// write!(writer, "helloworld_{}", "thisisaverylongstring")?;
+///
+/// let slice = writer.finish();
+/// assert_eq!(b"helloworld_thisisaverylongstring", &*slice);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+///
+/// Values can be encoded directly, to be decoded again using a [`crate::ByteReader`]:
+///
+/// ```
+/// # use byteview::{ByteReader, ByteViewWriter};
+/// let mut writer = ByteViewWriter::new();
+/// writer.put_u8(7);
+/// writer.put_var_u64(300);
+/// writer.put_slice(b"abc");
+///
+/// let mut reader = ByteReader::new(writer.finish());
+/// assert_eq!(7, reader.get_u8()?);
+/// assert_eq!(300, reader.get_var_u64()?);
+/// assert_eq!(b"abc", &*reader.get_slice(3)?);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ByteViewWriter {
+    view: ByteView,
+}
+
+impl ByteViewWriter {
+    /// Creates a new, empty writer.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new, empty writer that can hold at least `capacity` bytes without reallocating.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the capacity does not fit in a u32 (4 GiB).
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            view: ByteView::with_capacity(capacity),
+        }
+    }
+
+    /// Returns the amount of bytes the writer can hold without reallocating.
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.view.capacity()
+    }
+
+    /// Reserves capacity for at least `additional` more bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the resulting capacity does not fit in a u32 (4 GiB).
+    pub fn reserve(&mut self, additional: usize) {
+        let Some(capacity) = self.len().checked_add(additional) else {
+            panic!("byte slice too long");
+        };
+
+        if capacity > self.capacity() {
+            let mut view = ByteView::with_capacity(capacity);
+            view.extend_from_slice(&self.view);
+            self.view = view;
+        }
+    }
+
+    /// Returns the amount of bytes written.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.view.len()
+    }
+
+    /// Returns `true` if no bytes were written.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.view.is_empty()
+    }
+
+    /// Returns the bytes written so far.
+    #[must_use]
+    pub fn as_slice(&self) -> &[u8] {
+        &self.view
+    }
+
+    /// Appends the given bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the resulting length does not fit in a u32 (4 GiB).
+    pub fn put_slice(&mut self, bytes: &[u8]) {
+        self.view.extend_from_slice(bytes);
+    }
+
+    /// Appends a `u32` as a variable-length integer (LEB128),
+    /// which takes up 1 byte for values below 128, and at most 5 bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the resulting length does not fit in a u32 (4 GiB).
+    pub fn put_var_u32(&mut self, value: u32) {
+        self.put_var_u64(value.into());
+    }
+
+    /// Appends a `u64` as a variable-length integer (LEB128),
+    /// which takes up 1 byte for values below 128, and at most 10 bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the resulting length does not fit in a u32 (4 GiB).
+    pub fn put_var_u64(&mut self, value: u64) {
+        let mut buf = [0; varint::MAX_LEN];
+        self.put_slice(varint::encode(value, &mut buf));
+    }
+
+    impl_put_appending! {
+        ///
+        /// # Panics
+        ///
+        /// Panics if the resulting length does not fit in a u32 (4 GiB).
+    }
+
+    /// Converts the writer into an immutable slice, without copying.
+    #[must_use]
+    pub fn finish(self) -> ByteView {
+        self.view
+    }
+}
+
+impl std::io::Write for ByteViewWriter {
+    /// Appends the given bytes.
+    ///
+    /// Returns an error of kind [`ErrorKind::InvalidInput`] if the resulting length
+    /// would not fit in a u32 (4 GiB).
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if self.len().saturating_add(buf.len()) > u32::MAX as usize {
+            return Err(Error::new(ErrorKind::InvalidInput, "byte slice too long"));
+        }
+
+        self.view.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl From<ByteViewWriter> for ByteView {
+    fn from(value: ByteViewWriter) -> Self {
+        value.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ByteViewWriter;
+    use std::io::Write;
+
+    #[test]
+    fn writer_reserve() -> std::io::Result<()> {
+        let mut writer = ByteViewWriter::new();
+        assert!(writer.is_empty());
+
+        writer.reserve(100);
+        assert!(writer.capacity() >= 100);
+
+        writer.write_all(b"helloworld_")?;
+        let ptr = writer.as_slice().as_ptr();
+
+        writer.write_all(b"thisisaverylongstring")?;
+        assert_eq!(32, writer.len());
+        assert_eq!(ptr, writer.as_slice().as_ptr());
+
+        let mut slice = writer.finish();
+        assert_eq!(b"helloworld_thisisaverylongstring", &*slice);
+        assert_eq!(ptr, slice.as_ptr());
+        assert!(slice.get_mut().is_some());
+
+        Ok(())
+    }
+
+    #[test]
+    fn writer_grow() -> std::io::Result<()> {
+        let mut writer = ByteViewWriter::with_capacity(4);
+        let mut expected = Vec::new();
+
+        for i in 0..100 {
+            write!(writer, "{i},")?;
+            write!(expected, "{i},")?;
+        }
+        writer.flush()?;
+
+        assert_eq!(expected, &*writer.finish());
+
+        Ok(())
+    }
+
+    #[test]
+    fn writer_put() -> std::io::Result<()> {
+        use crate::ByteReader;
+
+        let values = [0, 1, 127, 128, 300, u64::from(u32::MAX), u64::MAX];
+
+        let mut writer = ByteViewWriter::new();
+        writer.put_u16_be(0x1234);
+        writer.put_i64_le(-5);
+        for value in values {
+            writer.put_var_u64(value);
+        }
+        writer.put_var_u32(u32::MAX);
+        writer.put_slice(b"helloworld_thisisaverylongstring");
+
+        let view = writer.finish();
+        assert_eq!(&[0x12, 0x34], &view[..2]);
+
+        let mut reader = ByteReader::new(view);
+        assert_eq!(0x1234, reader.get_u16_be()?);
+        assert_eq!(-5, reader.get_i64_le()?);
+        for value in values {
+            assert_eq!(value, reader.get_var_u64()?);
+        }
+        assert_eq!(u32::MAX, reader.get_var_u32()?);
+        assert_eq!(
+            b"helloworld_thisisaverylongstring",
+            &*reader.into_remaining(),
+        );
+
+        Ok(())
+    }
+}
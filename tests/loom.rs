@@ -0,0 +1,78 @@
+#![cfg(loom)]
+
+use byteview::ByteView;
+use loom::thread;
+
+#[test]
+fn loom_clone_drop() {
+    loom::model(|| {
+        let slice = ByteView::from("helloworld_thisisaverylongstring");
+        let copy = slice.clone();
+
+        let handle = thread::spawn(move || {
+            let copy2 = copy.slice(5..);
+            drop(copy);
+            copy2.len()
+        });
+
+        drop(slice);
+        assert_eq!(27, handle.join().unwrap());
+    });
+}
+
+#[test]
+fn loom_get_mut() {
+    loom::model(|| {
+        let mut slice = ByteView::from("helloworld_thisisaverylongstring");
+        let copy = slice.clone();
+
+        let handle = thread::spawn(move || drop(copy));
+
+        if let Some(mut mutator) = slice.get_mut() {
+            mutator[0] = b'j';
+        }
+
+        handle.join().unwrap();
+        assert!(slice.get_mut().is_some());
+    });
+}
+
+#[test]
+fn loom_weak_upgrade() {
+    loom::model(|| {
+        let slice = ByteView::from("helloworld_thisisaverylongstring");
+        let weak = slice.downgrade();
+
+        let handle = thread::spawn(move || weak.upgrade().map(|slice| slice.len()));
+
+        drop(slice);
+
+        if let Some(len) = handle.join().unwrap() {
+            assert_eq!(32, len);
+        }
+    });
+}
+
+#[test]
+fn loom_weak_unique() {
+    loom::model(|| {
+        let mut slice = ByteView::from("helloworld_thisisaverylongstring");
+        let copy = slice.clone();
+
+        let handle = thread::spawn(move || {
+            let weak = copy.downgrade();
+            drop(copy);
+            weak.upgrade()
+        });
+
+        // NOTE: Some other reference (strong or weak) is alive until the thread is joined
+        assert!(slice.get_mut().is_none());
+
+        let upgraded = handle.join().unwrap();
+        assert!(upgraded.is_some());
+        assert!(slice.get_mut().is_none());
+
+        drop(upgraded);
+        assert!(slice.get_mut().is_some());
+    });
+}